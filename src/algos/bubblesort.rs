@@ -1,4 +1,4 @@
-use crate::unchecked_tools::SliceUnchecked;
+use crate::unchecked_tools::{Moves, SliceUnchecked};
 
 pub fn bubblesort<T: Ord>(array: &mut[T]) {
 	unsafe {
@@ -14,3 +14,20 @@ pub fn bubblesort<T: Ord>(array: &mut[T]) {
 		}
 	}
 }
+
+// only swaps, so T doesn't have to be Copy
+fn bubblesort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], is_less: &mut F, moves: &mut M) {
+	let mut swapped = true;
+	while swapped {
+		swapped = false;
+		for i in 1..array.len() {
+			if is_less(&array[i], &array[i - 1]) {
+				array.swap(i - 1, i);
+				moves.swapped(1);
+				swapped = true;
+			}
+		}
+	}
+}
+
+by_entry_points!(bubblesort_by, bubblesort_by_key, bubblesort_less);
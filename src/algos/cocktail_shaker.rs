@@ -1,6 +1,9 @@
 use crate::unchecked_tools::SliceUnchecked;
 
 pub fn cocktail_shaker<T: Ord>(array: &mut[T]) {
+	if array.len() <= 1 {
+		return;
+	}
 	unsafe {
 		let mut lower = 0 as usize; // first unsorted element
 		let mut upper = array.len() - 1; // last unsorted element
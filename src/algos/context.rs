@@ -0,0 +1,37 @@
+// per-run context for randomized algorithms
+// sorts are plain fn(&mut [T]) so the harness can't hand them an rng directly, it sets a seed for the
+// current thread before each run instead (derived from the rng seed and test index, like the test
+// vectors) and randomized algorithms seed their rng from it, so any run can be replayed exactly
+// randomized algorithms also have *_with_rng versions taking the rng as a parameter
+
+use std::cell::Cell;
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+// outside the harness (tests, one-off calls) runs are still reproducible
+const DEFAULT_SEED: u64 = 0;
+
+thread_local! {
+	static SEED: Cell<u64> = const { Cell::new(DEFAULT_SEED) };
+}
+
+// puts the previous seed back, even if the sort panics
+struct Restore(u64);
+
+impl Drop for Restore {
+	fn drop(&mut self) {
+		SEED.with(|s| s.set(self.0));
+	}
+}
+
+// runs f with seed as the context for randomized algorithms on this thread
+pub fn with_seed<R>(seed: u64, f: impl FnOnce() -> R) -> R {
+	let _restore = Restore(SEED.with(|s| s.replace(seed)));
+	f()
+}
+
+// a fresh rng seeded from the current context
+pub fn rng() -> SmallRng {
+	SmallRng::seed_from_u64(SEED.with(|s| s.get()))
+}
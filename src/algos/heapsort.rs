@@ -1,6 +1,10 @@
+use std::mem::ManuallyDrop;
+use std::ptr;
+
 use crate::algos;
 use crate::utils::compare_and_swap;
-use crate::unchecked_tools::SliceUnchecked;
+use crate::operations::{CountMoves, Counted};
+use crate::unchecked_tools::{Hole, Moves, SliceUnchecked, Uncounted};
 
 // node's children are at 2i + 1 and 2i + 2
 // parent is at (i - 1) / 2
@@ -121,6 +125,11 @@ fn sort_down_guaranteed_right_child<T: Ord + Copy>(array: &mut[T]) {
 }
 
 pub fn heapsort_bottom_up_optimized<T: Ord + Copy>(array: &mut[T]) {
+	// the heap is built assuming the root has two children
+	if array.len() < 3 {
+		algos::insertionsort(array);
+		return;
+	}
 	// build heap
 	for i in (0..=(array.len() - 3) / 2).rev() {
 		sink_guaranteed_right_child(array, i);
@@ -131,3 +140,52 @@ pub fn heapsort_bottom_up_optimized<T: Ord + Copy>(array: &mut[T]) {
 	// extract sorted array
 	sort_down_guaranteed_right_child(array);
 }
+
+// sink for types that aren't Copy, the value being sunk is held in a hole instead of copied around
+unsafe fn sink_move<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], mut i: usize, is_less: &mut F,
+														   moves: &mut M) {
+	let p = array.as_mut_ptr();
+	let value = ManuallyDrop::new(ptr::read(p.add(i)));
+	let mut hole = Hole { src: &*value, dest: p.add(i) };
+	// reading the value out and the hole writing it back
+	moves.moved(2);
+	while 2 * i + 1 < array.len() {
+		let l = 2 * i + 1;
+		let r = 2 * i + 2;
+		let target = if r < array.len() && is_less(&*p.add(l), &*p.add(r)) { r } else { l };
+		if !is_less(&*value, &*p.add(target)) {
+			break;
+		}
+		ptr::copy_nonoverlapping(p.add(target), hole.dest, 1);
+		moves.moved(1);
+		hole.dest = p.add(target);
+		i = target;
+	}
+	// dropping the hole writes the value into place
+}
+
+pub fn heapsort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], is_less: &mut F, moves: &mut M) {
+	unsafe {
+		// build heap
+		for i in (0..array.len() / 2).rev() {
+			sink_move(array, i, is_less, moves);
+		}
+		// extraction
+		for i in (1..array.len()).rev() {
+			array.swap(0, i);
+			moves.swapped(1);
+			sink_move(&mut array[..i], 0, is_less, moves);
+		}
+	}
+}
+
+pub fn heapsort_move<T: Ord>(array: &mut [T]) {
+	heapsort_less(array, &mut T::lt, &mut Uncounted);
+}
+
+// heapsort_move for --operations, counts moves and swaps as well as comparisons
+pub fn heapsort_move_counted(array: &mut [Counted]) {
+	heapsort_less(array, &mut Counted::lt, &mut CountMoves);
+}
+
+by_entry_points!(heapsort_by, heapsort_by_key, heapsort_less);
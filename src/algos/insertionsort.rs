@@ -1,3 +1,9 @@
+use std::mem::ManuallyDrop;
+use std::ptr;
+
+use crate::operations::{CountMoves, Counted};
+use crate::unchecked_tools::{Hole, Moves, Uncounted};
+
 pub fn insertionsort<T: Ord + Copy>(array: &mut [T]) {
 	unsafe {
 		for mut i in 1..array.len() {
@@ -25,3 +31,52 @@ pub fn insertionsort_boundary_checked<T: Ord + Copy>(array: &mut [T]) {
 		}
 	}
 }
+
+// moves array[i] left into the sorted array[..i] (every gap-th element of it)
+// only moves elements (through a hole) instead of copying them, so T doesn't have to be Copy and a
+// panicking comparison leaves every element in the slice
+unsafe fn insert_tail<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], i: usize, gap: usize, is_less: &mut F,
+															   moves: &mut M) {
+	let p = array.as_mut_ptr();
+	if !is_less(&*p.add(i), &*p.add(i - gap)) {
+		return;
+	}
+	let value = ManuallyDrop::new(ptr::read(p.add(i)));
+	let mut hole = Hole { src: &*value, dest: p.add(i - gap) };
+	ptr::copy_nonoverlapping(p.add(i - gap), p.add(i), 1);
+	// reading the value out, that copy, and the hole writing it back
+	moves.moved(3);
+	let mut j = i - gap;
+	while j >= gap && is_less(&*value, &*p.add(j - gap)) {
+		ptr::copy_nonoverlapping(p.add(j - gap), hole.dest, 1);
+		moves.moved(1);
+		j -= gap;
+		hole.dest = p.add(j);
+	}
+	// dropping the hole writes the value into place
+}
+
+// insertionsort on every gap-th element, also used by shellsort_by
+pub fn insertion_gap_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], gap: usize, is_less: &mut F,
+																   moves: &mut M) {
+	for i in gap..array.len() {
+		unsafe {
+			insert_tail(array, i, gap, is_less, moves);
+		}
+	}
+}
+
+fn insertionsort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], is_less: &mut F, moves: &mut M) {
+	insertion_gap_less(array, 1, is_less, moves);
+}
+
+pub fn insertionsort_move<T: Ord>(array: &mut [T]) {
+	insertionsort_less(array, &mut T::lt, &mut Uncounted);
+}
+
+// insertionsort_move for --operations, counts moves as well as comparisons
+pub fn insertionsort_move_counted(array: &mut [Counted]) {
+	insertionsort_less(array, &mut Counted::lt, &mut CountMoves);
+}
+
+by_entry_points!(insertionsort_by, insertionsort_by_key, insertionsort_less);
@@ -1,18 +1,28 @@
 use crate::algos;
-use crate::unchecked_tools::SliceUnchecked;
+use crate::operations::{CountMoves, Counted};
+use crate::unchecked_tools::{Moves, SliceUnchecked, Uncounted};
 
 const fn num_bits<T>() -> usize { std::mem::size_of::<T>() * 8 }
 
 pub fn introsort<T: Ord + Copy>(array: &mut [T]) {
+	introsort_with_threshold(array, algos::INSERTIONSORT_THRESHOLD);
+}
+
+pub fn introsort_with_threshold<T: Ord + Copy>(array: &mut [T], threshold: usize) {
+	if array.is_empty() {
+		return;
+	}
 	// max recursion = 2 * floor(log_2(array.len()))
-	introsort_step(array, (num_bits::<usize>() - array.len().leading_zeros() as usize - 1) * 2);
+	// the heapsort fallback needs at least 3 elements
+	introsort_step(array, (num_bits::<usize>() - array.len().leading_zeros() as usize - 1) * 2,
+		std::cmp::max(threshold, 2));
 }
 
-fn introsort_step<T: Ord + Copy>(mut array: &mut [T], mut r_height: usize) {
+fn introsort_step<T: Ord + Copy>(mut array: &mut [T], mut r_height: usize, threshold: usize) {
 	// loop creates something along the lines of a tail-call recursion
 	// TODO: no performance difference observed on x86 :/
 	loop {
-		if array.len() <= algos::INSERTIONSORT_THRESHOLD {
+		if array.len() <= threshold {
 			algos::insertionsort(array);
 			return;
 		} else if r_height == 0 {
@@ -23,13 +33,58 @@ fn introsort_step<T: Ord + Copy>(mut array: &mut [T], mut r_height: usize) {
 			// safety: 0 <= pivot < array.len()
 			let (l, r) = unsafe { array.split_at_unchecked_mut_excl(pivot) };
 			if l.len() < r.len() {
-				introsort_step(l, r_height - 1);
+				introsort_step(l, r_height - 1, threshold);
+				array = r;
+			} else {
+				introsort_step(r, r_height - 1, threshold);
+				array = l;
+			}
+			r_height -= 1;
+		}
+	}
+}
+
+// introsort built from the move-based variants, for types that aren't Copy
+fn introsort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], is_less: &mut F, moves: &mut M) {
+	if array.is_empty() {
+		return;
+	}
+	let r_height = (num_bits::<usize>() - array.len().leading_zeros() as usize - 1) * 2;
+	introsort_move_step(array, r_height, is_less, moves);
+}
+
+fn introsort_move_step<T, F: FnMut(&T, &T) -> bool, M: Moves>(mut array: &mut [T], mut r_height: usize,
+															  is_less: &mut F, moves: &mut M) {
+	loop {
+		if array.len() <= algos::INSERTIONSORT_THRESHOLD {
+			algos::insertion_gap_less(array, 1, is_less, moves);
+			return;
+		} else if r_height == 0 {
+			algos::heapsort::heapsort_less(array, is_less, moves);
+			return;
+		} else {
+			let pivot = algos::quicksort::partition_move(array, is_less, moves);
+			// safety: 0 <= pivot < array.len()
+			let (l, r) = unsafe { array.split_at_unchecked_mut_excl(pivot) };
+			if l.len() < r.len() {
+				introsort_move_step(l, r_height - 1, is_less, moves);
 				array = r;
 			} else {
-				introsort_step(r, r_height - 1);
+				introsort_move_step(r, r_height - 1, is_less, moves);
 				array = l;
 			}
 			r_height -= 1;
 		}
 	}
 }
+
+pub fn introsort_move<T: Ord>(array: &mut [T]) {
+	introsort_less(array, &mut T::lt, &mut Uncounted);
+}
+
+// introsort_move for --operations, counts moves and swaps as well as comparisons
+pub fn introsort_move_counted(array: &mut [Counted]) {
+	introsort_less(array, &mut Counted::lt, &mut CountMoves);
+}
+
+by_entry_points!(introsort_by, introsort_by_key, introsort_less);
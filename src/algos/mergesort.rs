@@ -1,5 +1,8 @@
+use std::ptr;
+
 use crate::algos;
-use crate::unchecked_tools::SliceUnchecked;
+use crate::operations::{CountMoves, Counted};
+use crate::unchecked_tools::{MergeHole, Moves, SliceUnchecked, Uncounted};
 
 // TODO: get rid of
 pub fn merge_single<T: Ord + Copy>(slice: &mut [T], middle: usize) {
@@ -10,7 +13,8 @@ pub fn merge_single<T: Ord + Copy>(slice: &mut [T], middle: usize) {
 		let mut merged: Vec<T> = Vec::with_capacity(slice.len());
 		merged.set_len(slice.len());
 		while i < middle && j < slice.len() {
-			*merged.get_unchecked_mut(k) = if *slice.get_unchecked(i) < *slice.get_unchecked(j)
+			// ties are taken from the left to keep the sort stable
+			*merged.get_unchecked_mut(k) = if *slice.get_unchecked(i) <= *slice.get_unchecked(j)
 				{i += 1; *slice.get_unchecked(i - 1)} else {j += 1; *slice.get_unchecked(j - 1)};
 			k += 1;
 		}
@@ -35,7 +39,8 @@ fn merge<T: Ord + Copy>(slice: &mut [T], buffer: &mut Vec<T>) {
 		let mut j = middle;
 		let mut k = 0;
 		while i < middle && j < slice.len() {
-			if slice.get_unchecked(i) < slice.get_unchecked(j) {
+			// ties are taken from the left to keep the sort stable
+			if slice.get_unchecked(i) <= slice.get_unchecked(j) {
 				i += 1;
 				*buffer.get_unchecked_mut(k) = *slice.get_unchecked(i - 1);
 			} else {
@@ -76,20 +81,25 @@ fn mergesort_step<T: Ord + Copy>(array: &mut [T], buffer: &mut Vec<T>) {
 }
 
 pub fn mergesort_hybrid<T: Ord + Copy>(array: &mut [T]) {
+	mergesort_hybrid_with_threshold(array, algos::INSERTIONSORT_THRESHOLD);
+}
+
+pub fn mergesort_hybrid_with_threshold<T: Ord + Copy>(array: &mut [T], threshold: usize) {
 	let mut buffer: Vec<T> = Vec::with_capacity(array.len());
 	unsafe { buffer.set_len(array.len()); }
-	mergesort_hybrid_r(array, &mut buffer);
+	mergesort_hybrid_r(array, &mut buffer, threshold);
 }
 
-fn mergesort_hybrid_r<T: Ord + Copy>(array: &mut [T], buffer: &mut Vec<T>) {
-	if array.len() <= algos::INSERTIONSORT_THRESHOLD {
+fn mergesort_hybrid_r<T: Ord + Copy>(array: &mut [T], buffer: &mut Vec<T>, threshold: usize) {
+	// subarrays of 1 or less are always sorted, merging them would need a threshold of at least 1
+	if array.len() <= std::cmp::max(threshold, 1) {
 		algos::insertionsort(array);
 		return;
 	}
 	let middle = array.len() / 2;
 	// these array slices will have their boundary checks optimized out
-	mergesort_hybrid_r(&mut array[..middle], buffer);
-	mergesort_hybrid_r(&mut array[middle..], buffer);
+	mergesort_hybrid_r(&mut array[..middle], buffer, threshold);
+	mergesort_hybrid_r(&mut array[middle..], buffer, threshold);
 	merge(array, buffer);
 }
 
@@ -214,3 +224,63 @@ fn wmerge<T: Ord + Copy>(xs: &mut [T], mut i: usize, m: usize, mut j: usize, n:
 		}
 	}
 }
+
+// merges slice[..middle] and slice[middle..] for types that aren't Copy
+// the left run is moved out to the buffer and merged back in from the front, the hole is responsible
+// for the part of it that hasn't been merged back yet. the buffer's length stays 0, it's only used
+// for its capacity
+// ties are taken from the left to keep the sort stable
+pub fn merge_move<T, F: FnMut(&T, &T) -> bool, M: Moves>(slice: &mut [T], middle: usize, buffer: &mut Vec<T>,
+														  is_less: &mut F, moves: &mut M) {
+	buffer.reserve(middle);
+	unsafe {
+		let p = slice.as_mut_ptr();
+		let b = buffer.as_mut_ptr();
+		ptr::copy_nonoverlapping(p, b, middle);
+		moves.moved(middle);
+		let mut hole = MergeHole { start: b, end: b.add(middle), dest: p };
+		let mut right = p.add(middle);
+		let end = p.add(slice.len());
+		while hole.start < hole.end && right < end {
+			let next = if is_less(&*right, &*hole.start) {
+				right = right.add(1);
+				right.sub(1)
+			} else {
+				hole.start = hole.start.add(1);
+				hole.start.sub(1)
+			};
+			ptr::copy_nonoverlapping(next, hole.dest, 1);
+			hole.dest = hole.dest.add(1);
+		}
+		// one move per element merged so far, then the hole moves the rest of the left run
+		moves.moved(hole.dest.offset_from(p) as usize + hole.end.offset_from(hole.start) as usize);
+		// dropping the hole moves whatever's left of the left run into the gap before right
+	}
+}
+
+fn mergesort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], is_less: &mut F, moves: &mut M) {
+	let mut buffer: Vec<T> = Vec::with_capacity(array.len() / 2);
+	mergesort_move_step(array, &mut buffer, is_less, moves);
+}
+
+fn mergesort_move_step<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], buffer: &mut Vec<T>, is_less: &mut F,
+															  moves: &mut M) {
+	if array.len() <= 1 {
+		return;
+	}
+	let middle = array.len() / 2;
+	mergesort_move_step(&mut array[..middle], buffer, is_less, moves);
+	mergesort_move_step(&mut array[middle..], buffer, is_less, moves);
+	merge_move(array, middle, buffer, is_less, moves);
+}
+
+pub fn mergesort_move<T: Ord>(array: &mut [T]) {
+	mergesort_less(array, &mut T::lt, &mut Uncounted);
+}
+
+// mergesort_move for --operations, counts moves as well as comparisons
+pub fn mergesort_move_counted(array: &mut [Counted]) {
+	mergesort_less(array, &mut Counted::lt, &mut CountMoves);
+}
+
+by_entry_points!(mergesort_by, mergesort_by_key, mergesort_less);
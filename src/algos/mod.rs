@@ -1,9 +1,25 @@
+// *_by and *_by_key entry points mirroring slice::sort_by and slice::sort_by_key, for an
+// implementation taking an is_less function
+macro_rules! by_entry_points {
+	($by:ident, $by_key:ident, $less:ident) => {
+		pub fn $by<T, F: FnMut(&T, &T) -> std::cmp::Ordering>(array: &mut [T], mut compare: F) {
+			$less(array, &mut |a: &T, b: &T| compare(a, b) == std::cmp::Ordering::Less,
+				  &mut crate::unchecked_tools::Uncounted);
+		}
+		pub fn $by_key<T, K: Ord, F: FnMut(&T) -> K>(array: &mut [T], mut key: F) {
+			$less(array, &mut |a: &T, b: &T| key(a) < key(b), &mut crate::unchecked_tools::Uncounted);
+		}
+	};
+}
+
 pub mod bubblesort;
 pub use bubblesort::*;
 
 pub mod cocktail_shaker;
 pub use cocktail_shaker::*;
 
+pub mod context;
+
 pub mod heapsort;
 pub use heapsort::*;
 
@@ -34,6 +50,9 @@ pub use shellsort::*;
 pub mod timsort;
 pub use timsort::*;
 
+// size at or below which the hybrid algorithms switch to insertionsort
+// quicksort_hybrid, mergesort_hybrid, and introsort also have *_with_threshold versions taking it
+// at runtime, used by the sweep subcommand to find the best value
 pub const INSERTIONSORT_THRESHOLD: usize = 32;
 
 // It would be elegant to just pace a rust slice to the c code. Turns out it's pretty
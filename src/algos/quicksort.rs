@@ -1,8 +1,9 @@
 use rand::rngs::SmallRng;
-use rand::{Rng, SeedableRng};
+use rand::Rng;
 
 use crate::algos;
-use crate::unchecked_tools::SliceUnchecked;
+use crate::operations::{CountMoves, Counted};
+use crate::unchecked_tools::{Moves, SliceUnchecked, Uncounted};
 
 pub fn partition_end<T: Ord + Copy>(slice: &mut [T]) -> usize {
 	unsafe {
@@ -38,12 +39,12 @@ fn partition_random<T: Ord + Copy>(slice: &mut [T], rng: &mut SmallRng) -> usize
 	partition_end(slice)
 }
 
+// pivots come from the run's context (algos::context) so runs are reproducible
 pub fn quicksort_random<T: Ord + Copy>(array: &mut [T]) {
-	let mut rng = SmallRng::from_entropy();
-	quicksort_random_step(array, &mut rng);
+	quicksort_random_with_rng(array, &mut algos::context::rng());
 }
 
-fn quicksort_random_step<T: Ord + Copy>(array: &mut [T], rng: &mut SmallRng) {
+pub fn quicksort_random_with_rng<T: Ord + Copy>(array: &mut [T], rng: &mut SmallRng) {
 	if array.len() <= 1 {
 		return;
 	}
@@ -51,12 +52,17 @@ fn quicksort_random_step<T: Ord + Copy>(array: &mut [T], rng: &mut SmallRng) {
 	// TODO: no performance difference observed on x86, slight diff on arm
 	let pivot = partition_random(array, rng);
 	let (l, r) = unsafe { array.split_at_unchecked_mut_excl(pivot) };
-	quicksort_random_step(l, rng);
-	quicksort_random_step(r, rng);
+	quicksort_random_with_rng(l, rng);
+	quicksort_random_with_rng(r, rng);
 }
 
 pub fn quicksort_hybrid<T: Ord + Copy>(array: &mut [T]) {
-	if array.len() <= algos::INSERTIONSORT_THRESHOLD {
+	quicksort_hybrid_with_threshold(array, algos::INSERTIONSORT_THRESHOLD);
+}
+
+pub fn quicksort_hybrid_with_threshold<T: Ord + Copy>(array: &mut [T], threshold: usize) {
+	// partitioning needs at least one element
+	if array.len() <= std::cmp::max(threshold, 1) {
 		algos::insertionsort(array);
 		return;
 	}
@@ -64,6 +70,46 @@ pub fn quicksort_hybrid<T: Ord + Copy>(array: &mut [T]) {
 	// TODO: no performance difference observed on x86, slight diff on arm
 	let pivot = partition_end(array);
 	let (l, r) = unsafe { array.split_at_unchecked_mut_excl(pivot) };
-	quicksort_hybrid(l);
-	quicksort_hybrid(r);
+	quicksort_hybrid_with_threshold(l, threshold);
+	quicksort_hybrid_with_threshold(r, threshold);
+}
+
+// partition_end for types that aren't Copy
+// elements are only ever swapped so a panicking comparison leaves a permutation behind, no hole needed
+pub fn partition_move<T, F: FnMut(&T, &T) -> bool, M: Moves>(slice: &mut [T], is_less: &mut F, moves: &mut M)
+	-> usize {
+	let last = slice.len() - 1;
+	let mut i = 0;
+	for j in 0..last {
+		if is_less(&slice[j], &slice[last]) {
+			slice.swap(i, j);
+			moves.swapped(1);
+			i += 1;
+		}
+	}
+	slice.swap(i, last);
+	moves.swapped(1);
+	i
+}
+
+fn quicksort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], is_less: &mut F, moves: &mut M) {
+	if array.len() <= 1 {
+		return;
+	}
+	let pivot = partition_move(array, is_less, moves);
+	// safety: 0 <= pivot < array.len()
+	let (l, r) = unsafe { array.split_at_unchecked_mut_excl(pivot) };
+	quicksort_less(l, is_less, moves);
+	quicksort_less(r, is_less, moves);
 }
+
+pub fn quicksort_move<T: Ord>(array: &mut [T]) {
+	quicksort_less(array, &mut T::lt, &mut Uncounted);
+}
+
+// quicksort_move for --operations, counts swaps as well as comparisons
+pub fn quicksort_move_counted(array: &mut [Counted]) {
+	quicksort_less(array, &mut Counted::lt, &mut CountMoves);
+}
+
+by_entry_points!(quicksort_by, quicksort_by_key, quicksort_less);
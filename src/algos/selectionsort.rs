@@ -1,8 +1,8 @@
-use crate::unchecked_tools::SliceUnchecked;
+use crate::unchecked_tools::{Moves, SliceUnchecked};
 
 pub fn selectionsort<T: Ord>(array: &mut [T]) {
 	unsafe {
-		for i in 0..(array.len() - 1) {
+		for i in 0..array.len().saturating_sub(1) {
 			let mut min = array.get_unchecked(i);
 			let mut min_index = i;
 			for j in (i + 1)..array.len() {
@@ -15,3 +15,19 @@ pub fn selectionsort<T: Ord>(array: &mut [T]) {
 		}
 	}
 }
+
+// only swaps, so T doesn't have to be Copy
+fn selectionsort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(array: &mut [T], is_less: &mut F, moves: &mut M) {
+	for i in 0..array.len().saturating_sub(1) {
+		let mut min_index = i;
+		for j in (i + 1)..array.len() {
+			if is_less(&array[j], &array[min_index]) {
+				min_index = j;
+			}
+		}
+		array.swap(i, min_index);
+		moves.swapped(1);
+	}
+}
+
+by_entry_points!(selectionsort_by, selectionsort_by_key, selectionsort_less);
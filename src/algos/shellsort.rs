@@ -1,4 +1,5 @@
-use crate::unchecked_tools::SliceUnchecked;
+use crate::algos;
+use crate::unchecked_tools::{Moves, SliceUnchecked};
 
 // TODO: explore optimizations of these implementations further
 
@@ -55,11 +56,44 @@ pub fn shell_function_recursive<T: Ord + Copy>(slice: &mut [T], gap_function: fn
 	}
 }
 
+// gap sequences, also used by the gaps subcommand
+// functions of k are increasing in k, gonnet_baeza_next gives the gap after h
+
+pub fn knuth_gap(k: u32) -> usize {
+	(3usize.pow(k) - 1) / 2
+}
+
+pub fn sedgewick82_gap(k: u32) -> usize {
+	if k == 0 { 1 } else { 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1 }
+}
+
+pub fn sedgewick86_gap(k: u32) -> usize {
+	if k % 2 == 0 {
+		9 * (2usize.pow(k) - 2usize.pow(k / 2)) + 1
+	} else {
+		8 * 2usize.pow(k) - 6 * 2usize.pow((k + 1) / 2) + 1
+	}
+}
+
+pub fn gonnet_baeza_next(h: usize) -> usize {
+	std::cmp::max(5 * h / 11, 1)
+}
+
+pub fn tokuda_gap(k: u32) -> usize {
+	((9.0 * 2.25f64.powi(k as i32 - 1) - 4.0) / 5.0).ceil() as usize
+}
+
+pub const CIURA_SEQUENCE: [usize; 12] = [
+	20622, 8855, 3802, 1633,
+	701, 301, 132, 57,
+	23, 10, 4, 1
+];
+
 pub fn shellsort_knuth<T: Ord + Copy>(slice: &mut [T]) {
 	// (3^k - 1) / 2 not exceeding ceil(n / 3)
 	shell_function_known(
 		slice,
-		|k: u32| (3usize.pow(k) - 1) / 2,
+		knuth_gap,
 		(2.0 * (slice.len() as f64 / 3.0).ceil() + 1.0).log(3.0).floor() as u32,
 		1
 	);
@@ -69,7 +103,7 @@ pub fn shellsort_sedgewick82<T: Ord + Copy>(slice: &mut [T]) {
 	// TODO: test k upper-bound
 	shell_function(
 		slice,
-		|k: u32| if k == 0 { 1 } else { 4usize.pow(k) + 3 * 2usize.pow(k - 1) + 1 },
+		sedgewick82_gap,
 		slice.len() / 2
 	);
 }
@@ -78,9 +112,7 @@ pub fn shellsort_sedgewick86<T: Ord + Copy>(slice: &mut [T]) {
 	// TODO: test k upper-bound
 	shell_function(
 		slice,
-		|k: u32| if k % 2 == 0
-		{9 * (2usize.pow(k) - 2usize.pow(k / 2)) + 1} else
-		{8 * 2usize.pow(k) - 6 * 2usize.pow((k + 1) / 2) + 1},
+		sedgewick86_gap,
 		slice.len() / 2
 	);
 }
@@ -89,7 +121,7 @@ pub fn shellsort_gonnet_baeza<T: Ord + Copy>(slice: &mut [T]) {
 	// TODO: test k upper-bound
 	shell_function_recursive(
 		slice,
-		|h: usize| std::cmp::max(5 * h / 11, 1),
+		gonnet_baeza_next,
 		slice.len(),
 		1
 	);
@@ -99,16 +131,22 @@ pub fn shellsort_tokuda<T: Ord + Copy>(slice: &mut [T]) {
 	// TODO: test k upper-bound
 	shell_function(
 		slice,
-		|k: u32| ((9.0 * 2.25f64.powi(k as i32 - 1) - 4.0) / 5.0).ceil() as usize,
+		tokuda_gap,
 		slice.len() / 2
 	);
 }
 
 pub fn shellsort_ciura<T: Ord + Copy>(slice: &mut [T]) {
-	const DEFAULT_SEQUENCE: [usize; 12] = [
-		20622, 8855, 3802, 1633,
-		701, 301, 132, 57,
-		23, 10, 4, 1
-	];
-	shell_sequence(slice, &DEFAULT_SEQUENCE);
+	shell_sequence(slice, &CIURA_SEQUENCE);
 }
+
+// ciura's sequence, move-based so T doesn't have to be Copy
+fn shellsort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(slice: &mut [T], is_less: &mut F, moves: &mut M) {
+	for gap in CIURA_SEQUENCE.iter() {
+		if *gap < slice.len() {
+			algos::insertion_gap_less(slice, *gap, is_less, moves);
+		}
+	}
+}
+
+by_entry_points!(shellsort_by, shellsort_by_key, shellsort_less);
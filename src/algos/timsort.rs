@@ -1,22 +1,37 @@
-use crate::algos::insertionsort;
+use crate::algos::{insertion_gap_less, insertionsort, merge_move};
+use crate::operations::{CountMoves, Counted};
+use crate::unchecked_tools::{Moves, Uncounted};
 use std::num::Wrapping;
 use std::mem::size_of;
 use std::cmp::min;
 
-// Binary search for where the element should end up in a sorted array.
-// This version of binary search always returns a valid index, even if the exact element is not
-// found.
-unsafe fn binary_search<T: Ord>(slice: &[T], element: T) -> usize {
+// Binary search for where the element should end up in a sorted array, after any elements equal
+// to it.
+unsafe fn upper_bound<T: Ord>(slice: &[T], element: T) -> usize {
     let mut left = 0;
     let mut right = slice.len();
     while left < right {
         let middle = (left + right) / 2;
-        if *slice.get_unchecked(middle) < element {
+        if *slice.get_unchecked(middle) <= element {
             left = middle + 1;
-        } else if *slice.get_unchecked(middle) > element {
+        } else {
             right = middle;
+        }
+    }
+    left
+}
+
+// Binary search for where the element should end up in a sorted array, before any elements equal
+// to it.
+unsafe fn lower_bound<T: Ord>(slice: &[T], element: T) -> usize {
+    let mut left = 0;
+    let mut right = slice.len();
+    while left < right {
+        let middle = (left + right) / 2;
+        if *slice.get_unchecked(middle) < element {
+            left = middle + 1;
         } else {
-            return middle;
+            right = middle;
         }
     }
     left
@@ -24,13 +39,14 @@ unsafe fn binary_search<T: Ord>(slice: &[T], element: T) -> usize {
 
 // Merge 2 slices into destination. This merging algorithm uses galloping in order to hopefully
 // optimize merging even more. short_merge_left() merges from left to right, while
-// short_merge_right() merges from right to left.
+// short_merge_right() merges from right to left. Ties are broken in favor of the run that comes
+// first in the array so that the sort is stable, for short_merge_right() that's second.
 unsafe fn short_merge_left<T: Ord + Copy + std::fmt::Debug>(first: &[T], second: &[T], destination: &mut [T]) {
     let mut i = 0;
 	let mut j = 0;
 	let mut k = 0;
 	while i < first.len() && j < second.len() {
-		if *first.get_unchecked(i) < *second.get_unchecked(j) {
+		if *first.get_unchecked(i) <= *second.get_unchecked(j) {
 			*destination.get_unchecked_mut(k) = *first.get_unchecked(i);
 			i += 1;
 		} else {
@@ -60,7 +76,7 @@ unsafe fn short_merge_right<T: Ord + Copy + std::fmt::Debug>(first: &[T], second
 	let mut j = second.len() as isize - 1;
 	let mut k = destination.len() as isize - 1;
 	while i >= 0 && j >= 0 {
-		if *first.get_unchecked(i as usize) > *second.get_unchecked(j as usize) {
+		if *first.get_unchecked(i as usize) >= *second.get_unchecked(j as usize) {
 			*destination.get_unchecked_mut(k as usize) = *first.get_unchecked(i as usize);
 			i -= 1;
 		} else {
@@ -88,8 +104,9 @@ unsafe fn short_merge_right<T: Ord + Copy + std::fmt::Debug>(first: &[T], second
 // needed to be merged and temporary memory in order to reduce the amount of out-of-space memory.
 // The merging itself is done by short_merge().
 unsafe fn quick_merge<T: Ord + Copy + std::fmt::Debug>(slice: &mut [T], split: usize, buffer: &mut Vec<T>) {
-    let left = binary_search(&slice[..split], *slice.get_unchecked(split));
-    let right = binary_search(&slice[split..], *slice.get_unchecked(split - 1)) + split;
+    // Elements before left and from right on are already in place, equal elements included.
+    let left = upper_bound(&slice[..split], *slice.get_unchecked(split));
+    let right = lower_bound(&slice[split..], *slice.get_unchecked(split - 1)) + split;
     if split - left < right - split {
         buffer.clear();
         for element in &slice[left..split] {
@@ -108,6 +125,10 @@ unsafe fn quick_merge<T: Ord + Copy + std::fmt::Debug>(slice: &mut [T], split: u
 }
 
 pub fn timsort<T: Ord + Copy + std::fmt::Debug>(slice: &mut [T]) {
+    // Nothing to sort, and the first run below starts out one element long.
+    if slice.len() <= 1 {
+        return;
+    }
     unsafe {
         let left_shift_distance = size_of::<usize>() * 8 + 6;
         let right_shift_distance = (size_of::<usize>() * 8 - 6) as isize - slice.len().leading_zeros() as isize;
@@ -116,7 +137,7 @@ pub fn timsort<T: Ord + Copy + std::fmt::Debug>(slice: &mut [T]) {
 
         let mut run_stack = vec![0];
         let mut start = 0;
-        let mut end = 1; // TODO handle slice of size 1 or 0.
+        let mut end = 1;
         let mut buffer = Vec::new();
         while end < slice.len() || run_stack.len() > 2 {
             // Add a new run to the stack.
@@ -164,3 +185,81 @@ pub fn timsort<T: Ord + Copy + std::fmt::Debug>(slice: &mut [T]) {
         }
     }
 }
+
+// Minimum run length, same as timsort() above: n itself below 64, otherwise the top 6 bits of n plus
+// one if any of the rest are set.
+fn min_run(mut n: usize) -> usize {
+    let mut r = 0;
+    while n >= 64 {
+        r |= n & 1;
+        n >>= 1;
+    }
+    n + r
+}
+
+// Which runs to merge next (runs[r] and runs[r + 1]), if any. Keeps run lengths shrinking down the
+// stack and merges everything once the last run reaches the end of the slice.
+fn collapse(runs: &[(usize, usize)], len: usize) -> Option<usize> {
+    let n = runs.len();
+    if n >= 2 && (runs[n - 1].0 + runs[n - 1].1 == len
+                  || runs[n - 2].1 <= runs[n - 1].1
+                  || (n >= 3 && runs[n - 3].1 <= runs[n - 2].1 + runs[n - 1].1)
+                  || (n >= 4 && runs[n - 4].1 <= runs[n - 3].1 + runs[n - 2].1)) {
+        if n >= 3 && runs[n - 3].1 < runs[n - 1].1 {
+            Option::Some(n - 3)
+        } else {
+            Option::Some(n - 2)
+        }
+    } else {
+        Option::None
+    }
+}
+
+// Timsort for types that aren't Copy. Simpler than timsort(): runs are found and extended the same
+// way but merges always go through merge_move(), without skipping the elements already in place.
+fn timsort_less<T, F: FnMut(&T, &T) -> bool, M: Moves>(slice: &mut [T], is_less: &mut F, moves: &mut M) {
+    let len = slice.len();
+    let min_run_len = min_run(len);
+    let mut buffer = Vec::with_capacity(len / 2);
+    // (start, length) of the runs that haven't been merged yet.
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let mut start = 0;
+    while start < len {
+        let mut end = start + 1;
+        if end < len && is_less(&slice[end], &slice[end - 1]) {
+            // Strictly descending, so reversing it can't reorder equal elements.
+            while end < len && is_less(&slice[end], &slice[end - 1]) {
+                end += 1;
+            }
+            slice[start..end].reverse();
+            moves.swapped((end - start) / 2);
+        } else {
+            while end < len && !is_less(&slice[end], &slice[end - 1]) {
+                end += 1;
+            }
+        }
+        if end - start < min_run_len {
+            end = min(start + min_run_len, len);
+            insertion_gap_less(&mut slice[start..end], 1, is_less, moves);
+        }
+        runs.push((start, end - start));
+        start = end;
+        while let Option::Some(r) = collapse(&runs, len) {
+            let (left, right) = (runs[r], runs[r + 1]);
+            merge_move(&mut slice[left.0..(right.0 + right.1)], left.1, &mut buffer, is_less, moves);
+            runs[r] = (left.0, left.1 + right.1);
+            runs.remove(r + 1);
+        }
+    }
+}
+
+pub fn timsort_move<T: Ord>(slice: &mut [T]) {
+    timsort_less(slice, &mut T::lt, &mut Uncounted);
+}
+
+// timsort_move for --operations, counts moves and swaps as well as comparisons
+pub fn timsort_move_counted(slice: &mut [Counted]) {
+    timsort_less(slice, &mut Counted::lt, &mut CountMoves);
+}
+
+by_entry_points!(timsort_by, timsort_by_key, timsort_less);
@@ -1,14 +1,6 @@
 // NOTE: this build script is only correct for host compiling, not cross-compiling
 use std::process::Command;
 
-// cpuid only works on x86
-// procfs only works on linux
-#[cfg(target_os = "linux")]
-use procfs;
-// but don't actually need any cpu-specific stuff on windows now
-//#[cfg(target_os = "windows")]
-//use cpuid;
-
 fn main() {
 	// run make
 	assert!(Command::new("make").spawn().unwrap().wait().unwrap().success());
@@ -17,7 +9,17 @@ fn main() {
 	//println!("cargo:rustc-flags=-l dylib=stdc++");
 	//println!("cargo:rustc-flags=-l libgcc_s_seh-1");
 
+	// record the compiler version for result metadata
+	let rustc = std::env::var("RUSTC").unwrap_or(String::from("rustc"));
+	let version = Command::new(rustc).arg("--version").output()
+					.map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+					.unwrap_or(String::from("unknown"));
+	println!("cargo:rustc-env=RUSTC_VERSION={}", version);
+
 	// add cfg flags for cpu
+	// the arm11 only changes which --profile is the default, the settings themselves are chosen at runtime
+	println!("cargo:rustc-check-cfg=cfg(arm11)");
+	// procfs only works on linux, and nothing cpu-specific is needed on windows
 	#[cfg(target_os = "linux")]
 	{
 		// this will have to be updated for every raspberry pi tested on
@@ -27,4 +29,4 @@ fn main() {
 	}
 	//#[cfg(target_os = "windows")]
 	//{}
-}
\ No newline at end of file
+}
@@ -0,0 +1,740 @@
+// command line handling
+// everything that used to be a compile-time constant in main.rs (TEST_MODE, MAX_TEST_SIZE, etc.)
+// now lives in Config and is set from a named profile plus any explicit overrides
+
+use crate::datasets::Sampling;
+use crate::elements::ElementType;
+use crate::gap_search::Score;
+use crate::gaps::HMax;
+use crate::inputs;
+
+pub const USAGE: &str = "\
+usage: sorting-benchmarks [run] [options]
+       sorting-benchmarks analyze <samples file> [options]
+       sorting-benchmarks compare <samples file A> <samples file B> [options]
+       sorting-benchmarks check --baseline <samples file> [options]
+       sorting-benchmarks sweep [options]
+       sorting-benchmarks gaps [options]
+       sorting-benchmarks gap-search [options]
+       sorting-benchmarks comparators [options]
+
+commands:
+  run                      run benchmarks (default)
+  analyze                  recompute statistics for a file saved with --output samples, accepts
+                           the selection, output, and statistics options
+  compare                  compare two samples files cell by cell (e.g. before and after a
+                           change), accepts the selection and statistics options
+  check                    rerun benchmarks and compare them against a baseline samples file,
+                           exits with status 1 if any cell got significantly slower
+  sweep                    benchmark the hybrid algorithms (quicksort_hybrid, mergesort_hybrid,
+                           introsort) over a range of insertionsort thresholds and report the best
+                           threshold for each size with a 98% confidence interval
+  gaps                     benchmark the shellsort gap sequences under a range of h_max caps
+                           (largest gap allowed), plus any sequences from --gap-file, and report
+                           the fastest configuration at each size
+  gap-search               search for a shellsort gap sequence that does well on the selected
+                           sizes and distributions and print it as a rust const
+  comparators              benchmark each algorithm family through its *_by and *_by_key entry
+                           points on i32s and on records sorted by an integer key or a string
+
+options:
+  --profile <name>         quick, full (default), or embedded (default on the raspberry pi zero)
+  --min-size <n>           smallest test size (sizes go up by powers of 10)
+  --max-size <n>           largest test size
+  --distribution <name>    input distribution(s) to run, or all (default: uniform), see below
+  --element <type>         element type(s) to sort, or all (default: i32), see below
+  --tests <n>              number of runs per (algorithm, input, size) cell
+  --runtime-limit <secs>   stop scheduling a cell once it has used this much time
+  --seed <n>               rng seed used for test vectors and job order
+  --workers <n>            number of worker threads (1 = single-threaded)
+  --job-timeout <secs>     run each worker in its own process and kill any job that takes longer
+                           than this, its cell is reported as timed out (unix only)
+  --algo <glob>            only run algorithms matching the pattern (e.g. 'shellsort_*')
+  --group <name>           only run algorithms in a group, one per family (e.g. heapsort)
+  --exclude <glob>         don't run algorithms matching the pattern
+  --list                   list the registered algorithms and groups and exit
+  --operations             also count the comparisons each algorithm makes (and the moves and swaps
+                           of the move-based variants) on the first few test vectors of every
+                           distribution and print them after the timing tables
+  --complexity             fit the mean runtimes to n, n log n, n^1.25, n^1.5, and n^2 and warn
+                           about algorithms that grow differently than their registered complexity
+  --checkpoint <path>      periodically save completed timings and the remaining jobs to <path>
+  --checkpoint-interval <secs>
+                           how often --checkpoint saves (60)
+  --resume <path>          continue an interrupted run from a checkpoint, the run settings and
+                           selection come from the checkpoint
+  --dump-inputs <path>     write every test vector the run uses to <path> as a binary corpus (i32
+                           arrays with a header), see the readme for the format
+  --inputs-from <path>     replay the arrays in a corpus instead of generating test vectors, the
+                           distributions, sizes, number of tests, and seed come from the corpus
+  --dataset <path>         draw test vectors from a file of integers, may be repeated. replaces the
+                           distributions unless --distribution is given too. one integer per
+                           line, raw little-endian i32s (.bin, .raw, .i32), or a csv column
+                           (<path>.csv:<header name or 1-based index>)
+  --sampling <mode>        how --dataset test vectors are drawn: window (default), a random
+                           contiguous run of the data, or resample, values drawn with replacement
+  --output <format>        also write results as json, csv, or samples (raw timings), or a
+                           comma-separated combination
+  --output-prefix <path>   where --output writes to, <path>.<format> (default: results)
+  --alpha <p>              significance level for \"statistically equal to fastest\" and for
+                           compare / check (0.001)
+  --diff-threshold <x>     relative difference for \"within x of fastest\" (0.05)
+  --outlier-coefficient <k>
+                           tukey fence multiplier for discarding outliers (3.0)
+  --baseline <path>        samples file to check against (check only)
+  --max-regression <x>     relative slowdown check tolerates before failing (0.05), a cell fails
+                           if it is slower by more than this with p < --alpha
+  --thresholds <list>      thresholds to sweep, a comma-separated list or a range a..b or a..b:step
+                           (sweep only, default: 4..64:4)
+  --h-max <list>           caps on the largest gap, each n, n/<k>, or a number (gaps only,
+                           default: n/4,n/3,n/2,n)
+  --gap-file <path>        also run the gap sequences in <path>, one per line as an optional
+                           \"name:\" followed by gaps separated by commas or spaces (gaps only)
+  --score <name>           what gap-search minimizes, comparisons (default) or time, both relative
+                           to n log2 n
+  --iterations <n>         number of candidate sequences gap-search tries (1000)
+  -h, --help               print this message
+
+--algo, --group, and --exclude may be repeated or given comma-separated lists. Patterns match
+either the full name (algos::heapsort_top_down) or the bare function name (heapsort_top_down).
+
+distributions:
+  uniform, sorted, reversed, nearly-sorted (n/100 random swaps), few-unique (8 values), organ-pipe,
+  sawtooth, random-runs (sorted runs of random length), all-equal, zipf
+  plain quicksorts are limited to O(n^2) sizes on everything but uniform and random-runs
+
+element types:
+  i32, u8, u16, u64, u128, record16, record64, record256 (i32 key plus a 16/64/256 byte payload)
+  every distribution is generated as i32 keys and converted preserving order, narrow types end up
+  with more duplicates. radixsort and the c / c++ algorithms only run on i32
+
+profiles:
+  quick     reduced default algorithm set, max size 10,000, 50 tests (for development)
+  full      all algorithms, max size 1,000,000, 200 tests
+  embedded  all algorithms, max size 100,000, 200 tests (raspberry pi zero etc.)";
+
+// the quick profile only runs these by default
+const QUICK_ALGORITHMS: &[&str] = &[
+	"algos::bubblesort",
+	"algos::cocktail_shaker",
+	"algos::selectionsort",
+	"algos::insertionsort",
+	"algos::insertionsort_boundary_checked",
+	"algos::insertionsort_c",
+	"algos::shellsort_knuth",
+	"algos::shellsort_sedgewick82",
+	"algos::mergesort_hybrid",
+	"algos::mergesort_in_place",
+	"algos::heapsort_top_down",
+	"algos::quicksort_end",
+	"algos::quicksort_hybrid",
+	"algos::radixsort",
+	"algos::rustsort",
+	"algos::cpp_std_sort"
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Profile {
+	Quick,
+	Full,
+	Embedded
+}
+
+impl Profile {
+	pub fn from_name(name: &str) -> Result<Profile, String> {
+		match name {
+			"quick" => Ok(Profile::Quick),
+			"full" => Ok(Profile::Full),
+			"embedded" => Ok(Profile::Embedded),
+			_ => Err(format!("unknown profile \"{}\"", name))
+		}
+	}
+	pub fn name(&self) -> &'static str {
+		match self {
+			Profile::Quick => "quick",
+			Profile::Full => "full",
+			Profile::Embedded => "embedded"
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+	Json,
+	Csv,
+	// raw per-run timings, can be re-analyzed later
+	Samples
+}
+
+impl OutputFormat {
+	pub fn from_name(name: &str) -> Result<OutputFormat, String> {
+		match name {
+			"json" => Ok(OutputFormat::Json),
+			"csv" => Ok(OutputFormat::Csv),
+			"samples" => Ok(OutputFormat::Samples),
+			_ => Err(format!("unknown output format \"{}\"", name))
+		}
+	}
+	pub fn extension(&self) -> &'static str {
+		match self {
+			OutputFormat::Json => "json",
+			OutputFormat::Csv => "csv",
+			OutputFormat::Samples => "samples"
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+	pub profile: Profile,
+	// skips sleeping between runs
+	// this is for use during development
+	pub test_mode: bool,
+	pub min_size: usize,
+	pub max_size: usize,
+	// names from inputs::DISTRIBUTIONS
+	pub distributions: Vec<String>,
+	// names from elements::ElementType
+	pub elements: Vec<String>,
+	pub n_tests: usize,
+	// nanoseconds
+	pub runtime_limit: u64,
+	pub seed: u64,
+	pub n_workers: usize,
+	// nanoseconds, workers are subprocesses that get killed past this when set
+	pub job_timeout: Option<u64>,
+	// algorithm selection, empty algorithms and groups means everything is selected
+	// algorithms holds glob patterns, groups holds group names
+	pub algorithms: Vec<String>,
+	pub groups: Vec<String>,
+	pub exclude: Vec<String>,
+	pub outputs: Vec<OutputFormat>,
+	pub output_prefix: String,
+	// statistics settings
+	// significance level for "statistically equal to fastest"
+	pub alpha: f64,
+	// relative difference for "within x of fastest"
+	pub diff_threshold: f64,
+	// tukey fence multiplier for discarding outliers
+	pub outlier_coefficient: f64,
+	// relative slowdown the check subcommand tolerates
+	pub max_regression: f64,
+	// where to periodically save progress, none to not save any
+	pub checkpoint: Option<String>,
+	// nanoseconds
+	pub checkpoint_interval: u64,
+	// checkpoint to continue from
+	pub resume: Option<String>,
+	// print comparison counts after the timing tables
+	pub operations: bool,
+	// print complexity fits after the timing tables
+	pub complexity: bool,
+	// insertionsort thresholds for the sweep subcommand
+	pub thresholds: Vec<usize>,
+	// gap caps for the gaps subcommand
+	pub h_max: Vec<HMax>,
+	// extra gap sequences for the gaps subcommand
+	pub gap_file: Option<String>,
+	// what the gap-search subcommand minimizes
+	pub score: Score,
+	pub iterations: usize,
+	// corpus file to write the run's test vectors to
+	pub dump_inputs: Option<String>,
+	// corpus file to replay instead of generating test vectors
+	pub inputs_from: Option<String>,
+	// files to draw test vectors from (--dataset), see datasets.rs
+	pub datasets: Vec<String>,
+	pub sampling: Sampling
+}
+
+impl Config {
+	pub fn from_profile(profile: Profile) -> Config {
+		// only run on physical cores / 2 threads, see the benchmarking strategy in the readme
+		//num_cpus::get_physical() - 1
+		let n_workers = num_cpus::get_physical() / 2;
+		let base = Config {
+			profile,
+			test_mode: false,
+			min_size: 10,
+			max_size: 1_000_000,
+			distributions: vec![String::from("uniform")],
+			elements: vec![String::from("i32")],
+			n_tests: 200,
+			runtime_limit: 10e9 as u64,
+			seed: 2222,
+			n_workers,
+			job_timeout: Option::None,
+			algorithms: Vec::new(),
+			groups: Vec::new(),
+			exclude: Vec::new(),
+			outputs: Vec::new(),
+			output_prefix: String::from("results"),
+			alpha: 0.001,
+			diff_threshold: 0.05,
+			outlier_coefficient: 3.0,
+			max_regression: 0.05,
+			checkpoint: Option::None,
+			checkpoint_interval: 60e9 as u64,
+			resume: Option::None,
+			operations: false,
+			complexity: false,
+			thresholds: (4..=64).step_by(4).collect(),
+			h_max: vec![HMax::Fraction(4), HMax::Fraction(3), HMax::Fraction(2), HMax::Fraction(1)],
+			gap_file: Option::None,
+			score: Score::Comparisons,
+			iterations: 1000,
+			dump_inputs: Option::None,
+			inputs_from: Option::None,
+			datasets: Vec::new(),
+			sampling: Sampling::Window
+		};
+		match profile {
+			Profile::Full => base,
+			Profile::Quick => Config {
+				test_mode: true,
+				max_size: 10_000,
+				n_tests: 50,
+				algorithms: QUICK_ALGORITHMS.iter().map(|a| String::from(*a)).collect(),
+				..base
+			},
+			// used to be a compile-time fork for the arm11 (raspberry pi zero)
+			Profile::Embedded => Config {
+				max_size: 100_000,
+				..base
+			}
+		}
+	}
+	pub fn test_sizes(&self) -> Vec<usize> {
+		let mut v = Vec::new();
+		let mut test_size = self.min_size;
+		while test_size <= self.max_size {
+			v.push(test_size);
+			// sizes near usize::MAX have no next power of ten
+			test_size = match test_size.checked_mul(10) {
+				Option::Some(size) => size,
+				Option::None => break
+			};
+		}
+		v
+	}
+}
+
+pub enum Command {
+	Run(Config),
+	List(Config),
+	// re-run the statistics on a saved samples file
+	Analyze(Config, String),
+	// compare two saved samples files
+	Compare(Config, String, String),
+	// run benchmarks and fail on regressions against a baseline samples file
+	Check(Config, String),
+	// benchmark the hybrid algorithms over a range of thresholds
+	Sweep(Config),
+	// benchmark shellsort gap sequences and caps
+	Gaps(Config),
+	// search for a good shellsort gap sequence
+	GapSearch(Config),
+	// benchmark the *_by and *_by_key entry points
+	Comparators(Config),
+	Help
+}
+
+// options that only make sense when benchmarks are actually being run
+const RUN_ONLY_FLAGS: &[&str] = &[
+	"--profile", "--min-size", "--max-size", "--distribution", "--element", "--tests", "--runtime-limit", "--seed",
+	"--workers", "--list", "--operations", "--checkpoint", "--checkpoint-interval", "--resume", "--dump-inputs",
+	"--inputs-from", "--dataset", "--sampling", "--job-timeout"
+];
+
+// a resumed run takes these from the checkpoint
+const RESUME_CONFLICTS: &[&str] = &[
+	"--profile", "--min-size", "--max-size", "--distribution", "--element", "--tests", "--runtime-limit", "--seed",
+	"--algo", "--group", "--exclude", "--list", "--dump-inputs", "--inputs-from", "--dataset", "--sampling"
+];
+
+// a replayed run takes these from the corpus, and a checkpoint of one couldn't be resumed without it
+const INPUTS_FROM_CONFLICTS: &[&str] = &[
+	"--min-size", "--max-size", "--distribution", "--tests", "--seed", "--checkpoint", "--resume", "--dataset"
+];
+
+// checkpoints only record distribution names, a resumed run wouldn't know where to find the data
+const DATASET_CONFLICTS: &[&str] = &["--checkpoint", "--resume"];
+
+// compare only prints a table
+const OUTPUT_FLAGS: &[&str] = &["--output", "--output-prefix"];
+
+const CHECK_ONLY_FLAGS: &[&str] = &["--baseline", "--max-regression"];
+
+const SWEEP_ONLY_FLAGS: &[&str] = &["--thresholds"];
+
+const GAPS_ONLY_FLAGS: &[&str] = &["--h-max", "--gap-file"];
+
+const GAP_SEARCH_ONLY_FLAGS: &[&str] = &["--score", "--iterations"];
+
+// gap-search scores every candidate on a fixed number of test vectors and has no runtime limit
+const GAP_SEARCH_CONFLICTS: &[&str] = &["--tests", "--runtime-limit"];
+
+// sweep, gaps, and comparators run single-threaded on i32 and only print tables
+const SWEEP_CONFLICTS: &[&str] = &[
+	"--element", "--workers", "--list", "--operations", "--complexity", "--checkpoint", "--checkpoint-interval", "--resume",
+	"--output", "--output-prefix", "--dump-inputs", "--inputs-from", "--dataset", "--sampling", "--job-timeout"
+];
+
+// "a,b" -> ["a", "b"]
+fn split_list(value: &str) -> impl Iterator<Item = String> + '_ {
+	value.split(',').filter(|s| !s.is_empty()).map(String::from)
+}
+
+// "8,16,32", "8..64" (inclusive), or "8..64:8"
+fn parse_thresholds(value: &str) -> Result<Vec<usize>, String> {
+	let invalid = || format!("invalid value for --thresholds: \"{}\"", value);
+	let thresholds: Vec<usize> = match value.split_once("..") {
+		Option::Some((start, rest)) => {
+			let (end, step) = rest.split_once(':').unwrap_or((rest, "1"));
+			let start: usize = start.parse().map_err(|_| invalid())?;
+			let end: usize = end.parse().map_err(|_| invalid())?;
+			let step: usize = step.parse().map_err(|_| invalid())?;
+			if step == 0 {
+				return Err(invalid());
+			}
+			(start..=end).step_by(step).collect()
+		}
+		Option::None => split_list(value).map(|t| t.parse().map_err(|_| invalid()))
+										 .collect::<Result<Vec<usize>, String>>()?
+	};
+	if thresholds.len() < 2 {
+		return Err(String::from("--thresholds needs at least two values to sweep"));
+	}
+	Ok(thresholds)
+}
+
+// accepts 1000000, 1_000_000, and 1,000,000
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+	value.replace(['_', ','], "")
+		 .parse::<T>()
+		 .map_err(|_| format!("invalid value for {}: \"{}\"", flag, value))
+}
+
+// splits "--flag=value" and "--flag value" forms into (flag, value) pairs, anything that isn't a
+// flag is returned separately (subcommand name and file operands)
+#[allow(clippy::type_complexity)]
+fn split_args(args: &[String]) -> Result<(Vec<String>, Vec<(String, Option<String>)>), String> {
+	let mut positionals = Vec::new();
+	let mut flags = Vec::new();
+	let mut i = 0;
+	while i < args.len() {
+		let arg = &args[i];
+		if arg == "-h" || arg == "--help" {
+			flags.push((String::from("--help"), Option::None));
+		} else if arg == "--list" || arg == "--operations" || arg == "--complexity" {
+			flags.push((arg.clone(), Option::None));
+		} else if !arg.starts_with("--") {
+			positionals.push(arg.clone());
+		} else if let Option::Some(eq) = arg.find('=') {
+			flags.push((String::from(&arg[..eq]), Option::Some(String::from(&arg[eq + 1..]))));
+		} else if i + 1 < args.len() {
+			flags.push((arg.clone(), Option::Some(args[i + 1].clone())));
+			i += 1;
+		} else {
+			return Err(format!("missing value for {}", arg));
+		}
+		i += 1;
+	}
+	Ok((positionals, flags))
+}
+
+// args should not include the program name
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+	let (positionals, flags) = split_args(args)?;
+	if flags.iter().any(|(flag, _)| flag == "--help") {
+		return Ok(Command::Help);
+	}
+	// the profile is applied first so explicit flags override it regardless of order
+	// the raspberry pi zero defaults to the embedded profile, see build.rs
+	let mut profile = if cfg!(arm11) { Profile::Embedded } else { Profile::Full };
+	for (flag, value) in &flags {
+		if flag == "--profile" {
+			profile = Profile::from_name(value.as_ref().unwrap())?;
+		}
+	}
+	let mut config = Config::from_profile(profile);
+	// an explicit selection replaces the profile's default selection
+	if flags.iter().any(|(flag, _)| flag == "--algo" || flag == "--group") {
+		config.algorithms.clear();
+		config.groups.clear();
+	}
+	// datasets replace the synthetic distributions unless some are asked for too
+	if flags.iter().any(|(flag, _)| flag == "--distribution" || flag == "--dataset") {
+		config.distributions.clear();
+	}
+	if flags.iter().any(|(flag, _)| flag == "--element") {
+		config.elements.clear();
+	}
+	let mut list = false;
+	let mut baseline = Option::None;
+	for (flag, value) in &flags {
+		if flag == "--list" {
+			list = true;
+			continue;
+		}
+		if flag == "--operations" {
+			config.operations = true;
+			continue;
+		}
+		if flag == "--complexity" {
+			config.complexity = true;
+			continue;
+		}
+		let value = value.as_ref().unwrap();
+		match flag.as_str() {
+			"--profile" => (),
+			"--min-size" => config.min_size = parse_number(flag, value)?,
+			"--max-size" => config.max_size = parse_number(flag, value)?,
+			"--distribution" => {
+				for name in split_list(value) {
+					if name == "all" {
+						config.distributions.extend(inputs::names().into_iter().map(String::from));
+					} else if inputs::find(&name).is_some() {
+						config.distributions.push(name);
+					} else {
+						return Err(format!("unknown distribution \"{}\" (available: {}, all)", name,
+							inputs::names().join(", ")));
+					}
+				}
+			}
+			"--element" => {
+				for name in split_list(value) {
+					if name == "all" {
+						config.elements.extend(ElementType::ALL.iter().map(|e| String::from(e.name())));
+					} else if ElementType::from_name(&name).is_some() {
+						config.elements.push(name);
+					} else {
+						return Err(format!("unknown element type \"{}\" (available: {}, all)", name,
+							ElementType::ALL.iter().map(|e| e.name()).collect::<Vec<&str>>().join(", ")));
+					}
+				}
+			}
+			"--tests" => config.n_tests = parse_number(flag, value)?,
+			"--runtime-limit" => {
+				let seconds: f64 = parse_number(flag, value)?;
+				config.runtime_limit = (seconds * 1e9) as u64;
+			}
+			"--seed" => config.seed = parse_number(flag, value)?,
+			"--workers" => config.n_workers = parse_number(flag, value)?,
+			"--job-timeout" => {
+				let seconds: f64 = parse_number(flag, value)?;
+				if seconds.is_nan() || seconds <= 0.0 {
+					return Err(String::from("--job-timeout must be positive"));
+				}
+				if cfg!(not(unix)) {
+					return Err(String::from("--job-timeout needs fork, it's only supported on unix"));
+				}
+				config.job_timeout = Option::Some((seconds * 1e9) as u64);
+			}
+			"--algo" => config.algorithms.extend(split_list(value)),
+			"--group" => config.groups.extend(split_list(value)),
+			"--exclude" => config.exclude.extend(split_list(value)),
+			"--output" => {
+				for name in split_list(value) {
+					config.outputs.push(OutputFormat::from_name(&name)?);
+				}
+			}
+			"--output-prefix" => config.output_prefix = value.clone(),
+			"--alpha" => config.alpha = parse_number(flag, value)?,
+			"--diff-threshold" => config.diff_threshold = parse_number(flag, value)?,
+			"--outlier-coefficient" => config.outlier_coefficient = parse_number(flag, value)?,
+			"--checkpoint" => config.checkpoint = Option::Some(value.clone()),
+			"--checkpoint-interval" => {
+				let seconds: f64 = parse_number(flag, value)?;
+				config.checkpoint_interval = (seconds * 1e9) as u64;
+			}
+			"--resume" => config.resume = Option::Some(value.clone()),
+			"--baseline" => baseline = Option::Some(value.clone()),
+			"--max-regression" => config.max_regression = parse_number(flag, value)?,
+			"--thresholds" => {
+				let mut thresholds = parse_thresholds(value)?;
+				thresholds.sort_unstable();
+				thresholds.dedup();
+				config.thresholds = thresholds;
+			}
+			"--h-max" => {
+				let mut h_max = Vec::new();
+				for value in split_list(value) {
+					let h = HMax::parse(&value)?;
+					if !h_max.contains(&h) {
+						h_max.push(h);
+					}
+				}
+				if h_max.is_empty() {
+					return Err(String::from("no h_max values given"));
+				}
+				config.h_max = h_max;
+			}
+			"--gap-file" => config.gap_file = Option::Some(value.clone()),
+			"--dump-inputs" => config.dump_inputs = Option::Some(value.clone()),
+			"--inputs-from" => config.inputs_from = Option::Some(value.clone()),
+			"--dataset" => {
+				if !config.datasets.contains(value) {
+					config.datasets.push(value.clone());
+				}
+			}
+			"--sampling" => config.sampling = Sampling::from_name(value)?,
+			"--score" => config.score = Score::from_name(value)?,
+			"--iterations" => config.iterations = parse_number(flag, value)?,
+			_ => return Err(format!("unknown option {}", flag))
+		}
+	}
+	if config.min_size == 0 {
+		return Err(String::from("--min-size must be at least 1"));
+	}
+	if config.min_size > config.max_size {
+		return Err(String::from("--min-size must not exceed --max-size"));
+	}
+	// keep the first occurrence of each
+	let mut seen = Vec::new();
+	config.distributions.retain(|d| if seen.contains(d) { false } else { seen.push(d.clone()); true });
+	if config.distributions.is_empty() && config.datasets.is_empty() {
+		return Err(String::from("no distributions selected"));
+	}
+	let mut seen = Vec::new();
+	config.elements.retain(|e| if seen.contains(e) { false } else { seen.push(e.clone()); true });
+	if config.elements.is_empty() {
+		return Err(String::from("no element types selected"));
+	}
+	if config.n_tests < 2 {
+		return Err(String::from("--tests must be at least 2"));
+	}
+	if !(config.alpha > 0.0 && config.alpha < 1.0) {
+		return Err(String::from("--alpha must be between 0 and 1"));
+	}
+	if config.resume.is_some() {
+		if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| RESUME_CONFLICTS.contains(&f.as_str())) {
+			return Err(format!("{} can't be used with --resume", flag));
+		}
+		// keep saving to the checkpoint the run was resumed from
+		if config.checkpoint.is_none() {
+			config.checkpoint = config.resume.clone();
+		}
+	}
+	if !config.datasets.is_empty() {
+		if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| DATASET_CONFLICTS.contains(&f.as_str())) {
+			return Err(format!("{} can't be used with --dataset", flag));
+		}
+	} else if flags.iter().any(|(f, _)| f == "--sampling") {
+		return Err(String::from("--sampling can only be used with --dataset"));
+	}
+	if config.inputs_from.is_some() {
+		if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| INPUTS_FROM_CONFLICTS.contains(&f.as_str())) {
+			return Err(format!("{} can't be used with --inputs-from", flag));
+		}
+	}
+	if config.max_regression < 0.0 {
+		return Err(String::from("--max-regression must not be negative"));
+	}
+	let subcommand = positionals.first().map(String::as_str).unwrap_or("run");
+	let operands = if positionals.is_empty() { &positionals[..] } else { &positionals[1..] };
+	if subcommand != "check" {
+		if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| CHECK_ONLY_FLAGS.contains(&f.as_str())) {
+			return Err(format!("{} can only be used with check", flag));
+		}
+	}
+	if subcommand != "sweep" {
+		if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| SWEEP_ONLY_FLAGS.contains(&f.as_str())) {
+			return Err(format!("{} can only be used with sweep", flag));
+		}
+	}
+	if subcommand != "gaps" {
+		if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| GAPS_ONLY_FLAGS.contains(&f.as_str())) {
+			return Err(format!("{} can only be used with gaps", flag));
+		}
+	}
+	if subcommand != "gap-search" {
+		if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| GAP_SEARCH_ONLY_FLAGS.contains(&f.as_str())) {
+			return Err(format!("{} can only be used with gap-search", flag));
+		}
+	}
+	match subcommand {
+		"run" => {
+			if let Option::Some(operand) = operands.first() {
+				return Err(format!("unexpected argument \"{}\"", operand));
+			}
+			if list {
+				Ok(Command::List(config))
+			} else {
+				Ok(Command::Run(config))
+			}
+		}
+		"analyze" => {
+			if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| RUN_ONLY_FLAGS.contains(&f.as_str())) {
+				return Err(format!("{} can't be used with analyze", flag));
+			}
+			match operands {
+				[file] => Ok(Command::Analyze(config, file.clone())),
+				_ => Err(String::from("analyze takes exactly one samples file"))
+			}
+		}
+		"compare" => {
+			if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| RUN_ONLY_FLAGS.contains(&f.as_str())
+																	|| OUTPUT_FLAGS.contains(&f.as_str())
+																	|| f == "--complexity") {
+				return Err(format!("{} can't be used with compare", flag));
+			}
+			match operands {
+				[a, b] => Ok(Command::Compare(config, a.clone(), b.clone())),
+				_ => Err(String::from("compare takes exactly two samples files"))
+			}
+		}
+		"check" => {
+			if let Option::Some(operand) = operands.first() {
+				return Err(format!("unexpected argument \"{}\"", operand));
+			}
+			if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| ["--list", "--resume", "--operations",
+																		 "--complexity"].contains(&f.as_str())) {
+				return Err(format!("{} can't be used with check", flag));
+			}
+			match baseline {
+				Option::Some(baseline) => Ok(Command::Check(config, baseline)),
+				Option::None => Err(String::from("check needs a --baseline samples file"))
+			}
+		}
+		"sweep" => {
+			if let Option::Some(operand) = operands.first() {
+				return Err(format!("unexpected argument \"{}\"", operand));
+			}
+			if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| SWEEP_CONFLICTS.contains(&f.as_str())) {
+				return Err(format!("{} can't be used with sweep", flag));
+			}
+			Ok(Command::Sweep(config))
+		}
+		"gaps" => {
+			if let Option::Some(operand) = operands.first() {
+				return Err(format!("unexpected argument \"{}\"", operand));
+			}
+			if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| SWEEP_CONFLICTS.contains(&f.as_str())) {
+				return Err(format!("{} can't be used with gaps", flag));
+			}
+			Ok(Command::Gaps(config))
+		}
+		"gap-search" => {
+			if let Option::Some(operand) = operands.first() {
+				return Err(format!("unexpected argument \"{}\"", operand));
+			}
+			if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| SWEEP_CONFLICTS.contains(&f.as_str())
+																	|| GAP_SEARCH_CONFLICTS.contains(&f.as_str())) {
+				return Err(format!("{} can't be used with gap-search", flag));
+			}
+			Ok(Command::GapSearch(config))
+		}
+		"comparators" => {
+			if let Option::Some(operand) = operands.first() {
+				return Err(format!("unexpected argument \"{}\"", operand));
+			}
+			if let Option::Some((flag, _)) = flags.iter().find(|(f, _)| SWEEP_CONFLICTS.contains(&f.as_str())
+																	|| f == "--group") {
+				return Err(format!("{} can't be used with comparators", flag));
+			}
+			Ok(Command::Comparators(config))
+		}
+		_ => Err(format!("unknown command \"{}\"", subcommand))
+	}
+}
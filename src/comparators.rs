@@ -0,0 +1,191 @@
+// comparator-heavy workloads (comparators subcommand)
+// benchmarks each algorithm family through its *_by and *_by_key entry points, where every comparison
+// goes through a closure, on i32s and on records sorted by an integer key or by a string field
+// records are built from the same test vectors as the main benchmark, building them isn't timed
+
+use std::cmp::Ordering;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::algos;
+use crate::cli;
+use crate::inputs;
+use crate::registry;
+use crate::sweep;
+use crate::utils;
+use crate::{BenchmarkManager, BenchmarkResult};
+
+// family name and the registered algorithm its size limits are taken from
+pub static FAMILIES: &[(&str, &str)] = &[
+	("bubblesort",    "algos::bubblesort"),
+	("selectionsort", "algos::selectionsort"),
+	("insertionsort", "algos::insertionsort"),
+	("shellsort",     "algos::shellsort_ciura"),
+	("mergesort",     "algos::mergesort"),
+	("heapsort",      "algos::heapsort_bottom_up"),
+	("quicksort",     "algos::quicksort_end"),
+	("introsort",     "algos::introsort"),
+	("timsort",       "algos::timsort")
+];
+
+// i32: i32s through a comparator, key: records by their i32 key, name: records by their name
+pub static WORKLOADS: &[&str] = &["i32", "key", "name"];
+
+#[derive(Clone, Debug)]
+pub struct Record {
+	pub key: i32,
+	pub name: String
+}
+
+impl Record {
+	// names are zero padded so they sort the same way as the keys, the shared prefix makes every string
+	// comparison look at a few bytes
+	pub fn new(key: i32) -> Record {
+		Record { key, name: format!("record {:010}", key as i64 - i32::MIN as i64) }
+	}
+}
+
+pub fn sort_by<T, F: FnMut(&T, &T) -> Ordering>(family: usize, array: &mut [T], compare: F) {
+	match FAMILIES[family].0 {
+		"bubblesort" => algos::bubblesort_by(array, compare),
+		"selectionsort" => algos::selectionsort_by(array, compare),
+		"insertionsort" => algos::insertionsort_by(array, compare),
+		"shellsort" => algos::shellsort_by(array, compare),
+		"mergesort" => algos::mergesort_by(array, compare),
+		"heapsort" => algos::heapsort_by(array, compare),
+		"quicksort" => algos::quicksort_by(array, compare),
+		"introsort" => algos::introsort_by(array, compare),
+		"timsort" => algos::timsort_by(array, compare),
+		_ => unreachable!()
+	}
+}
+
+pub fn sort_by_key<T, K: Ord, F: FnMut(&T) -> K>(family: usize, array: &mut [T], key: F) {
+	match FAMILIES[family].0 {
+		"bubblesort" => algos::bubblesort_by_key(array, key),
+		"selectionsort" => algos::selectionsort_by_key(array, key),
+		"insertionsort" => algos::insertionsort_by_key(array, key),
+		"shellsort" => algos::shellsort_by_key(array, key),
+		"mergesort" => algos::mergesort_by_key(array, key),
+		"heapsort" => algos::heapsort_by_key(array, key),
+		"quicksort" => algos::quicksort_by_key(array, key),
+		"introsort" => algos::introsort_by_key(array, key),
+		"timsort" => algos::timsort_by_key(array, key),
+		_ => unreachable!()
+	}
+}
+
+// "algos::quicksort_by", what --algo and --exclude match against
+// the groups only know about registered algorithms so --group isn't accepted
+pub fn name(family: usize) -> String {
+	format!("algos::{}_by", FAMILIES[family].0)
+}
+
+// selected families
+pub fn selection(config: &cli::Config) -> Vec<usize> {
+	(0..FAMILIES.len()).filter(|i| BenchmarkManager::is_selected(config, &name(*i))).collect()
+}
+
+fn size_limit(family: usize, distribution: usize) -> usize {
+	registry::find(FAMILIES[family].1).unwrap().size_limit(inputs::DISTRIBUTIONS[distribution].2)
+}
+
+// timings for one (family, workload, distribution, size)
+pub struct Run {
+	// index into FAMILIES
+	pub family: usize,
+	// index into WORKLOADS
+	pub workload: usize,
+	// index into inputs::DISTRIBUTIONS
+	pub distribution: usize,
+	pub size: usize,
+	// raw timings in ns
+	pub samples: Vec<u64>
+}
+
+fn time<T>(config: &cli::Config, array: &mut [T], sort: impl FnOnce(&mut [T])) -> u64 {
+	if !config.test_mode { thread::sleep(Duration::from_millis(10)) };
+	let start = Instant::now();
+	sort(array);
+	start.elapsed().as_nanos() as u64
+}
+
+fn run_bench(config: &cli::Config, run: &Run, test_i: usize) -> u64 {
+	let family = run.family;
+	let mut keys = sweep::test_vector(config, run.distribution, run.size, test_i);
+	if WORKLOADS[run.workload] == "i32" {
+		let r = time(config, &mut keys, |array| sort_by(family, array, |a, b| a.cmp(b)));
+		utils::verify_sorted(&keys);
+		return r;
+	}
+	let mut records: Vec<Record> = keys.iter().map(|key| Record::new(*key)).collect();
+	let r = if WORKLOADS[run.workload] == "key" {
+		time(config, &mut records, |array| sort_by_key(family, array, |record| record.key))
+	} else {
+		time(config, &mut records, |array| sort_by(family, array, |a, b| a.name.cmp(&b.name)))
+	};
+	utils::verify_sorted(&records.iter().map(|record| record.key).collect::<Vec<i32>>());
+	r
+}
+
+// benchmarks the families on every workload, size, and distribution
+pub fn run(config: &cli::Config, families: &[usize]) -> Vec<Run> {
+	let mut runs = Vec::new();
+	for &family in families {
+		for workload in 0..WORKLOADS.len() {
+			for distribution in &config.distributions {
+				let distribution = inputs::find(distribution).expect("unknown distribution");
+				for size in config.test_sizes() {
+					if size <= size_limit(family, distribution) {
+						runs.push(Run { family, workload, distribution, size, samples: Vec::new() });
+					}
+				}
+			}
+		}
+	}
+	let samples = sweep::run_jobs(config, runs.len(), 1, |run_i, _| {
+		let run = &runs[run_i];
+		format!("{} {} {} {}", name(run.family), WORKLOADS[run.workload],
+			inputs::DISTRIBUTIONS[run.distribution].0, utils::commafy(run.size))
+	}, |run_i, _, test_i| run_bench(config, &runs[run_i], test_i));
+	for (run, mut samples) in runs.iter_mut().zip(samples) {
+		run.samples = samples.pop().unwrap();
+	}
+	runs
+}
+
+// one table per (workload, distribution) with a row per family
+pub fn print(config: &cli::Config, families: &[usize], runs: &[Run]) {
+	let mut tables: Vec<(usize, usize)> = Vec::new();
+	for run in runs {
+		if !tables.contains(&(run.workload, run.distribution)) {
+			tables.push((run.workload, run.distribution));
+		}
+	}
+	let labels: Vec<String> = families.iter().map(|family| FAMILIES[*family].0.to_string()).collect();
+	for (workload, distribution) in tables {
+		let table_runs: Vec<&Run> = runs.iter()
+										.filter(|r| r.workload == workload && r.distribution == distribution)
+										.collect();
+		let sizes: Vec<usize> = config.test_sizes().into_iter()
+									  .filter(|size| table_runs.iter().any(|r| r.size == *size))
+									  .collect();
+		// families past their size limit get a -
+		let mut results: Vec<Vec<Option<BenchmarkResult>>> = sizes.iter().map(|size| {
+			families.iter().map(|family| {
+				table_runs.iter()
+						  .find(|r| r.family == *family && r.size == *size)
+						  .and_then(|r| BenchmarkResult::from_samples(&r.samples, config.outlier_coefficient))
+			}).collect()
+		}).collect();
+		if config.distributions.len() > 1 || inputs::DISTRIBUTIONS[distribution].0 != "uniform" {
+			println!("Comparator workload {} ({} input):", WORKLOADS[workload], inputs::DISTRIBUTIONS[distribution].0);
+		} else {
+			println!("Comparator workload {}:", WORKLOADS[workload]);
+		}
+		sweep::table(config, "family", &labels, &sizes, &mut results).printstd();
+		println!("└ Values in ms; 98% confidence interval displayed; s = statistically equal to fastest; \
+					* = within {}% of fastest", config.diff_threshold * 100.0);
+		println!();
+	}
+}
@@ -0,0 +1,119 @@
+// cell-by-cell comparison of two saved runs (compare subcommand)
+// e.g. before and after a change to an algorithm, or the same build on two machines
+
+use prettytable::*;
+
+use crate::samples::{SampleCell, SampleFile};
+use crate::utils;
+use crate::BenchmarkResult;
+
+pub struct CellComparison {
+	pub algorithm: String,
+	pub distribution: String,
+	pub element: String,
+	pub size: usize,
+	pub a: BenchmarkResult,
+	pub b: BenchmarkResult,
+	// two-tailed p-value for the difference in means
+	pub p: f64,
+	// relative change in mean runtime from a to b, negative means b is faster
+	pub change: f64
+}
+
+impl CellComparison {
+	pub fn is_significant(&self, alpha: f64) -> bool {
+		self.p < alpha
+	}
+	// significantly slower in b by more than max_regression (relative to a)
+	pub fn is_regression(&self, max_regression: f64, alpha: f64) -> bool {
+		self.change > max_regression && self.is_significant(alpha)
+	}
+	// e.g. "sorted" or "uniform u8", i32 elements are the usual and aren't mentioned
+	pub fn input(&self) -> String {
+		if self.element == "i32" {
+			self.distribution.clone()
+		} else {
+			format!("{} {}", self.distribution, self.element)
+		}
+	}
+}
+
+// pairs up cells with the same algorithm, input distribution, element type, and size, in the
+// order they appear in a
+// cells only present on one side, that failed, or without enough runs to summarize are skipped
+pub fn compare_cells(a: &[SampleCell], b: &[SampleCell], outlier_coefficient: f64) -> Vec<CellComparison> {
+	let mut comparisons = Vec::new();
+	for cell_a in a.iter().filter(|c| c.failure.is_none()) {
+		let cell_b = b.iter().find(|c| c.failure.is_none()
+									   && c.algorithm == cell_a.algorithm
+									   && c.distribution == cell_a.distribution
+									   && c.element == cell_a.element
+									   && c.size == cell_a.size);
+		if cell_b.is_none() {
+			continue;
+		}
+		let result_a = BenchmarkResult::from_samples(&cell_a.samples, outlier_coefficient);
+		let result_b = BenchmarkResult::from_samples(&cell_b.unwrap().samples, outlier_coefficient);
+		if let (Option::Some(result_a), Option::Some(result_b)) = (result_a, result_b) {
+			let (p, _) = result_a.compare(&result_b);
+			comparisons.push(CellComparison {
+				algorithm: cell_a.algorithm.clone(),
+				distribution: cell_a.distribution.clone(),
+				element: cell_a.element.clone(),
+				size: cell_a.size,
+				change: (result_b.mean - result_a.mean) / result_a.mean,
+				a: result_a,
+				b: result_b,
+				p
+			});
+		}
+	}
+	comparisons
+}
+
+fn describe(label: &str, path: &str, file: &SampleFile) -> String {
+	format!("{} = {} (profile: {}, seed: {}, cpu: {}, {})", label, path, file.metadata.profile,
+		file.metadata.seed, file.metadata.cpu, file.metadata.rustc)
+}
+
+pub fn print(path_a: &str, a: &SampleFile, path_b: &str, b: &SampleFile,
+			 comparisons: &[CellComparison], alpha: f64) {
+	println!("{}", describe("A", path_a, a));
+	println!("{}", describe("B", path_b, b));
+	let mut sizes: Vec<usize> = comparisons.iter().map(|c| c.size).collect();
+	sizes.sort_unstable();
+	sizes.dedup();
+	// one row per (algorithm, input)
+	let mut rows: Vec<(&str, String)> = Vec::new();
+	for c in comparisons {
+		let row = (c.algorithm.as_str(), c.input());
+		if !rows.contains(&row) {
+			rows.push(row);
+		}
+	}
+	let mut table = Table::new();
+	table.add_row(Row::new(std::iter::once(String::from(""))
+							.chain(sizes.iter().map(|x| utils::commafy(*x)))
+							.map(|x| Cell::new(&x)).collect()));
+	for (algorithm, input) in rows {
+		// uniform is the usual input, anything else gets labeled
+		let mut row = vec![if input == "uniform" {
+			Cell::new(algorithm)
+		} else {
+			Cell::new(&format!("{} ({})", algorithm, input))
+		}];
+		for size in &sizes {
+			match comparisons.iter().find(|c| c.algorithm == algorithm && c.input() == input
+											  && c.size == *size) {
+				Option::Some(c) => row.push(Cell::new(&format!("{:.5} → {:.5} ({:+.1}%) {}",
+					c.a.mean / 1e6, c.b.mean / 1e6, c.change * 100.0,
+					if c.is_significant(alpha) {"s"} else {" "}))),
+				Option::None => row.push(Cell::new("-"))
+			}
+		}
+		table.add_row(Row::new(row));
+	}
+	table.printstd();
+	println!("└ Mean runtime in ms for A → B and relative change (negative = B is faster); \
+				s = statistically significant (p < {})", alpha);
+}
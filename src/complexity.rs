@@ -0,0 +1,111 @@
+// empirical complexity (--complexity)
+// fits mean runtimes across the test sizes to t = c * f(n) for a few candidate models and checks the
+// growth against the complexity each algorithm is registered with (registry.rs)
+// fits are done on logs (ln t = ln c + ln f(n)) so every size counts the same, the free exponent is the
+// slope of ln t against ln n. a declared label is contradicted when the measured exponent is more
+// than EXPONENT_TOLERANCE away from the declared model's exponent over the same sizes
+// labels are worst-case bounds, so growing slower than declared can just mean an easy input
+
+// below this the timer and per-run overhead swamp the sort itself
+pub const FIT_MIN_SIZE: usize = 1000;
+pub const MIN_FIT_POINTS: usize = 2;
+const EXPONENT_TOLERANCE: f64 = 0.2;
+
+// n^exponent, times log2 n if log
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Model {
+	pub exponent: f64,
+	pub log: bool
+}
+
+pub const CANDIDATES: [Model; 5] = [
+	Model { exponent: 1.0, log: false },
+	Model { exponent: 1.0, log: true },
+	Model { exponent: 1.25, log: false },
+	Model { exponent: 1.5, log: false },
+	Model { exponent: 2.0, log: false }
+];
+
+impl Model {
+	pub fn name(&self) -> String {
+		let power = if self.exponent == 1.0 { String::from("n") } else { format!("n^{}", self.exponent) };
+		if self.log { format!("{} log n", power) } else { power }
+	}
+	pub fn f(&self, n: usize) -> f64 {
+		let n = n as f64;
+		let f = n.powf(self.exponent);
+		if self.log { f * n.log2() } else { f }
+	}
+	// the model's own log-log slope over the sizes, just the exponent unless there's a log factor
+	pub fn local_exponent(&self, sizes: &[usize]) -> f64 {
+		let points: Vec<(usize, f64)> = sizes.iter().map(|n| (*n, self.f(*n))).collect();
+		exponent(&points)
+	}
+}
+
+// slope of ln t against ln n, points are (size, time)
+pub fn exponent(points: &[(usize, f64)]) -> f64 {
+	let x: Vec<f64> = points.iter().map(|p| (p.0 as f64).ln()).collect();
+	let y: Vec<f64> = points.iter().map(|p| p.1.ln()).collect();
+	let mean_x = x.iter().sum::<f64>() / x.len() as f64;
+	let mean_y = y.iter().sum::<f64>() / y.len() as f64;
+	let covariance: f64 = x.iter().zip(&y).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+	let variance: f64 = x.iter().map(|x| (x - mean_x).powi(2)).sum();
+	covariance / variance
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Fit {
+	pub model: Model,
+	// t = constant * f(n), in the units of the times
+	pub constant: f64,
+	// root mean square of the log residuals, 0.1 is roughly 10% off on average
+	pub error: f64
+}
+
+pub fn fit(model: Model, points: &[(usize, f64)]) -> Fit {
+	let residuals: Vec<f64> = points.iter().map(|(n, t)| t.ln() - model.f(*n).ln()).collect();
+	let ln_c = residuals.iter().sum::<f64>() / residuals.len() as f64;
+	let error = (residuals.iter().map(|r| (r - ln_c).powi(2)).sum::<f64>() / residuals.len() as f64).sqrt();
+	Fit { model, constant: ln_c.exp(), error }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+	Consistent,
+	// grows faster than declared, a real contradiction
+	Faster,
+	// grows slower than declared
+	Slower
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Report {
+	pub exponent: f64,
+	// the candidate with the lowest error
+	pub best: Fit,
+	pub verdict: Verdict
+}
+
+// none if there aren't enough sizes at or above FIT_MIN_SIZE
+pub fn analyze(points: &[(usize, f64)], declared: Model) -> Option<Report> {
+	let points: Vec<(usize, f64)> = points.iter().copied().filter(|p| p.0 >= FIT_MIN_SIZE).collect();
+	if points.len() < MIN_FIT_POINTS {
+		return Option::None;
+	}
+	let exponent = exponent(&points);
+	let best = CANDIDATES.iter()
+						 .map(|model| fit(*model, &points))
+						 .min_by(|a, b| a.error.partial_cmp(&b.error).unwrap())
+						 .unwrap();
+	let sizes: Vec<usize> = points.iter().map(|p| p.0).collect();
+	let difference = exponent - declared.local_exponent(&sizes);
+	let verdict = if difference > EXPONENT_TOLERANCE {
+		Verdict::Faster
+	} else if difference < -EXPONENT_TOLERANCE {
+		Verdict::Slower
+	} else {
+		Verdict::Consistent
+	};
+	Option::Some(Report { exponent, best, verdict })
+}
@@ -0,0 +1,115 @@
+// recorded benchmark inputs, written with --dump-inputs and replayed with --inputs-from
+// test vectors are normally regenerated from the seed for every run and thrown away, a corpus keeps
+// the exact keys so the arrays behind an odd result can be replayed (e.g. under a profiler) and so
+// data from elsewhere can go through the same pipeline
+//
+// format: little-endian binary
+//   header: "SORTKEYS", u32 version, u64 seed, u64 number of arrays
+//   array:  u32 name length, utf-8 name, u64 test index, u64 length, length i32 keys
+// the seed is the one the arrays were generated with, replaying under it gives randomized algorithms
+// the same seeds too. external data can use 0
+// arrays with the same name make up one input (a distribution, "uniform" etc. for dumped runs), their
+// lengths are the test sizes and test indices order the runs within a size
+
+use std::convert::TryInto;
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 8] = b"SORTKEYS";
+const VERSION: u32 = 1;
+// names are distribution and dataset names, anything longer is a corrupt length
+const MAX_NAME_LEN: usize = 4096;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Array {
+	pub name: String,
+	pub test_i: usize,
+	pub keys: Vec<i32>
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Corpus {
+	pub seed: u64,
+	pub arrays: Vec<Array>
+}
+
+// arrays are written one at a time so a dump doesn't have to fit in memory
+pub fn write_header(writer: &mut impl Write, seed: u64, n_arrays: usize) -> io::Result<()> {
+	writer.write_all(MAGIC)?;
+	writer.write_all(&VERSION.to_le_bytes())?;
+	writer.write_all(&seed.to_le_bytes())?;
+	writer.write_all(&(n_arrays as u64).to_le_bytes())
+}
+
+pub fn write_array(writer: &mut impl Write, name: &str, test_i: usize, keys: &[i32]) -> io::Result<()> {
+	if name.len() > MAX_NAME_LEN {
+		return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("input name is over {} bytes", MAX_NAME_LEN)));
+	}
+	writer.write_all(&(name.len() as u32).to_le_bytes())?;
+	writer.write_all(name.as_bytes())?;
+	writer.write_all(&(test_i as u64).to_le_bytes())?;
+	writer.write_all(&(keys.len() as u64).to_le_bytes())?;
+	let mut bytes = Vec::with_capacity(keys.len() * 4);
+	for key in keys {
+		bytes.extend_from_slice(&key.to_le_bytes());
+	}
+	writer.write_all(&bytes)
+}
+
+fn read_bytes(reader: &mut impl Read, n: usize) -> Result<Vec<u8>, String> {
+	let mut bytes = vec![0; n];
+	reader.read_exact(&mut bytes).map_err(|e| match e.kind() {
+		io::ErrorKind::UnexpectedEof => String::from("file is truncated"),
+		_ => e.to_string()
+	})?;
+	Ok(bytes)
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32, String> {
+	Ok(u32::from_le_bytes(read_bytes(reader, 4)?.try_into().unwrap()))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64, String> {
+	Ok(u64::from_le_bytes(read_bytes(reader, 8)?.try_into().unwrap()))
+}
+
+pub fn read(mut reader: impl Read) -> Result<Corpus, String> {
+	if read_bytes(&mut reader, MAGIC.len()).ok().as_deref() != Option::Some(&MAGIC[..]) {
+		return Err(String::from("not an input corpus"));
+	}
+	let version = read_u32(&mut reader)?;
+	if version != VERSION {
+		return Err(format!("unsupported corpus version {}", version));
+	}
+	let seed = read_u64(&mut reader)?;
+	let n_arrays = read_u64(&mut reader)?;
+	let mut arrays = Vec::new();
+	for _ in 0..n_arrays {
+		let name_len = read_u32(&mut reader)? as usize;
+		if name_len > MAX_NAME_LEN {
+			return Err(format!("input name is too long ({} bytes)", name_len));
+		}
+		let name = String::from_utf8(read_bytes(&mut reader, name_len)?).map_err(|_| String::from("name isn't utf-8"))?;
+		if name.is_empty() {
+			return Err(String::from("empty input name"));
+		}
+		let test_i = read_u64(&mut reader)? as usize;
+		let len = read_u64(&mut reader)? as usize;
+		// read in chunks, a corrupt length shouldn't allocate everything up front
+		let mut keys = Vec::new();
+		while keys.len() < len {
+			let chunk = std::cmp::min(len - keys.len(), 1 << 16);
+			let bytes = read_bytes(&mut reader, chunk * 4)?;
+			keys.extend(bytes.chunks_exact(4).map(|b| i32::from_le_bytes(b.try_into().unwrap())));
+		}
+		arrays.push(Array { name, test_i, keys });
+	}
+	if reader.read(&mut [0]).map_err(|e| e.to_string())? != 0 {
+		return Err(String::from("trailing data after the last array"));
+	}
+	Ok(Corpus { seed, arrays })
+}
+
+pub fn load(path: &str) -> Result<Corpus, String> {
+	let file = std::fs::File::open(path).map_err(|e| format!("can't read {}: {}", path, e))?;
+	read(io::BufReader::new(file)).map_err(|e| format!("{}: {}", path, e))
+}
@@ -0,0 +1,130 @@
+// user-supplied datasets (--dataset)
+// real data is rarely shaped like any of the synthetic distributions, this reads a file of integers
+// and draws each test vector from it, either as a random contiguous window (keeps whatever runs and
+// clusters the data has) or as a resample with replacement (keeps the value distribution only)
+// formats, picked by extension:
+//   .csv                  one column of a csv file, path.csv:<header name or 1-based index>
+//   .bin, .raw, .i32      raw little-endian i32s
+//   anything else         one integer per line, blank lines are skipped
+// values have to be integers that fit in an i32, they're used as keys directly
+
+use crate::inputs;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sampling {
+	// a random contiguous run of the data, sizes larger than the dataset are skipped
+	Window,
+	// values drawn uniformly with replacement, any size works
+	Resample
+}
+
+impl Sampling {
+	pub fn from_name(name: &str) -> Result<Sampling, String> {
+		match name {
+			"window" => Ok(Sampling::Window),
+			"resample" => Ok(Sampling::Resample),
+			_ => Err(format!("unknown sampling \"{}\" (expected window or resample)", name))
+		}
+	}
+	pub fn name(&self) -> &'static str {
+		match self {
+			Sampling::Window => "window",
+			Sampling::Resample => "resample"
+		}
+	}
+}
+
+fn parse_value(value: &str, line: usize) -> Result<i32, String> {
+	let value = value.trim();
+	match value.parse::<i64>() {
+		Ok(v) if v >= i32::MIN as i64 && v <= i32::MAX as i64 => Ok(v as i32),
+		Ok(_) => Err(format!("line {}: {} doesn't fit in an i32", line, value)),
+		Err(_) => Err(format!("line {}: \"{}\" isn't an integer", line, value))
+	}
+}
+
+pub fn parse_lines(text: &str) -> Result<Vec<i32>, String> {
+	text.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.trim().is_empty())
+		.map(|(i, line)| parse_value(line, i + 1))
+		.collect()
+}
+
+pub fn parse_binary(bytes: &[u8]) -> Result<Vec<i32>, String> {
+	let chunks = bytes.chunks_exact(4);
+	if !chunks.remainder().is_empty() {
+		return Err(format!("length {} isn't a multiple of 4 bytes", bytes.len()));
+	}
+	Ok(chunks.map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect())
+}
+
+// fields are split on commas, quoted fields can't contain commas
+fn fields(line: &str) -> Vec<&str> {
+	line.split(',').map(|f| f.trim().trim_matches('"')).collect()
+}
+
+// column is a header name or a 1-based index, with an index a first row that isn't a number is taken
+// to be a header
+pub fn parse_csv(text: &str, column: &str) -> Result<Vec<i32>, String> {
+	let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).peekable();
+	let index = match column.parse::<usize>() {
+		Ok(0) => return Err(String::from("csv columns are numbered from 1")),
+		Ok(n) => {
+			if let Option::Some((i, line)) = lines.peek() {
+				if fields(line).get(n - 1).is_some_and(|f| parse_value(f, i + 1).is_err()) {
+					lines.next();
+				}
+			}
+			n - 1
+		}
+		Err(_) => {
+			let header = lines.next().map(|(_, line)| fields(line)).unwrap_or_default();
+			header.iter().position(|f| *f == column)
+				  .ok_or_else(|| format!("no column named \"{}\" (header: {})", column, header.join(", ")))?
+		}
+	};
+	lines.map(|(i, line)| match fields(line).get(index) {
+		Option::Some(field) => parse_value(field, i + 1),
+		Option::None => Err(format!("line {}: no column {}", i + 1, index + 1))
+	}).collect()
+}
+
+// "orders.csv:amount" -> ("orders.csv", some("amount")), only csv files take a column
+fn split_spec(spec: &str) -> (&str, Option<&str>) {
+	match spec.rsplit_once(':') {
+		Option::Some((path, column)) if path.ends_with(".csv") => (path, Option::Some(column)),
+		_ => (spec, Option::None)
+	}
+}
+
+// what the dataset is called in the tables: the file name without its extension, plus the column
+pub fn name(spec: &str) -> String {
+	let (path, column) = split_spec(spec);
+	let file = std::path::Path::new(path).file_stem().map_or(path.into(), |s| s.to_string_lossy());
+	match column {
+		Option::Some(column) => format!("{}.{}", file, column),
+		Option::None => file.into_owned()
+	}
+}
+
+pub fn parse(spec: &str, contents: &[u8]) -> Result<Vec<i32>, String> {
+	let (path, column) = split_spec(spec);
+	let text = || String::from_utf8(contents.to_vec()).map_err(|_| String::from("file isn't utf-8"));
+	let values = match path.rsplit('.').next() {
+		Option::Some("csv") => parse_csv(&text()?, column.unwrap_or("1"))?,
+		Option::Some("bin") | Option::Some("raw") | Option::Some("i32") => parse_binary(contents)?,
+		_ => parse_lines(&text()?)?
+	};
+	if values.is_empty() {
+		return Err(String::from("no values"));
+	}
+	Ok(values)
+}
+
+pub fn load(spec: &str, sampling: Sampling) -> Result<inputs::Source, String> {
+	let (path, _) = split_spec(spec);
+	let contents = std::fs::read(path).map_err(|e| format!("can't read {}: {}", path, e))?;
+	let values = parse(spec, &contents).map_err(|e| format!("{}: {}", path, e))?;
+	Ok(inputs::Source::Dataset { name: name(spec), values, sampling })
+}
@@ -0,0 +1,220 @@
+// element types the benchmark can sort
+// input distributions generate i32 keys, which are converted to the element type being benchmarked
+// before sorting. conversions preserve order so a sorted input stays sorted (narrow types end up with
+// ties where the keys only differed in low bits)
+
+use std::time::Instant;
+
+use crate::operations::Counted;
+use crate::utils;
+
+pub trait Element: Ord + Copy + std::fmt::Debug {
+	fn from_key(key: i32) -> Self;
+}
+
+// maps i32 onto u32 preserving order
+fn unsigned(key: i32) -> u32 {
+	(key as u32) ^ 0x8000_0000
+}
+
+impl Element for i32 {
+	fn from_key(key: i32) -> Self {
+		key
+	}
+}
+
+impl Element for u8 {
+	fn from_key(key: i32) -> Self {
+		(unsigned(key) >> 24) as u8
+	}
+}
+
+impl Element for u16 {
+	fn from_key(key: i32) -> Self {
+		(unsigned(key) >> 16) as u16
+	}
+}
+
+// wider types repeat the key so every byte varies (matters for anything that looks at digits)
+impl Element for u64 {
+	fn from_key(key: i32) -> Self {
+		let key = unsigned(key) as u64;
+		key << 32 | key
+	}
+}
+
+impl Element for u128 {
+	fn from_key(key: i32) -> Self {
+		let key = u64::from_key(key) as u128;
+		key << 64 | key
+	}
+}
+
+// an integer key with a payload dragged along, for looking at the cost of moving big elements
+// around. only the key takes part in comparisons
+#[derive(Clone, Copy)]
+pub struct Record<const N: usize> {
+	pub key: i32,
+	// never looked at, it's just along for the ride
+	#[allow(dead_code)]
+	pub payload: [u8; N]
+}
+
+impl<const N: usize> PartialEq for Record<N> {
+	fn eq(&self, other: &Self) -> bool {
+		self.key == other.key
+	}
+}
+
+impl<const N: usize> Eq for Record<N> {}
+
+impl<const N: usize> PartialOrd for Record<N> {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Option::Some(self.cmp(other))
+	}
+}
+
+impl<const N: usize> Ord for Record<N> {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.key.cmp(&other.key)
+	}
+}
+
+// the payload would drown out everything else in failed assertion messages
+impl<const N: usize> std::fmt::Debug for Record<N> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "Record<{}>({})", N, self.key)
+	}
+}
+
+impl<const N: usize> Element for Record<N> {
+	fn from_key(key: i32) -> Self {
+		let mut payload = [0; N];
+		for (i, byte) in payload.iter_mut().enumerate() {
+			*byte = key.to_le_bytes()[i % 4];
+		}
+		Record { key, payload }
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ElementType {
+	I32,
+	U8,
+	U16,
+	U64,
+	U128,
+	Record16,
+	Record64,
+	Record256
+}
+
+impl ElementType {
+	pub const ALL: &'static [ElementType] = &[
+		ElementType::I32, ElementType::U8, ElementType::U16, ElementType::U64, ElementType::U128,
+		ElementType::Record16, ElementType::Record64, ElementType::Record256
+	];
+	pub fn from_name(name: &str) -> Option<ElementType> {
+		ElementType::ALL.iter().copied().find(|e| e.name() == name)
+	}
+	pub fn name(&self) -> &'static str {
+		match self {
+			ElementType::I32 => "i32",
+			ElementType::U8 => "u8",
+			ElementType::U16 => "u16",
+			ElementType::U64 => "u64",
+			ElementType::U128 => "u128",
+			ElementType::Record16 => "record16",
+			ElementType::Record64 => "record64",
+			ElementType::Record256 => "record256"
+		}
+	}
+}
+
+// one algorithm instantiated for every element type
+// algorithms that only exist for i32 (radixsort, the c / c++ ones) leave the rest as none
+#[derive(Clone, Copy)]
+pub struct Sorts {
+	pub i32: fn(&mut [i32]),
+	pub u8: Option<fn(&mut [u8])>,
+	pub u16: Option<fn(&mut [u16])>,
+	pub u64: Option<fn(&mut [u64])>,
+	pub u128: Option<fn(&mut [u128])>,
+	pub record16: Option<fn(&mut [Record<16>])>,
+	pub record64: Option<fn(&mut [Record<64>])>,
+	pub record256: Option<fn(&mut [Record<256>])>,
+	// instrumented instantiation for --operations, not benchmarked
+	pub counted: Option<fn(&mut [Counted])>
+}
+
+// converts the keys, waits for settle(), then times the sort
+fn time_sort<T: Element>(sort: fn(&mut [T]), keys: &[i32], settle: impl FnOnce()) -> u64 {
+	let mut array: Vec<T> = keys.iter().map(|key| T::from_key(*key)).collect();
+	settle();
+	let start = Instant::now();
+	sort(&mut array);
+	let r = start.elapsed().as_nanos() as u64;
+	// this is covered in test cases but just to be sure...
+	utils::verify_sorted(&array);
+	r
+}
+
+impl Sorts {
+	pub fn i32_only(sort: fn(&mut [i32])) -> Sorts {
+		Sorts {
+			i32: sort,
+			u8: Option::None,
+			u16: Option::None,
+			u64: Option::None,
+			u128: Option::None,
+			record16: Option::None,
+			record64: Option::None,
+			record256: Option::None,
+			counted: Option::None
+		}
+	}
+	pub fn supports(&self, element: ElementType) -> bool {
+		match element {
+			ElementType::I32 => true,
+			ElementType::U8 => self.u8.is_some(),
+			ElementType::U16 => self.u16.is_some(),
+			ElementType::U64 => self.u64.is_some(),
+			ElementType::U128 => self.u128.is_some(),
+			ElementType::Record16 => self.record16.is_some(),
+			ElementType::Record64 => self.record64.is_some(),
+			ElementType::Record256 => self.record256.is_some()
+		}
+	}
+	// sorts keys converted to the element type and returns the time taken in ns
+	// settle is called right before the clock starts
+	// panics if the algorithm doesn't support the element type
+	pub fn run(&self, element: ElementType, keys: &[i32], settle: impl FnOnce()) -> u64 {
+		match element {
+			ElementType::I32 => time_sort(self.i32, keys, settle),
+			ElementType::U8 => time_sort(self.u8.unwrap(), keys, settle),
+			ElementType::U16 => time_sort(self.u16.unwrap(), keys, settle),
+			ElementType::U64 => time_sort(self.u64.unwrap(), keys, settle),
+			ElementType::U128 => time_sort(self.u128.unwrap(), keys, settle),
+			ElementType::Record16 => time_sort(self.record16.unwrap(), keys, settle),
+			ElementType::Record64 => time_sort(self.record64.unwrap(), keys, settle),
+			ElementType::Record256 => time_sort(self.record256.unwrap(), keys, settle)
+		}
+	}
+}
+
+// Sorts for a function generic over the element type
+#[macro_export] macro_rules! sorts {
+	($($f:ident)::+) => {
+		elements::Sorts {
+			i32: $($f)::+::<i32>,
+			u8: Option::Some($($f)::+::<u8>),
+			u16: Option::Some($($f)::+::<u16>),
+			u64: Option::Some($($f)::+::<u64>),
+			u128: Option::Some($($f)::+::<u128>),
+			record16: Option::Some($($f)::+::<elements::Record<16>>),
+			record64: Option::Some($($f)::+::<elements::Record<64>>),
+			record256: Option::Some($($f)::+::<elements::Record<256>>),
+			counted: Option::Some($($f)::+::<operations::Counted>)
+		}
+	};
+}
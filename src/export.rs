@@ -0,0 +1,227 @@
+// machine-readable export of benchmark results (--output json / csv)
+// the prettytable output is nice to look at but miserable to parse
+
+use std::io::{self, Write};
+
+use crate::BenchmarkResult;
+
+// information about the run itself, needed to make sense of results from different machines
+#[derive(Clone)]
+pub struct RunMetadata {
+	pub profile: String,
+	pub seed: u64,
+	pub n_tests: usize,
+	pub min_size: usize,
+	pub max_size: usize,
+	pub distributions: Vec<String>,
+	pub elements: Vec<String>,
+	// nanoseconds
+	pub runtime_limit: u64,
+	pub n_workers: usize,
+	pub alpha: f64,
+	pub diff_threshold: f64,
+	pub outlier_coefficient: f64,
+	pub cpu: String,
+	pub rustc: String,
+	// nanoseconds
+	pub runtime: u64
+}
+
+impl RunMetadata {
+	// (key, value) pairs, used for the csv header and samples files
+	pub fn to_pairs(&self) -> Vec<(&'static str, String)> {
+		vec![
+			("profile", self.profile.clone()),
+			("seed", self.seed.to_string()),
+			("n_tests", self.n_tests.to_string()),
+			("min_size", self.min_size.to_string()),
+			("max_size", self.max_size.to_string()),
+			("distributions", self.distributions.join(",")),
+			("elements", self.elements.join(",")),
+			("runtime_limit_ns", self.runtime_limit.to_string()),
+			("n_workers", self.n_workers.to_string()),
+			("alpha", self.alpha.to_string()),
+			("diff_threshold", self.diff_threshold.to_string()),
+			("outlier_coefficient", self.outlier_coefficient.to_string()),
+			("cpu", self.cpu.clone()),
+			("rustc", self.rustc.clone()),
+			("runtime_ns", self.runtime.to_string())
+		]
+	}
+	pub fn from_pairs(pairs: &[(String, String)]) -> Result<RunMetadata, String> {
+		let get = |key: &str| pairs.iter()
+								   .find(|(k, _)| k == key)
+								   .map(|(_, v)| v.as_str())
+								   .ok_or(format!("missing metadata field {}", key));
+		fn number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+			value.parse::<T>().map_err(|_| format!("bad metadata field {}: \"{}\"", key, value))
+		}
+		Ok(RunMetadata {
+			profile: String::from(get("profile")?),
+			seed: number("seed", get("seed")?)?,
+			n_tests: number("n_tests", get("n_tests")?)?,
+			min_size: number("min_size", get("min_size")?)?,
+			max_size: number("max_size", get("max_size")?)?,
+			// files from before input distributions only have uniform inputs
+			distributions: get("distributions").unwrap_or("uniform")
+											   .split(',')
+											   .map(String::from)
+											   .collect(),
+			// and before element types only sorted i32
+			elements: get("elements").unwrap_or("i32").split(',').map(String::from).collect(),
+			runtime_limit: number("runtime_limit_ns", get("runtime_limit_ns")?)?,
+			n_workers: number("n_workers", get("n_workers")?)?,
+			alpha: number("alpha", get("alpha")?)?,
+			diff_threshold: number("diff_threshold", get("diff_threshold")?)?,
+			outlier_coefficient: number("outlier_coefficient", get("outlier_coefficient")?)?,
+			cpu: String::from(get("cpu")?),
+			rustc: String::from(get("rustc")?),
+			runtime: number("runtime_ns", get("runtime_ns")?)?
+		})
+	}
+}
+
+// why a cell has no result, the first job in it that failed or timed out
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellFailure {
+	pub test_i: usize,
+	pub message: String,
+	pub timed_out: bool
+}
+
+impl CellFailure {
+	pub fn status(&self) -> &'static str {
+		if self.timed_out { "timed_out" } else { "failed" }
+	}
+	pub fn from_status(status: &str, test_i: usize, message: &str) -> Option<CellFailure> {
+		let timed_out = match status {
+			"failed" => false,
+			"timed_out" => true,
+			_ => return Option::None
+		};
+		Option::Some(CellFailure { test_i, message: String::from(message), timed_out })
+	}
+}
+
+// one (algorithm, input, size) cell of the results table
+pub struct CellRecord<'a> {
+	pub algorithm: &'a str,
+	pub complexity: &'a str,
+	pub distribution: &'a str,
+	pub element: &'a str,
+	pub size: usize,
+	pub result: Result<&'a BenchmarkResult, &'a CellFailure>
+}
+
+fn json_string(s: &str) -> String {
+	let mut out = String::with_capacity(s.len() + 2);
+	out.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => out.push_str("\\\""),
+			'\\' => out.push_str("\\\\"),
+			'\n' => out.push_str("\\n"),
+			'\t' => out.push_str("\\t"),
+			c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+			c => out.push(c)
+		}
+	}
+	out.push('"');
+	out
+}
+
+// json has no nan or infinity, degenerate statistics (e.g. a t interval from too few runs) become null
+fn json_number(x: f64) -> String {
+	if x.is_finite() {
+		x.to_string()
+	} else {
+		String::from("null")
+	}
+}
+
+// the csv equivalent of json_number, an empty field
+fn csv_number(x: f64) -> String {
+	if x.is_finite() {
+		x.to_string()
+	} else {
+		String::new()
+	}
+}
+
+fn csv_field(s: &str) -> String {
+	if s.contains([',', '"', '\n']) {
+		format!("\"{}\"", s.replace('"', "\"\""))
+	} else {
+		String::from(s)
+	}
+}
+
+// 98% confidence interval half-width in nanoseconds (t_ci is in ms for display)
+fn ci_ns(result: &BenchmarkResult) -> f64 {
+	result.t_ci() * 1e6
+}
+
+pub fn write_json(out: &mut impl Write, metadata: &RunMetadata, cells: &[CellRecord]) -> io::Result<()> {
+	writeln!(out, "{{")?;
+	writeln!(out, "\t\"metadata\": {{")?;
+	writeln!(out, "\t\t\"profile\": {},", json_string(&metadata.profile))?;
+	writeln!(out, "\t\t\"seed\": {},", metadata.seed)?;
+	writeln!(out, "\t\t\"n_tests\": {},", metadata.n_tests)?;
+	writeln!(out, "\t\t\"min_size\": {},", metadata.min_size)?;
+	writeln!(out, "\t\t\"max_size\": {},", metadata.max_size)?;
+	let distributions: Vec<String> = metadata.distributions.iter().map(|d| json_string(d)).collect();
+	writeln!(out, "\t\t\"distributions\": [{}],", distributions.join(", "))?;
+	let elements: Vec<String> = metadata.elements.iter().map(|e| json_string(e)).collect();
+	writeln!(out, "\t\t\"elements\": [{}],", elements.join(", "))?;
+	writeln!(out, "\t\t\"runtime_limit_ns\": {},", metadata.runtime_limit)?;
+	writeln!(out, "\t\t\"n_workers\": {},", metadata.n_workers)?;
+	writeln!(out, "\t\t\"alpha\": {},", json_number(metadata.alpha))?;
+	writeln!(out, "\t\t\"diff_threshold\": {},", json_number(metadata.diff_threshold))?;
+	writeln!(out, "\t\t\"outlier_coefficient\": {},", json_number(metadata.outlier_coefficient))?;
+	writeln!(out, "\t\t\"cpu\": {},", json_string(&metadata.cpu))?;
+	writeln!(out, "\t\t\"rustc\": {},", json_string(&metadata.rustc))?;
+	writeln!(out, "\t\t\"runtime_ns\": {}", metadata.runtime)?;
+	writeln!(out, "\t}},")?;
+	writeln!(out, "\t\"results\": [")?;
+	for (i, cell) in cells.iter().enumerate() {
+		// failed cells have no statistics, just what went wrong
+		let fields = match cell.result {
+			Ok(r) => format!("\"status\": \"ok\", \"mean_ns\": {}, \"stdev_ns\": {}, \"count\": {}, \"ci98_ns\": {}, \
+							 \"outliers\": {}, \"is_fastest\": {}, \"is_stat_fastest\": {}",
+							 json_number(r.mean), json_number(r.stdev), r.count, json_number(ci_ns(r)), r.outliers,
+							 r.is_fastest, r.is_stat_fastest),
+			Err(f) => format!("\"status\": {}, \"failed_test\": {}, \"failure\": {}",
+							  json_string(f.status()), f.test_i, json_string(&f.message))
+		};
+		writeln!(out,
+			"\t\t{{\"algorithm\": {}, \"complexity\": {}, \"distribution\": {}, \"element\": {}, \
+			\"size\": {}, {}}}{}",
+				json_string(cell.algorithm), json_string(cell.complexity),
+				json_string(cell.distribution), json_string(cell.element), cell.size, fields,
+				if i + 1 < cells.len() {","} else {""})?;
+	}
+	writeln!(out, "\t]")?;
+	writeln!(out, "}}")?;
+	Ok(())
+}
+
+// metadata goes in leading comment lines so the rest of the file is a plain csv table
+pub fn write_csv(out: &mut impl Write, metadata: &RunMetadata, cells: &[CellRecord]) -> io::Result<()> {
+	for (key, value) in metadata.to_pairs() {
+		writeln!(out, "# {}: {}", key, value)?;
+	}
+	writeln!(out, "algorithm,complexity,distribution,element,size,mean_ns,stdev_ns,count,ci98_ns,\
+					outliers,is_fastest,is_stat_fastest,status,failed_test,failure")?;
+	for cell in cells {
+		// the statistics columns are empty for failed cells, the failure columns for the rest
+		let fields = match cell.result {
+			Ok(r) => format!("{},{},{},{},{},{},{},ok,,", csv_number(r.mean), csv_number(r.stdev), r.count,
+							 csv_number(ci_ns(r)), r.outliers, r.is_fastest, r.is_stat_fastest),
+			Err(f) => format!(",,,,,,,{},{},{}", f.status(), f.test_i, csv_field(&f.message))
+		};
+		writeln!(out, "{},{},{},{},{},{}",
+			csv_field(cell.algorithm), csv_field(cell.complexity), csv_field(cell.distribution),
+			csv_field(cell.element), cell.size, fields)?;
+	}
+	Ok(())
+}
@@ -0,0 +1,194 @@
+// shellsort gap sequence search (gap-search subcommand)
+// hill climbing: starting from ciura's sequence extended past its last gap, repeatedly make a small
+// random change (nudge, insert, or remove a gap) and keep it if the sequence scores no worse
+// every candidate is scored on the same test vectors so scores are directly comparable, by default
+// on comparison counts since they're exact and cheap to get. scoring on runtime works too but the
+// noise means some changes are kept or thrown away by chance
+
+use std::time::Instant;
+
+use prettytable::*;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::algos;
+use crate::cli;
+use crate::gaps;
+use crate::inputs;
+use crate::operations;
+use crate::utils;
+use crate::BenchmarkManager;
+
+// test vectors per (distribution, size) a candidate is scored on
+const SCORE_RUNS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Score {
+	Comparisons,
+	Time
+}
+
+impl Score {
+	pub fn from_name(name: &str) -> Result<Score, String> {
+		match name {
+			"comparisons" => Ok(Score::Comparisons),
+			"time" => Ok(Score::Time),
+			_ => Err(format!("unknown score \"{}\" (available: comparisons, time)", name))
+		}
+	}
+	pub fn name(&self) -> &'static str {
+		match self {
+			Score::Comparisons => "comparisons",
+			Score::Time => "time"
+		}
+	}
+}
+
+// the test vectors every candidate is scored on, one list per (distribution, size)
+pub fn test_vectors(config: &cli::Config) -> Vec<Vec<Vec<i32>>> {
+	let mut cells = Vec::new();
+	for distribution in &config.distributions {
+		let distribution = inputs::find(distribution).expect("unknown distribution");
+		for size in config.test_sizes() {
+			cells.push((0..SCORE_RUNS).map(|test_i| {
+				let mut test_vector = vec![0; size];
+				let mut rng = SmallRng::seed_from_u64(BenchmarkManager::seedgen(config.seed, test_i));
+				(inputs::DISTRIBUTIONS[distribution].1)(&mut test_vector, &mut rng);
+				test_vector
+			}).collect());
+		}
+	}
+	cells
+}
+
+// mean comparisons (or ns) per n log2 n over every test vector, lower is better
+// gaps_for gives the gap sequence for an array length, largest gap first
+pub fn score(score: Score, test_vectors: &[Vec<Vec<i32>>], gaps_for: impl Fn(usize) -> Vec<usize>) -> f64 {
+	let total: f64 = test_vectors.iter().map(|cell| {
+		let size = cell[0].len();
+		let gaps = gaps_for(size);
+		let sum: u64 = cell.iter().map(|keys| match score {
+			Score::Comparisons => operations::count(|array| algos::shell_sequence(array, &gaps), keys).comparisons,
+			Score::Time => {
+				let mut array = keys.clone();
+				let start = Instant::now();
+				algos::shell_sequence(&mut array, &gaps);
+				let r = start.elapsed().as_nanos() as u64;
+				utils::verify_sorted(&array);
+				r
+			}
+		}).sum();
+		operations::per_n_log_n(sum as f64 / cell.len() as f64, size)
+	}).sum();
+	total / test_vectors.len() as f64
+}
+
+// ciura's sequence extended by a factor of 2.25 up to (but not including) max_size, smallest first
+pub fn initial(max_size: usize) -> Vec<usize> {
+	let mut sequence: Vec<usize> = algos::CIURA_SEQUENCE.iter()
+												  .rev()
+												  .copied()
+												  .filter(|g| *g == 1 || *g < max_size)
+												  .collect();
+	loop {
+		let next = (*sequence.last().unwrap() as f64 * 2.25) as usize;
+		if next >= max_size {
+			break;
+		}
+		sequence.push(next);
+	}
+	sequence
+}
+
+// a random small change to an increasing sequence that starts with 1, the result is still one
+// gaps stay below max_size, gaps past the largest array are never used
+pub fn mutate(sequence: &[usize], max_size: usize, rng: &mut SmallRng) -> Vec<usize> {
+	let mut sequence = sequence.to_vec();
+	match rng.gen_range(0..3) {
+		// nudge a gap somewhere between its neighbours
+		0 if sequence.len() > 1 => {
+			let i = rng.gen_range(1..sequence.len());
+			let upper = if i + 1 < sequence.len() { sequence[i + 1] } else { max_size };
+			if upper - sequence[i - 1] > 1 {
+				sequence[i] = rng.gen_range(sequence[i - 1] + 1..upper);
+			}
+		}
+		// remove a gap other than 1
+		1 if sequence.len() > 1 => {
+			sequence.remove(rng.gen_range(1..sequence.len()));
+		}
+		// add a gap after the last one or between two others
+		_ => {
+			let i = rng.gen_range(0..sequence.len());
+			let upper = if i + 1 < sequence.len() {
+				sequence[i + 1]
+			} else {
+				std::cmp::min(sequence[i].saturating_mul(3), max_size)
+			};
+			if upper - sequence[i] > 1 {
+				sequence.insert(i + 1, rng.gen_range(sequence[i] + 1..upper));
+			}
+		}
+	}
+	sequence
+}
+
+// returns the best sequence found, smallest gap first, and its score
+pub fn search(config: &cli::Config, test_vectors: &[Vec<Vec<i32>>]) -> (Vec<usize>, f64) {
+	let mut rng = SmallRng::seed_from_u64(config.seed);
+	let mut best = initial(config.max_size);
+	let score_of = |sequence: &[usize]| {
+		let gaps: Vec<usize> = sequence.iter().rev().copied().collect();
+		score(config.score, test_vectors, |_| gaps.clone())
+	};
+	let mut best_score = score_of(&best);
+	let start = Instant::now();
+	let mut last_update = 0;
+	eprint!("starting...");
+	for iteration in 0..config.iterations {
+		if start.elapsed().as_millis() as u64 - last_update >= 1_000 {
+			last_update = start.elapsed().as_millis() as u64;
+			eprint!("\x1b[2K\r{}/{} best score {:.4} with {} gaps", utils::commafy(iteration),
+				utils::commafy(config.iterations), best_score, best.len());
+		}
+		let candidate = mutate(&best, config.max_size, &mut rng);
+		let candidate_score = score_of(&candidate);
+		// ties are taken so the search can move along plateaus
+		if candidate_score <= best_score {
+			best = candidate;
+			best_score = candidate_score;
+		}
+	}
+	eprintln!();
+	(best, best_score)
+}
+
+// the sequence as a const to paste into algos/shellsort.rs, formatted like CIURA_SEQUENCE
+pub fn rust_const(name: &str, sequence: &[usize]) -> String {
+	let gaps: Vec<String> = sequence.iter().rev().map(|g| g.to_string()).collect();
+	let lines: Vec<String> = gaps.chunks(4).map(|line| format!("\t{}", line.join(", "))).collect();
+	format!("pub const {}: [usize; {}] = [\n{}\n];", name, sequence.len(), lines.join(",\n"))
+}
+
+// the search result next to the built-in sequences (capped at n / 2 like the shellsorts)
+// everything is rescored here so runtimes are measured the same way for every sequence
+pub fn print(config: &cli::Config, test_vectors: &[Vec<Vec<i32>>], sequence: &[usize]) {
+	let gaps: Vec<usize> = sequence.iter().rev().copied().collect();
+	let mut table = Table::new();
+	table.add_row(row!["sequence", format!("{} / (n log2 n)", config.score.name())]);
+	table.add_row(row!["searched", format!("{:.4}", score(config.score, test_vectors, |_| gaps.clone()))]);
+	for (_, builtin) in gaps::builtin() {
+		let builtin_score = score(config.score, test_vectors, |size| builtin.gaps(size / 2));
+		table.add_row(row![builtin.name, format!("{:.4}", builtin_score)]);
+	}
+	table.printstd();
+	let sizes = config.test_sizes();
+	println!("└ Mean over {} distribution(s), sizes {} to {}, {} test vectors each", config.distributions.len(),
+		utils::commafy(sizes[0]), utils::commafy(*sizes.last().unwrap()), SCORE_RUNS);
+	println!();
+	println!("for algos/shellsort.rs:");
+	println!("{}", rust_const("SEARCHED_SEQUENCE", sequence));
+	println!();
+	println!("for gaps --gap-file:");
+	println!("searched: {}", sequence.iter().map(|g| g.to_string()).collect::<Vec<String>>().join(", "));
+}
@@ -0,0 +1,255 @@
+// shellsort gap sequence exploration (gaps subcommand)
+// most of the shellsorts cap their first gap at n / 2 ("TODO: test k upper-bound"), this runs their gap
+// sequences under a range of caps (h_max) along with any sequences read from a file and reports the
+// fastest configuration at each size
+// shell_function also runs the first gap past its cap, here the cap is a hard limit on the largest gap
+
+use crate::algos;
+use crate::cli;
+use crate::inputs;
+use crate::sweep;
+use crate::utils;
+use crate::{BenchmarkManager, BenchmarkResult};
+
+// the largest gap allowed for an array of length n
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HMax {
+	// n / k
+	Fraction(usize),
+	Absolute(usize)
+}
+
+impl HMax {
+	// "n", "n/4", or "1000"
+	pub fn parse(value: &str) -> Result<HMax, String> {
+		let invalid = || format!("invalid h_max \"{}\" (expected n, n/<k>, or a number)", value);
+		if value == "n" {
+			Ok(HMax::Fraction(1))
+		} else if let Option::Some(k) = value.strip_prefix("n/") {
+			match k.parse() {
+				Ok(k) if k > 0 => Ok(HMax::Fraction(k)),
+				_ => Err(invalid())
+			}
+		} else {
+			value.parse().map(HMax::Absolute).map_err(|_| invalid())
+		}
+	}
+	pub fn cap(&self, n: usize) -> usize {
+		match self {
+			HMax::Fraction(k) => n / k,
+			HMax::Absolute(h) => *h
+		}
+	}
+	pub fn name(&self) -> String {
+		match self {
+			HMax::Fraction(1) => String::from("n"),
+			HMax::Fraction(k) => format!("n/{}", k),
+			HMax::Absolute(h) => h.to_string()
+		}
+	}
+}
+
+// generated gaps stop here whatever the cap, the gap functions and gonnet_baeza_next overflow near usize::MAX
+// and a gap this big is already far past any array that fits in memory
+const MAX_GENERATED_GAP: usize = usize::MAX / 100;
+
+pub enum Generator {
+	// gap k, increasing in k
+	Function(fn(u32) -> usize),
+	// the gap after h, starting from the cap
+	Recursive(fn(usize) -> usize),
+	Fixed(Vec<usize>)
+}
+
+pub struct Sequence {
+	pub name: String,
+	pub generator: Generator
+}
+
+impl Sequence {
+	// the gaps to use under a cap, largest first and always ending in 1
+	pub fn gaps(&self, cap: usize) -> Vec<usize> {
+		let mut gaps = Vec::new();
+		match &self.generator {
+			Generator::Function(f) => {
+				let cap = std::cmp::min(cap, MAX_GENERATED_GAP);
+				let mut k = 0;
+				while f(k) <= cap {
+					gaps.push(f(k));
+					k += 1;
+				}
+			}
+			Generator::Recursive(f) => {
+				let mut h = std::cmp::min(cap, MAX_GENERATED_GAP);
+				while h > 1 {
+					gaps.push(h);
+					let next = f(h);
+					if next == h {
+						break;
+					}
+					h = next;
+				}
+			}
+			Generator::Fixed(sequence) => gaps.extend(sequence.iter().filter(|g| **g <= cap))
+		}
+		// some functions start at 0
+		gaps.retain(|g| *g > 0);
+		gaps.push(1);
+		gaps.sort_unstable_by(|a, b| b.cmp(a));
+		gaps.dedup();
+		gaps
+	}
+}
+
+// the shellsorts whose sequences can be explored, by algorithm name
+pub fn builtin() -> Vec<(&'static str, Sequence)> {
+	let sequence = |name: &str, generator| Sequence { name: String::from(name), generator };
+	vec![
+		("algos::shellsort_knuth",        sequence("knuth", Generator::Function(algos::knuth_gap))),
+		("algos::shellsort_sedgewick82",  sequence("sedgewick82", Generator::Function(algos::sedgewick82_gap))),
+		("algos::shellsort_sedgewick86",  sequence("sedgewick86", Generator::Function(algos::sedgewick86_gap))),
+		("algos::shellsort_gonnet_baeza", sequence("gonnet_baeza", Generator::Recursive(algos::gonnet_baeza_next))),
+		("algos::shellsort_tokuda",       sequence("tokuda", Generator::Function(algos::tokuda_gap))),
+		("algos::shellsort_ciura",        sequence("ciura", Generator::Fixed(algos::CIURA_SEQUENCE.to_vec())))
+	]
+}
+
+// built-in sequences for the selected shellsorts
+pub fn selection(config: &cli::Config) -> Vec<Sequence> {
+	builtin().into_iter()
+			 .filter(|(algorithm, _)| BenchmarkManager::is_selected(config, algorithm))
+			 .map(|(_, sequence)| sequence)
+			 .collect()
+}
+
+// one sequence per line, gaps separated by commas and/or whitespace in any order, optionally
+// preceded by "name:". blank lines and lines starting with # are ignored
+// e.g. "ciura_extended: 1, 4, 10, 23, 57, 132, 301, 701, 1750"
+pub fn parse_sequences(text: &str) -> Result<Vec<Sequence>, String> {
+	let mut sequences = Vec::new();
+	for (line_i, line) in text.lines().enumerate() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with('#') {
+			continue;
+		}
+		let (name, gaps) = match line.split_once(':') {
+			Option::Some((name, gaps)) => (String::from(name.trim()), gaps),
+			Option::None => (format!("line {}", line_i + 1), line)
+		};
+		let mut sequence = Vec::new();
+		for gap in gaps.split(|c: char| c == ',' || c.is_whitespace()).filter(|g| !g.is_empty()) {
+			match gap.parse::<usize>() {
+				Ok(gap) if gap > 0 => sequence.push(gap),
+				_ => return Err(format!("line {}: invalid gap \"{}\"", line_i + 1, gap))
+			}
+		}
+		if !sequence.contains(&1) {
+			return Err(format!("line {}: gap sequence doesn't contain 1", line_i + 1));
+		}
+		sequences.push(Sequence { name, generator: Generator::Fixed(sequence) });
+	}
+	if sequences.is_empty() {
+		return Err(String::from("no gap sequences"));
+	}
+	Ok(sequences)
+}
+
+pub fn load_sequences(path: &str) -> Result<Vec<Sequence>, String> {
+	let text = std::fs::read_to_string(path).map_err(|e| format!("can't read {}: {}", path, e))?;
+	parse_sequences(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
+// timings for one (distribution, size) across every configuration
+pub struct Exploration {
+	// index into inputs::DISTRIBUTIONS
+	pub distribution: usize,
+	pub size: usize,
+	// raw timings in ns, indexed [sequence * h_max.len() + h_max]
+	pub samples: Vec<Vec<u64>>
+}
+
+impl Exploration {
+	pub fn results(&self, outlier_coefficient: f64) -> Vec<Option<BenchmarkResult>> {
+		self.samples.iter().map(|s| BenchmarkResult::from_samples(s, outlier_coefficient)).collect()
+	}
+	// index of the configuration with the lowest mean, none if nothing has enough runs
+	pub fn fastest(&self, outlier_coefficient: f64) -> Option<usize> {
+		let results = self.results(outlier_coefficient);
+		(0..results.len()).filter(|i| results[*i].is_some())
+						  .min_by(|a, b| results[*a].unwrap().mean.partial_cmp(&results[*b].unwrap().mean).unwrap())
+	}
+}
+
+// "sedgewick82 h_max=n/2" for every (sequence, h_max) pair, in the same order as Exploration::samples
+pub fn labels(config: &cli::Config, sequences: &[Sequence]) -> Vec<String> {
+	sequences.iter()
+			 .flat_map(|s| config.h_max.iter().map(move |h| format!("{} h_max={}", s.name, h.name())))
+			 .collect()
+}
+
+// benchmarks every sequence under every cap at every size and distribution
+pub fn run(config: &cli::Config, sequences: &[Sequence]) -> Vec<Exploration> {
+	let mut explorations = Vec::new();
+	for distribution in &config.distributions {
+		for size in config.test_sizes() {
+			explorations.push(Exploration {
+				distribution: inputs::find(distribution).expect("unknown distribution"),
+				size,
+				samples: Vec::new()
+			});
+		}
+	}
+	let labels = labels(config, sequences);
+	let n_caps = config.h_max.len();
+	let samples = sweep::run_jobs(config, explorations.len(), labels.len(), |exploration_i, variant| {
+		let exploration = &explorations[exploration_i];
+		format!("{} {} {}", labels[variant], inputs::DISTRIBUTIONS[exploration.distribution].0,
+			utils::commafy(exploration.size))
+	}, |exploration_i, variant, test_i| {
+		let exploration = &explorations[exploration_i];
+		let cap = config.h_max[variant % n_caps].cap(exploration.size);
+		let gaps = sequences[variant / n_caps].gaps(cap);
+		sweep::run_bench(config, |array| algos::shell_sequence(array, &gaps), exploration.distribution,
+						 exploration.size, test_i)
+	});
+	for (exploration, samples) in explorations.iter_mut().zip(samples) {
+		exploration.samples = samples;
+	}
+	explorations
+}
+
+// one table per distribution with a row per configuration, followed by the fastest configuration at
+// each size
+pub fn print(config: &cli::Config, sequences: &[Sequence], explorations: &[Exploration]) {
+	let labels = labels(config, sequences);
+	let mut distributions: Vec<usize> = Vec::new();
+	for exploration in explorations {
+		if !distributions.contains(&exploration.distribution) {
+			distributions.push(exploration.distribution);
+		}
+	}
+	for distribution in distributions {
+		let columns: Vec<&Exploration> = explorations.iter().filter(|e| e.distribution == distribution).collect();
+		let mut results: Vec<Vec<Option<BenchmarkResult>>> =
+			columns.iter().map(|e| e.results(config.outlier_coefficient)).collect();
+		if config.distributions.len() > 1 || inputs::DISTRIBUTIONS[distribution].0 != "uniform" {
+			println!("Shellsort gap sequences ({} input):", inputs::DISTRIBUTIONS[distribution].0);
+		} else {
+			println!("Shellsort gap sequences:");
+		}
+		let sizes: Vec<usize> = columns.iter().map(|e| e.size).collect();
+		sweep::table(config, "sequence", &labels, &sizes, &mut results).printstd();
+		println!("└ Values in ms; 98% confidence interval displayed; s = statistically equal to fastest; \
+					* = within {}% of fastest", config.diff_threshold * 100.0);
+		for (exploration, column) in columns.iter().zip(&results) {
+			if let Option::Some(fastest) = exploration.fastest(config.outlier_coefficient) {
+				let tied = column.iter().enumerate()
+								 .filter(|(i, r)| *i != fastest && r.is_some_and(|r| r.is_stat_fastest))
+								 .count();
+				println!("fastest at size {}: {} ({} others statistically equal)", utils::commafy(exploration.size),
+					labels[fastest], tied);
+			}
+		}
+		println!();
+	}
+}
@@ -0,0 +1,220 @@
+// input distributions, the third axis of the job matrix next to algorithm and size
+// every generator fills a test vector from an rng seeded for that particular run, so inputs are
+// reproducible from the run's seed
+// we used to only benchmark uniformly random arrays, which is rarely what a real program sorts
+
+use std::collections::BTreeMap;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore};
+
+use crate::corpus;
+use crate::datasets::Sampling;
+
+pub type Generator = fn(&mut [i32], &mut SmallRng);
+
+// (name, generator, degenerate)
+// degenerate inputs are presorted or full of duplicates, plain quicksorts (end / random pivot with
+// no introsort fallback) go quadratic on them and recurse about n deep
+pub static DISTRIBUTIONS: &[(&str, Generator, bool)] = &[
+	("uniform",       uniform,       false),
+	("sorted",        sorted,        true),
+	("reversed",      reversed,      true),
+	("nearly-sorted", nearly_sorted, true),
+	("few-unique",    few_unique,    true),
+	("organ-pipe",    organ_pipe,    true),
+	("sawtooth",      sawtooth,      true),
+	("random-runs",   random_runs,   false),
+	("all-equal",     all_equal,     true),
+	("zipf",          zipf,          true)
+];
+
+// number of distinct values in few-unique
+const FEW_UNIQUE: usize = 8;
+
+pub fn find(name: &str) -> Option<usize> {
+	DISTRIBUTIONS.iter().position(|d| d.0 == name)
+}
+
+pub fn names() -> Vec<&'static str> {
+	DISTRIBUTIONS.iter().map(|d| d.0).collect()
+}
+
+// where an input's keys come from
+pub enum Source {
+	// index into DISTRIBUTIONS
+	Generated(usize),
+	// arrays replayed from a corpus (--inputs-from), by size in test index order
+	// empty when only the name is known (analyzing a samples file from a replayed run)
+	Recorded { name: String, arrays: BTreeMap<usize, Vec<Vec<i32>>> },
+	// test vectors drawn from a user's file (--dataset)
+	Dataset { name: String, values: Vec<i32>, sampling: Sampling }
+}
+
+impl Source {
+	// groups corpus arrays into one source per name, in order of first appearance
+	pub fn from_corpus(arrays: Vec<corpus::Array>) -> Vec<Source> {
+		let mut sources: Vec<Source> = Vec::new();
+		for array in &arrays {
+			if !sources.iter().any(|s| s.name() == array.name) {
+				sources.push(Source::Recorded { name: array.name.clone(), arrays: BTreeMap::new() });
+			}
+		}
+		let mut arrays = arrays;
+		arrays.sort_by_key(|a| a.test_i);
+		for array in arrays {
+			let i = sources.iter().position(|s| s.name() == array.name).unwrap();
+			if let Source::Recorded { arrays, .. } = &mut sources[i] {
+				arrays.entry(array.keys.len()).or_default().push(array.keys);
+			}
+		}
+		sources
+	}
+	pub fn name(&self) -> &str {
+		match self {
+			Source::Generated(d) => DISTRIBUTIONS[*d].0,
+			Source::Recorded { name, .. } | Source::Dataset { name, .. } => name
+		}
+	}
+	// recorded arrays are taken to be as degenerate as the distribution with the same name, if any
+	// nothing is known about the shape of a dataset, it could well be sorted
+	pub fn degenerate(&self) -> bool {
+		match self {
+			Source::Dataset { .. } => true,
+			_ => find(self.name()).is_some_and(|d| DISTRIBUTIONS[d].2)
+		}
+	}
+	// whether there are keys for a size at all
+	pub fn has_size(&self, size: usize) -> bool {
+		match self {
+			Source::Generated(_) => true,
+			Source::Recorded { arrays, .. } => arrays.contains_key(&size),
+			Source::Dataset { values, sampling, .. } => *sampling == Sampling::Resample || size <= values.len()
+		}
+	}
+	// (size, number of arrays) for every size a recorded source has, nothing for the others
+	pub fn recorded_sizes(&self) -> Vec<(usize, usize)> {
+		match self {
+			Source::Generated(_) | Source::Dataset { .. } => Vec::new(),
+			Source::Recorded { arrays, .. } => arrays.iter().map(|(size, a)| (*size, a.len())).collect()
+		}
+	}
+	// the keys for one run, rng is seeded for that run
+	pub fn keys(&self, size: usize, test_i: usize, rng: &mut SmallRng) -> Vec<i32> {
+		match self {
+			Source::Generated(d) => {
+				let mut keys = vec![0; size];
+				(DISTRIBUTIONS[*d].1)(&mut keys, rng);
+				keys
+			}
+			Source::Recorded { arrays, .. } => arrays[&size][test_i].clone(),
+			Source::Dataset { values, sampling: Sampling::Window, .. } => {
+				let start = rng.gen_range(0..=values.len() - size);
+				values[start..(start + size)].to_vec()
+			}
+			Source::Dataset { values, sampling: Sampling::Resample, .. } => {
+				(0..size).map(|_| values[rng.gen_range(0..values.len())]).collect()
+			}
+		}
+	}
+}
+
+fn sqrt_len(array: &[i32]) -> usize {
+	std::cmp::max((array.len() as f64).sqrt() as usize, 1)
+}
+
+// maps 0..=max onto the whole i32 range preserving order
+// keys are narrowed to the top bits for small element types, small values would all end up equal
+fn spread(value: usize, max: usize) -> i32 {
+	(i32::MIN as i64 + value as i64 * u32::MAX as i64 / std::cmp::max(max, 1) as i64) as i32
+}
+
+pub fn uniform(array: &mut [i32], rng: &mut SmallRng) {
+	for item in array.iter_mut() {
+		*item = rng.next_u32() as i32;
+	}
+}
+
+pub fn sorted(array: &mut [i32], rng: &mut SmallRng) {
+	uniform(array, rng);
+	array.sort_unstable();
+}
+
+pub fn reversed(array: &mut [i32], rng: &mut SmallRng) {
+	sorted(array, rng);
+	array.reverse();
+}
+
+// sorted, then n / 100 (at least 1) random pairs swapped
+pub fn nearly_sorted(array: &mut [i32], rng: &mut SmallRng) {
+	sorted(array, rng);
+	if array.len() < 2 {
+		return;
+	}
+	for _ in 0..std::cmp::max(array.len() / 100, 1) {
+		let i = rng.gen_range(0..array.len());
+		let j = rng.gen_range(0..array.len());
+		array.swap(i, j);
+	}
+}
+
+pub fn few_unique(array: &mut [i32], rng: &mut SmallRng) {
+	let mut values = [0; FEW_UNIQUE];
+	uniform(&mut values, rng);
+	for item in array.iter_mut() {
+		*item = values[rng.gen_range(0..FEW_UNIQUE)];
+	}
+}
+
+// ascending then descending: 0 1 2 ... 2 1 0 (spread out)
+pub fn organ_pipe(array: &mut [i32], _: &mut SmallRng) {
+	let n = array.len();
+	for (i, item) in array.iter_mut().enumerate() {
+		*item = spread(std::cmp::min(i, n - 1 - i), (n - 1) / 2);
+	}
+}
+
+// ascending runs of sqrt(n) values: 0 1 2 0 1 2 0 1 2 (spread out)
+pub fn sawtooth(array: &mut [i32], _: &mut SmallRng) {
+	let period = sqrt_len(array);
+	for (i, item) in array.iter_mut().enumerate() {
+		*item = spread(i % period, period - 1);
+	}
+}
+
+// random values in sorted runs of random length, sqrt(n) long on average
+pub fn random_runs(array: &mut [i32], rng: &mut SmallRng) {
+	uniform(array, rng);
+	let max_run = 2 * sqrt_len(array);
+	let mut i = 0;
+	while i < array.len() {
+		let end = std::cmp::min(i + rng.gen_range(1..=max_run), array.len());
+		array[i..end].sort_unstable();
+		i = end;
+	}
+}
+
+pub fn all_equal(array: &mut [i32], rng: &mut SmallRng) {
+	let value = rng.next_u32() as i32;
+	for item in array.iter_mut() {
+		*item = value;
+	}
+}
+
+// ranks 1..=n drawn with probability proportional to 1 / rank (zipf's law with s = 1), so a few
+// values make up most of the array. ranks are spread out, rank 1 is the smallest value
+pub fn zipf(array: &mut [i32], rng: &mut SmallRng) {
+	// cumulative weights for inverse transform sampling
+	let mut cdf = Vec::with_capacity(array.len());
+	let mut total = 0.0;
+	for rank in 1..=array.len() {
+		total += 1.0 / rank as f64;
+		cdf.push(total);
+	}
+	for item in array.iter_mut() {
+		let u = rng.gen::<f64>() * total;
+		// first rank whose cumulative weight exceeds u
+		let rank = cdf.partition_point(|w| *w <= u);
+		*item = spread(std::cmp::min(rank, cdf.len() - 1), cdf.len() - 1);
+	}
+}
@@ -1,79 +1,73 @@
 #![cfg(not(tarpaulin_include))] // this file should be excluded from test coverage
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
-use lazy_static::lazy_static;
-use num_cpus;
 use prettytable::*;
 use rand::rngs::SmallRng;
-use rand::{RngCore, SeedableRng, seq::SliceRandom};
-use regex::Regex;
+use rand::{SeedableRng, seq::SliceRandom};
+
+use elements::ElementType;
+use registry::{Algorithm, Family};
 
 mod algos;
+mod cli;
+mod compare;
+mod comparators;
+mod complexity;
+mod corpus;
+mod datasets;
+mod elements;
+mod export;
+mod gap_search;
+mod gaps;
+mod inputs;
 mod odd_algos;
+mod operations;
+mod registry;
+mod samples;
 mod statistics;
+#[cfg(unix)]
+mod subprocess;
+mod sweep;
 mod utils;
 mod unchecked_tools;
 mod tests;
 
-// this flag will disable a lot of algorithms and reduce the number of tests performed
-// this is for use during development
-const TEST_MODE: bool = false;
-
-const MIN_TEST_SIZE: usize = 10;
-#[cfg(not(arm11))]
-const MAX_TEST_SIZE: usize = if !TEST_MODE { 1_000_000 } else { 10_000 };
-#[cfg(arm11)]
-const MAX_TEST_SIZE: usize = if !TEST_MODE { 100_000   } else { 10_000 };
-const N_TESTS: usize =       if !TEST_MODE { 200       } else { 50     };
-const ALPHA: f64 = 0.001;
-const DIFF_THRESHOLD: f64 = 0.05;
-
-const RNG_SEED: u64 = 2222;
-const RUNTIME_LIMIT: u64 = 10e9 as u64;
 const MIN_ACCEPTABLE_TESTS: usize = 30;
-const OUTLIER_COEFFICIENT: f64 = 3.0;
-
-lazy_static! {
-	// don't want to run bubblesort on a million items (or 100,000 items for that matter)
-	static ref LIMIT_TABLE: HashMap<&'static str, usize> = {
-		let mut table = HashMap::new();
-		table.insert("O(n^2)", 10_000);
-		table.insert("O(n^(4/3))", usize::MAX);
-		table.insert("O(n^(3/2))", usize::MAX);
-		table.insert("O(n log n)", usize::MAX);
-		table.insert("O(n)", usize::MAX); // radixsort
-		table
-	};
-}
+// number of test vectors comparisons are averaged over for --operations
+const OPERATION_RUNS: usize = 10;
+// mixed into the rng seed for the seeds randomized algorithms get
+const ALGORITHM_SEED_SALT: u64 = 0x5EED_A160;
 
-lazy_static! {
-	static ref TEST_SIZES: Vec<usize> = {
-		let mut v = Vec::new();
-		let mut test_size = MIN_TEST_SIZE;
-		while test_size <= MAX_TEST_SIZE {
-			v.push(test_size);
-			test_size *= 10;
-		}
-		v
-	};
-}
+// whether an algorithm belongs in a table
+type AlgorithmFilter = fn(&Algorithm) -> bool;
 
-lazy_static! {
-	static ref N_WORKERS: usize = {
-		//num_cpus::get_physical() - 1
-		num_cpus::get_physical() / 2
-	};
-}
+// how the output is split up into tables, some tables show related families side by side
+// selecting algorithms with --group works off registry::Family instead, one group per family
+// (table title, filter)
+static TABLES: &[(&str, AlgorithmFilter)] = &[
+	("Bubble sorts", |a| a.family == Family::Bubble),
+	("Insertion sorts", |a| a.family == Family::Insertion),
+	("Insertion and selection sorts", |a| a.family == Family::Insertion || a.family == Family::Selection),
+	("Shell sorts", |a| a.family == Family::Shell || a.family == Family::Insertion),
+	("Merge sorts", |a| a.family == Family::Merge),
+	("Heap sorts", |a| a.family == Family::Heap),
+	("Quick sorts", |a| a.family == Family::Quick),
+	// the library sorts are the ones to beat
+	("Radix sort", |a| a.family == Family::Radix || a.family == Family::Library)
+];
 
 #[derive(Clone, Copy, Debug, Default)]
 struct BenchmarkResult {
 	mean: f64,
 	stdev: f64,
 	count: usize,
+	// number of runs discarded by tukey's method
+	outliers: usize,
 	is_stat_fastest: bool,
 	is_fastest: bool
 }
@@ -93,6 +87,39 @@ impl std::fmt::Display for BenchmarkResult {
 }
 
 impl BenchmarkResult {
+	// summarizes the raw timings for one cell
+	// returns none if there aren't enough runs to say anything meaningful
+	fn from_samples(results: &[u64], outlier_coefficient: f64) -> Option<BenchmarkResult> {
+		if results.len() <= MIN_ACCEPTABLE_TESTS {
+			// either not enough tests were performed within the runtime limit or no tests
+			// were performed because of complexity limits
+			return Option::None;
+		}
+		// compute stats
+		// We had an issue with a few benchmarks randomly having massive standard deviations
+		// every time we'd run the benchmark just a couple results would have anomalies and
+		// there wasn't any consistency or pattern to which benchmarks would have anomalies.
+		// The reason for the massive standard deviations was due to just a couple (usually
+		// just 1) extraneous results in the results vector (e.g. in a benchmark whose
+		// result's mean was ~2,200ns, there was an outlier of 112,600ns blowing up the
+		// standard deviation calculation). Here we use Tukey's method to discard outliers.
+		let q = statistics::quartiles(&results.to_vec());
+		let filtered: Vec<u64> = results.iter()
+										.copied()
+										.filter(|item| statistics::tukey(*item, &q, outlier_coefficient))
+										.collect();
+		let mean = filtered.iter().sum::<u64>() as f64 / filtered.len() as f64;
+		let stdev = statistics::stdev(&filtered, mean);
+		Option::Some(BenchmarkResult {
+			mean,
+			stdev,
+			count: filtered.len(),
+			outliers: results.len() - filtered.len(),
+			// fields will be used in display code
+			is_fastest: false,
+			is_stat_fastest: false
+		})
+	}
 	// returns a p-value and a percent difference (based off of the smaller mean)
 	fn compare(&self, other: &BenchmarkResult) -> (f64, f64) {
 		// percent diff
@@ -113,12 +140,12 @@ impl BenchmarkResult {
 		assert!(p <= 1.0, "problematic value: {}", p);
 		(p, diff)
 	}
-	fn update_display(&mut self, other: &BenchmarkResult) {
+	fn update_display(&mut self, other: &BenchmarkResult, alpha: f64, diff_threshold: f64) {
 		let (p, diff) = self.compare(other);
-		if p >= ALPHA {
+		if p >= alpha {
 			self.is_stat_fastest = true;
 		}
-		if diff <= DIFF_THRESHOLD {
+		if diff <= diff_threshold {
 			self.is_fastest = true;
 		}
 	}
@@ -143,9 +170,35 @@ enum MType {
 	WorkAssignment
 }
 
+// (algorithm, input, size, test) indices
+type Job = (usize, usize, usize, usize);
+
+// a job that panicked, either in the algorithm or in the sortedness check after it, or that was killed
+// for going past --job-timeout
+struct Failure {
+	job: Job,
+	message: String,
+	timed_out: bool
+}
+
+impl Failure {
+	// what's saved about it in the exported results and samples files
+	fn cell_failure(&self) -> export::CellFailure {
+		export::CellFailure { test_i: self.job.3, message: self.message.clone(), timed_out: self.timed_out }
+	}
+	fn from_cell_failure(cell: (usize, usize, usize), failure: &export::CellFailure) -> Failure {
+		Failure {
+			job: (cell.0, cell.1, cell.2, failure.test_i),
+			message: failure.message.clone(),
+			timed_out: failure.timed_out
+		}
+	}
+}
+
 #[derive(Clone, Copy)]
 struct WorkDescriptor {
 	algorithm_i: usize,
+	input: usize,
 	size: usize,
 	test_i: usize
 }
@@ -184,140 +237,285 @@ impl MPMessage {
 }
 
 struct BenchmarkManager {
-	algorithms: Vec<(Option<fn(&mut [i32])>, String, &'static str)>,
-	results_table: Vec<Vec<Option<BenchmarkResult>>>
+	config: cli::Config,
+	test_sizes: Vec<usize>,
+	algorithms: Vec<Algorithm>,
+	// whether each algorithm was picked by --algo / --group / --exclude
+	selected: Vec<bool>,
+	// where the keys for each distribution come from
+	sources: Vec<inputs::Source>,
+	// every combination of the selected distributions and element types
+	// (index into sources, element type)
+	inputs: Vec<(usize, ElementType)>,
+	// raw timings in ns, indexed [algorithm][input][size]
+	samples: Vec<Vec<Vec<Vec<u64>>>>,
+	results_table: Vec<Vec<Vec<Option<BenchmarkResult>>>>,
+	// jobs that panicked, their cells are reported as failed and not run any further
+	failures: Vec<Failure>,
+	// remaining job stack when resuming from a checkpoint
+	resumed_jobs: Option<Vec<Job>>,
+	// time spent before the checkpoint this run was resumed from
+	previous_runtime: Duration
 }
 
 impl BenchmarkManager {
-	pub fn new() -> BenchmarkManager {
-		let algorithms: Vec<(Option<fn(&mut [i32])>, String, &str)> =
-		if !TEST_MODE {
-			 vec![
-				sfn!(algos::bubblesort::<i32>,               "O(n^2)"),
-				sfn!(algos::cocktail_shaker::<i32>,          "O(n^2)"),
-				sfn!(algos::selectionsort::<i32>,            "O(n^2)"),
-				sfn!(algos::insertionsort::<i32>,            "O(n^2)"),
-				sfn!(algos::insertionsort_c,                 "O(n^2)"),
-				sfn!(algos::shellsort_knuth::<i32>,          "O(n^(4/3))"),
-				sfn!(algos::shellsort_sedgewick82::<i32>,    "O(n^(4/3))"),
-				sfn!(algos::shellsort_sedgewick86::<i32>,    "O(n^(4/3))"),
-				sfn!(algos::shellsort_gonnet_baeza::<i32>,   "O(n^(4/3))"),
-				sfn!(algos::shellsort_tokuda::<i32>,         "O(n^(4/3))"),
-				sfn!(algos::shellsort_ciura::<i32>,          "O(n^(4/3))"),
-				sfn!(algos::mergesort::<i32>,                "O(n log n)"),
-				sfn!(algos::mergesort_hybrid::<i32>,         "O(n log n)"),
-				sfn!(algos::mergesort_in_place_naive::<i32>, "O(n^2)"),
-				sfn!(algos::mergesort_in_place::<i32>,       "O(n log n)"),
-				sfn!(algos::heapsort_top_down::<i32>,        "O(n log n)"),
-				sfn!(algos::heapsort_bottom_up::<i32>,       "O(n log n)"),
-				sfn!(algos::heapsort_bottom_up_optimized::<i32>, "O(n log n)"),
-				sfn!(algos::quicksort_end::<i32>,            "O(n log n)"),
-				sfn!(algos::quicksort_random::<i32>,         "O(n log n)"),
-				sfn!(algos::quicksort_hybrid::<i32>,         "O(n log n)"),
-				sfn!(algos::introsort::<i32>,                "O(n log n)"),
-				sfn!(algos::timsort::<i32>,                  "O(n log n)"),
-				sfn!(algos::radixsort,                       "O(n)"),
-				sfn!(algos::rustsort::<i32>,                 "O(n log n)"),
-				sfn!(algos::rustsort_unsable::<i32>,         "O(n log n)"),
-				sfn!(algos::cpp_std_sort,                    "O(n log n)"),
-				// odd algos and unimportant variations will be tacked on at the end
-				(Option::None, String::from(""), ""), // table separator
-				sfn!(algos::insertionsort_boundary_checked::<i32>,"O(n^2)"),
-				sfn!(odd_algos::selectionsort_cocktail::<i32>,"O(n^2)"),
-				sfn!(odd_algos::selectionsort_minmax::<i32>, "O(n^2)"),
-				sfn!(odd_algos::selectionsort_manymins::<i32>,"O(n^2)"),
-				sfn!(odd_algos::shellsort_alternative_ciura::<i32>,"O(n^(4/3))"),
-				sfn!(odd_algos::mergesort_adaptive::<i32>,   "O(n log n)"),
-				sfn!(odd_algos::mergesort_double_hybrid::<i32>,"O(n log n)"),
-				sfn!(odd_algos::btreesort::<i32>,            "O(n log n)"),
-				sfn!(odd_algos::weird::<i32>,                "O(n^(3/2))")
-			]
-		} else {
-			vec![
-				sfn!(algos::bubblesort::<i32>,               "O(n^2)"),
-				sfn!(algos::cocktail_shaker::<i32>,          "O(n^2)"),
-				sfn!(algos::selectionsort::<i32>,            "O(n^2)"),
-				sfn!(algos::insertionsort::<i32>,            "O(n^2)"),
-				sfn!(algos::insertionsort_boundary_checked::<i32>,"O(n^2)"),
-				sfn!(algos::insertionsort_c,                 "O(n^2)"),
-				sfn!(algos::shellsort_knuth::<i32>,          "O(n^(4/3))"),
-				sfn!(algos::shellsort_sedgewick82::<i32>,    "O(n^(4/3))"),
-				sfn!(algos::mergesort_hybrid::<i32>,         "O(n log n)"),
-				sfn!(algos::mergesort_in_place::<i32>,       "O(n log n)"),
-				sfn!(algos::heapsort_top_down::<i32>,        "O(n log n)"),
-				sfn!(algos::quicksort_end::<i32>,            "O(n log n)"),
-				sfn!(algos::quicksort_hybrid::<i32>,         "O(n log n)"),
-				sfn!(algos::radixsort,                       "O(n)"),
-				sfn!(algos::rustsort::<i32>,                 "O(n log n)"),
-				sfn!(algos::cpp_std_sort,                    "O(n log n)")
-			]
-		};
+	pub fn new(config: cli::Config) -> BenchmarkManager {
+		let sources = config.distributions
+							.iter()
+							.map(|d| inputs::Source::Generated(inputs::find(d).expect("unknown distribution")))
+							.collect();
+		let test_sizes = config.test_sizes();
+		BenchmarkManager::with_sources(config, sources, test_sizes)
+	}
+	// the distributions followed by the --dataset files
+	pub fn with_datasets(mut config: cli::Config) -> Result<BenchmarkManager, String> {
+		let mut sources: Vec<inputs::Source> =
+			config.distributions
+				  .iter()
+				  .map(|d| inputs::Source::Generated(inputs::find(d).expect("unknown distribution")))
+				  .collect();
+		for spec in &config.datasets {
+			let source = datasets::load(spec, config.sampling)?;
+			if sources.iter().any(|s| s.name() == source.name()) {
+				return Err(format!("{}: there's already an input called {}", spec, source.name()));
+			}
+			sources.push(source);
+		}
+		config.distributions = sources.iter().map(|s| String::from(s.name())).collect();
+		let test_sizes = config.test_sizes();
+		Ok(BenchmarkManager::with_sources(config, sources, test_sizes))
+	}
+	fn with_sources(config: cli::Config, sources: Vec<inputs::Source>, test_sizes: Vec<usize>) -> BenchmarkManager {
+		let algorithms = registry::ALGORITHMS.clone();
 		// TODO: single vec serving as 2D array? algorithms[i][j] = results[i * len + j]
-		let results_table = vec![vec![Option::None; TEST_SIZES.len()]; algorithms.len()];
+		let selected = algorithms.iter().map(|a| BenchmarkManager::is_selected(&config, a.name)).collect();
+		let mut inputs = Vec::new();
+		for source_i in 0..sources.len() {
+			for element in &config.elements {
+				inputs.push((source_i, ElementType::from_name(element).expect("unknown element type")));
+			}
+		}
+		let samples = vec![vec![vec![Vec::new(); test_sizes.len()]; inputs.len()]; algorithms.len()];
+		let results_table = vec![vec![vec![Option::None; test_sizes.len()]; inputs.len()]; algorithms.len()];
 		BenchmarkManager {
+			config,
+			test_sizes,
 			algorithms,
-			results_table
+			selected,
+			sources,
+			inputs,
+			samples,
+			results_table,
+			failures: Vec::new(),
+			resumed_jobs: Option::None,
+			previous_runtime: Duration::from_secs(0)
+		}
+	}
+	// groups only match registered algorithms, anything else (e.g. from an old samples file) has to be
+	// picked by name
+	fn is_selected(config: &cli::Config, name: &str) -> bool {
+		// patterns can be written against the full path (algos::heapsort_top_down) or just the
+		// function name (heapsort_top_down)
+		let short_name = name.rsplit("::").next().unwrap();
+		let matches = |pattern: &String| utils::glob_match(pattern, name)
+										|| utils::glob_match(pattern, short_name);
+		let algorithm = registry::find(name);
+		let included = (config.algorithms.is_empty() && config.groups.is_empty())
+			|| config.algorithms.iter().any(matches)
+			|| algorithm.is_some_and(|a| config.groups.iter().any(|g| g == a.family.name()));
+		included && !config.exclude.iter().any(matches)
+	}
+	// checks that the selection options make sense, returns an error message if they don't
+	pub fn validate_selection(&self) -> Result<(), String> {
+		BenchmarkManager::validate_groups(&self.config)?;
+		if !self.selected.iter().any(|s| *s) {
+			return Err(String::from("no algorithms matched the selection"));
+		}
+		Ok(())
+	}
+	fn validate_groups(config: &cli::Config) -> Result<(), String> {
+		for group in &config.groups {
+			if Family::from_name(group).is_none() {
+				return Err(format!("unknown group \"{}\" (available: {})", group,
+					Family::ALL.iter().map(|f| f.name()).collect::<Vec<&str>>().join(", ")));
+			}
 		}
+		Ok(())
 	}
-	fn seedgen(n: usize) -> u64 {
+	pub fn list(&self) {
+		println!("algorithms:");
+		for (i, a) in self.algorithms.iter().enumerate() {
+			let mut properties = vec![a.complexity.label(), a.memory.label()];
+			if a.stable {
+				properties.push("stable");
+			}
+			if a.adaptive {
+				properties.push("adaptive");
+			}
+			if !a.sorts.supports(ElementType::U8) {
+				properties.push("i32 only");
+			}
+			println!("  {} {:<50} {}", if self.selected[i] {"*"} else {" "}, a.name, properties.join(", "));
+		}
+		println!("groups:");
+		for family in Family::ALL {
+			let members: Vec<&str> = self.algorithms.iter()
+													.filter(|a| a.family == *family)
+													.map(|a| a.name.rsplit("::").next().unwrap())
+													.collect();
+			println!("    {:<16} {}", family.name(), members.join(", "));
+		}
+		println!("* = selected");
+	}
+	fn seedgen(rng_seed: u64, n: usize) -> u64 {
 		let n = n as u64;
-		// in the old benchmark code we'd use an rng source with the fixed seed to generate N_TESTS
+		// in the old benchmark code we'd use an rng source with the fixed seed to generate n_tests
 		// test vectors
-		// this would give us N_TESTS unique (probably) test vectors, but the values were
+		// this would give us n_tests unique (probably) test vectors, but the values were
 		// predictable and constant across all tests
 		// in order to get unique but consistent test vectors with this situation, we use a basic
 		// method to create a seed based off of the current test we're on
-		// could just do RNG_SEED + n but why do that when we can do it complicated
-		// note: previously looked into creating a rng with RNG_SEED for every cell in the table
+		// could just do rng_seed + n but why do that when we can do it complicated
+		// note: previously looked into creating a rng with rng_seed for every cell in the table
 		// and passing those to threads with the various jobs, but, that would be complicated as far
 		// as lifetimes go and would introduce concurrency problems
 		// this way we don't have to avoid scheduling two sub-runs of (alg, size) at once
-		let mut seed = RNG_SEED;
+		let mut seed = rng_seed;
 		for i in 0..n {
-			seed = (seed.rotate_left(1) + n) ^ i ^ RNG_SEED;
+			seed = (seed.rotate_left(1) + n) ^ i ^ rng_seed;
 		}
 		seed
 	}
-	fn run_bench(sort: fn(&mut [i32]), size: usize, test_i: usize) -> u64 {
+	// seed for randomized algorithms (algos::context) on a particular run
+	// kept apart from the test vector seed so the algorithm's random choices don't line up with the
+	// random input
+	fn algorithm_seed(rng_seed: u64, n: usize) -> u64 {
+		BenchmarkManager::seedgen(rng_seed ^ ALGORITHM_SEED_SALT, n)
+	}
+	fn distribution_name(&self, input_i: usize) -> &str {
+		self.source(input_i).name()
+	}
+	fn source(&self, input_i: usize) -> &inputs::Source {
+		&self.sources[self.inputs[input_i].0]
+	}
+	fn element(&self, input_i: usize) -> ElementType {
+		self.inputs[input_i].1
+	}
+	// e.g. "sorted" or "uniform u8", i32 elements are the usual and aren't mentioned
+	fn input_name(&self, input_i: usize) -> String {
+		match self.element(input_i) {
+			ElementType::I32 => String::from(self.distribution_name(input_i)),
+			element => format!("{} {}", self.distribution_name(input_i), element.name())
+		}
+	}
+	// whether an algorithm can sort an input's element type
+	fn supports(&self, algorithm_i: usize, input_i: usize) -> bool {
+		self.algorithms[algorithm_i].sorts.supports(self.element(input_i))
+	}
+	// keys for a particular run, based off the seed for that run
+	fn test_vector(&self, input_i: usize, size: usize, test_i: usize) -> Vec<i32> {
+		let mut rng = SmallRng::seed_from_u64(BenchmarkManager::seedgen(self.config.seed, test_i));
+		self.source(input_i).keys(size, test_i, &mut rng)
+	}
+	fn run_bench(&self, sorts: &elements::Sorts, input_i: usize, size: usize, test_i: usize) -> u64 {
 		// setup the test itself based off seed for this particular run
-		let mut test_vector: Vec<i32> = vec![0; size];
-		let mut rng = SmallRng::seed_from_u64(BenchmarkManager::seedgen(test_i));
-		for i in 0..size {
-			test_vector[i] = rng.next_u32() as i32;
-		}
-		// sleep briefly - this is an attempt to produce more constant results
-		if !TEST_MODE { thread::sleep(Duration::from_millis(10)) };
-		// test body:
-		let start = Instant::now();
-		sort(&mut test_vector);
-		let r = start.elapsed().as_nanos() as u64;
-		// this is covered in test cases but just to be sure...
-		utils::verify_sorted(&test_vector);
-		r
+		let test_vector = self.test_vector(input_i, size, test_i);
+		// test body (converting to the element type and verifying happen outside the timing)
+		let seed = BenchmarkManager::algorithm_seed(self.config.seed, test_i);
+		algos::context::with_seed(seed, || sorts.run(self.element(input_i), &test_vector, || {
+			// sleep briefly - this is an attempt to produce more constant results
+			if !self.config.test_mode { thread::sleep(Duration::from_millis(10)) };
+		}))
+	}
+	// runs a job, catching panics so a broken algorithm only takes out its own cell
+	fn run_job(&self, job: Job) -> Result<u64, String> {
+		let (algorithm_i, input_i, size_i, test_i) = job;
+		utils::catch_quietly(|| {
+			self.run_bench(&self.algorithms[algorithm_i].sorts, input_i, self.test_sizes[size_i], test_i)
+		})
 	}
-	fn generate_benchmark_jobs(&self) -> Vec<(usize, usize, usize)> {
+	// "algos::x on sorted input at size 1,000, test 3 (seed 42): improperly sorted: ..."
+	// the run's seed and the test index are enough to regenerate the keys
+	fn describe_failure(&self, failure: &Failure) -> String {
+		let (algorithm_i, input_i, size_i, test_i) = failure.job;
+		format!("{} on {} input at size {}, test {} (seed {}): {}", self.algorithms[algorithm_i].name,
+			self.input_name(input_i), utils::commafy(self.test_sizes[size_i]), test_i, self.config.seed,
+			failure.message)
+	}
+	// the first failure in a cell
+	fn failure(&self, algorithm_i: usize, input_i: usize, size_i: usize) -> Option<&Failure> {
+		self.failures.iter().find(|f| (f.job.0, f.job.1, f.job.2) == (algorithm_i, input_i, size_i))
+	}
+	fn failed(&self, algorithm_i: usize, input_i: usize, size_i: usize) -> bool {
+		self.failure(algorithm_i, input_i, size_i).is_some()
+	}
+	// logs a failed job and stops its cell from being scheduled again
+	fn record_failure(&self, failures: &mut Vec<Failure>, time_table: &mut [Vec<Vec<u64>>], failure: Failure) {
+		eprintln!("\x1b[2K\rerror: {}", self.describe_failure(&failure));
+		let (algorithm_i, input_i, size_i, _) = failure.job;
+		time_table[algorithm_i][input_i][size_i] = u64::MAX;
+		failures.push(failure);
+	}
+	// largest size worth running an algorithm on for a given input distribution
+	fn size_limit(&self, algorithm_i: usize, input_i: usize) -> usize {
+		self.algorithms[algorithm_i].size_limit(self.source(input_i).degenerate())
+	}
+	fn generate_benchmark_jobs(&self) -> Vec<Job> {
 		let mut jobs = Vec::new();
-		for size_i in 0..TEST_SIZES.len() {
-			for (i, a) in self.algorithms.iter().enumerate() {
-				if a.0.is_none() {
-					continue;
-				}
-				if TEST_SIZES[size_i] <= *LIMIT_TABLE.get(&a.2).unwrap_or(&usize::MAX) {
-					for n in 0..N_TESTS {
-						jobs.push((i, size_i, n));
+		for size_i in 0..self.test_sizes.len() {
+			for d in 0..self.inputs.len() {
+				for i in 0..self.algorithms.len() {
+					if !self.selected[i] || !self.supports(i, d) {
+						continue;
+					}
+					// recorded inputs don't necessarily have every size
+					let size = self.test_sizes[size_i];
+					if size <= self.size_limit(i, d) && self.source(d).has_size(size) {
+						for n in 0..self.config.n_tests {
+							jobs.push((i, d, size_i, n));
+						}
 					}
 				}
 			}
 		}
 		jobs
 	}
-	fn get_next_job(&self, time_table: &Vec<Vec<u64>>, jobs: &mut Vec<(usize, usize, usize)>)
-		-> Option<(usize, usize, usize)> {
+	// writes every test vector the jobs use to a corpus file (--dump-inputs), returns how many were
+	// written. keys don't depend on the element type so each is written once
+	pub fn dump_inputs(&self, path: &str) -> std::io::Result<usize> {
+		// (source, size, test) indices
+		let vectors: BTreeSet<(usize, usize, usize)> =
+			self.generate_benchmark_jobs()
+				.iter()
+				.map(|(_, d, size_i, test_i)| (self.inputs[*d].0, *size_i, *test_i))
+				.collect();
+		let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+		corpus::write_header(&mut file, self.config.seed, vectors.len())?;
+		for (source_i, size_i, test_i) in &vectors {
+			let input_i = self.inputs.iter().position(|(s, _)| s == source_i).unwrap();
+			let keys = self.test_vector(input_i, self.test_sizes[*size_i], *test_i);
+			corpus::write_array(&mut file, self.sources[*source_i].name(), *test_i, &keys)?;
+		}
+		file.flush()?;
+		Ok(vectors.len())
+	}
+	// the shuffled job stack for a fresh run, or what was left of it in the checkpoint
+	fn initial_jobs(&mut self) -> Vec<Job> {
+		if let Option::Some(jobs) = self.resumed_jobs.take() {
+			return jobs;
+		}
+		let mut jobs = self.generate_benchmark_jobs();
+		// shuffle jobs using seed
+		let mut rng = SmallRng::seed_from_u64(self.config.seed);
+		jobs.shuffle(&mut rng);
+		jobs
+	}
+	fn get_next_job(&self, time_table: &Vec<Vec<Vec<u64>>>, jobs: &mut Vec<Job>) -> Option<Job> {
 		// fetch a new job while discarding any jobs whose predecessors have exceeded the runtime
 		// limit
 		while !jobs.is_empty() {
 			let job = jobs.pop().unwrap();
-			if time_table[job.0][job.1] >= RUNTIME_LIMIT {
+			if time_table[job.0][job.1][job.2] >= self.config.runtime_limit {
 				// discard job and continue
 			} else {
 				return Option::Some(job);
@@ -325,49 +523,33 @@ impl BenchmarkManager {
 		}
 		return Option::None;
 	}
-	fn compute_results(&mut self, results: Vec<Vec<Vec<u64>>>) {
+	fn compute_results(&mut self) {
 		for algorithm_i in 0..self.algorithms.len() {
-			if self.algorithms[algorithm_i].0.is_none() {
+			if !self.selected[algorithm_i] {
 				continue;
 			}
-			for size_i in 0..TEST_SIZES.len() {
-				// compute stats
-				// We had an issue with a few benchmarks randomly having massive standard deviations
-				// every time we'd run the benchmark just a couple results would have anomalies and
-				// there wasn't any consistency or pattern to which benchmarks would have anomalies.
-				// The reason for the massive standard deviations was due to just a couple (usually
-				// just 1) extraneous results in the results vector (e.g. in a benchmark whose
-				// result's mean was ~2,200ns, there was an outlier of 112,600ns blowing up the
-				// standard deviation calculation). Here we use Tukey's method to discard outliers.
-				// note results is shadowed twice here
-				let results = &results[algorithm_i][size_i];
-				if results.len() != N_TESTS {
-					println!("---------->> {} {} {}", self.algorithms[algorithm_i].1,
-													  utils::commafy(TEST_SIZES[size_i]),
-													  results.len());
-				}
-				if results.len() <= MIN_ACCEPTABLE_TESTS {
-					// either not enough tests were performed within the runtime limit or no tests
-					// were performed because of complexity limits
-					self.results_table[algorithm_i][size_i] = Option::None;
+			for d in 0..self.inputs.len() {
+				if !self.supports(algorithm_i, d) {
 					continue;
 				}
-				let q = statistics::quartiles(&results);
-				let results: Vec<u64> = results.into_iter()
-										.map(|item| *item)
-										.filter(|item| statistics::tukey(*item, &q,
-																			OUTLIER_COEFFICIENT))
-										.collect();
-				let mean = results.iter().sum::<u64>() as f64 / results.len() as f64;
-				let stdev = statistics::stdev(&results, mean);
-				self.results_table[algorithm_i][size_i] = Option::Some(BenchmarkResult {
-					mean,
-					stdev,
-					count: results.len(),
-					// fields will be used in display code
-					is_fastest: false,
-					is_stat_fastest: false
-				});
+				for size_i in 0..self.test_sizes.len() {
+					if !self.source(d).has_size(self.test_sizes[size_i]) {
+						continue;
+					}
+					if self.failed(algorithm_i, d, size_i) {
+						self.results_table[algorithm_i][d][size_i] = Option::None;
+						continue;
+					}
+					let results = &self.samples[algorithm_i][d][size_i];
+					if results.len() != self.config.n_tests {
+						println!("---------->> {} {} {} {}", self.algorithms[algorithm_i].name,
+															 self.input_name(d),
+															 utils::commafy(self.test_sizes[size_i]),
+															 results.len());
+					}
+					self.results_table[algorithm_i][d][size_i] =
+						BenchmarkResult::from_samples(results, self.config.outlier_coefficient);
+				}
 			}
 		}
 	}
@@ -394,7 +576,7 @@ impl BenchmarkManager {
 		let (coordinator_tx, coordinator_rx) = mpsc::channel();
 		let mut threads = Vec::new();
 		let mut channels: Vec<Option<mpsc::Sender<MPMessage>>> = Vec::new();
-		for i in 0..*N_WORKERS {
+		for i in 0..self.config.n_workers {
 			// the shadowing here is weird
 			let coordinator_tx = mpsc::Sender::clone(&coordinator_tx);
 			// tx moved into the channels vector
@@ -414,17 +596,14 @@ impl BenchmarkManager {
 				// TODO: this actually isn't making much difference...
 				//utils::set_thread_priority_max();
 				// kickstart the process by requesting work
-				coordinator_tx.send((id, u64::MAX)).unwrap();
+				coordinator_tx.send((id, Option::None)).unwrap();
 				// begin work loop
 				for received in rx {
 					if received.m_type == MType::WorkAssignment {
 						let job = received.get_work_message();
-						let result = BenchmarkManager::run_bench(
-							self_ptr.algorithms[job.algorithm_i].0.unwrap(),
-							TEST_SIZES[job.size],
-							job.test_i
-						);
-						coordinator_tx.send((id, result)).unwrap();
+						// a panic comes back as an error, the thread carries on with the next job
+						let result = self_ptr.run_job((job.algorithm_i, job.input, job.size, job.test_i));
+						coordinator_tx.send((id, Option::Some(result))).unwrap();
 					} else {
 						panic!("unexpected non-WorkAssignment message received in worker");
 					}
@@ -437,27 +616,28 @@ impl BenchmarkManager {
 		// setup the test cases
 		// the size of this Vec is O(really big)
 		// this vec is used like a stack - jobs are consumed from the top
-		let mut jobs = self.generate_benchmark_jobs();
+		let mut jobs = self.initial_jobs();
 		let n_jobs = jobs.len();
 		println!("executing of jobs: {} on {} threads with max size = {}",
 			utils::commafy(n_jobs),
-			*N_WORKERS,
-			utils::commafy(MAX_TEST_SIZE));
-		// shuffle jobs using seed
-		let mut rng = SmallRng::seed_from_u64(RNG_SEED);
-		jobs.shuffle(&mut rng);
+			self.config.n_workers,
+			utils::commafy(self.config.max_size));
 		// we have to keep track of every thread's current job so we know how to assign its output
 		// it's a little ugly and non-elegant. the alternative is to include job info in the thread
 		// result return
-		let mut assignments = vec![Option::<(usize, usize, usize)>::None; *N_WORKERS];
+		let mut assignments = vec![Option::<Job>::None; self.config.n_workers];
 		// our final results will be Vec<Vec<Option<BenchmarkResult>>> but as we get the data needed
 		// for these jobs, we have to store in a Vec<Vec<Vec<u64>>>
-		let mut results = vec![
-							   vec![Vec::<u64>::with_capacity(N_TESTS); TEST_SIZES.len()];
-						  self.algorithms.len()];
+		// this starts out empty unless the run was resumed from a checkpoint
+		let mut results = std::mem::take(&mut self.samples);
 		// keep track of time spent on each cell
 		// could just sum results, but may as well keep running sums in this table
-		let mut time_table = vec![vec![0u64; TEST_SIZES.len()]; self.algorithms.len()];
+		let mut time_table: Vec<Vec<Vec<u64>>> = results.iter().map(|a| {
+			a.iter().map(|d| d.iter().map(|cell| cell.iter().sum()).collect()).collect()
+		}).collect();
+		// failed cells from before a resume stay failed
+		let mut failures = std::mem::take(&mut self.failures);
+		let mut last_checkpoint = 0;
 		// receive loop
 		// goal:
 		//   recieve results from the worker threads
@@ -471,15 +651,23 @@ impl BenchmarkManager {
 		eprint!("starting...");
 		for received in coordinator_rx {
 			let (thread_id, result) = received;
-			if result == u64::MAX /* && assignments[thread_id].is_none() */ {
-				// handle initial work request / kickstart
-				assert!(assignments[thread_id].is_none());
-				// no action needed - just proceed to work dispatch
-			} else {
-				// else log result from worker
-				let (algorithm_i, size_i, _) = assignments[thread_id].unwrap();
-				results[algorithm_i][size_i].push(result);
-				time_table[algorithm_i][size_i] += result;
+			match result {
+				Option::None => {
+					// handle initial work request / kickstart
+					assert!(assignments[thread_id].is_none());
+					// no action needed - just proceed to work dispatch
+				}
+				Option::Some(Ok(result)) => {
+					// else log result from worker
+					let (algorithm_i, d, size_i, _) = assignments[thread_id].unwrap();
+					results[algorithm_i][d][size_i].push(result);
+					// saturating, the cell may have failed on another thread in the meantime
+					time_table[algorithm_i][d][size_i] = time_table[algorithm_i][d][size_i].saturating_add(result);
+				}
+				Option::Some(Err(message)) => {
+					let job = assignments[thread_id].unwrap();
+					self.record_failure(&mut failures, &mut time_table, Failure { job, message, timed_out: false });
+				}
 			}
 			// dispatch new work or teardown
 			if let Option::Some(job) = self.get_next_job(&time_table, &mut jobs) {
@@ -487,25 +675,33 @@ impl BenchmarkManager {
 				if start.elapsed().as_millis() as u64 - last_update >= 1_000 {
 					last_update = start.elapsed().as_millis() as u64;
 					let time_remaining = BenchmarkManager::est_time_remaining(&start, n_jobs, jobs.len());
-					eprint!("\x1b[2K\r{}/{} {} {} {}",
+					eprint!("\x1b[2K\r{}/{} {} {} {} {}",
 								utils::commafy(jobs.len()), utils::commafy(n_jobs),
 								time_remaining,
-								self.algorithms[job.0].1,
-								utils::commafy(TEST_SIZES[job.1]));
+								self.algorithms[job.0].name,
+								self.input_name(job.1),
+								utils::commafy(self.test_sizes[job.2]));
 				}
 				channels[thread_id].as_ref()
 								   .unwrap()
 								   .send(MPMessage::new_work_message(WorkDescriptor {
 									   algorithm_i: job.0,
-									   size: job.1,
-									   test_i: job.2
+									   input: job.1,
+									   size: job.2,
+									   test_i: job.3
 								   })).unwrap();
 				assignments[thread_id] = Option::Some(job);
 			} else {
 				let c = channels[thread_id].take().unwrap();
 				channels[thread_id] = Option::None;
 				drop(c);
-				// could set thread's assignment to none but it doesn't matter
+				assignments[thread_id] = Option::None;
+			}
+			if self.checkpoint_due(&start, &mut last_checkpoint) {
+				// jobs that are currently running haven't finished, they go back on top of the stack
+				let mut pending = jobs.clone();
+				pending.extend(assignments.iter().flatten());
+				self.write_checkpoint(&results, &pending, start.elapsed());
 			}
 		}
 		println!();
@@ -514,8 +710,14 @@ impl BenchmarkManager {
 		for thread in threads {
 			thread.join().unwrap();
 		}
+		self.failures = failures;
+		// a finished checkpoint is just a samples file with every timing
+		if self.config.checkpoint.is_some() {
+			self.write_checkpoint(&results, &[], start.elapsed());
+		}
 		// compute final results
-		self.compute_results(results);
+		self.samples = results;
+		self.compute_results();
 	}
 	pub fn run_benchmarks_single_threaded(&mut self) {
 		// for systems like the raspberry pi zero
@@ -524,22 +726,23 @@ impl BenchmarkManager {
 		// setup the test cases
 		// the size of this Vec is O(really big)
 		// this vec is used like a stack - jobs are consumed from the top
-		let mut jobs = self.generate_benchmark_jobs();
+		let mut jobs = self.initial_jobs();
 		let n_jobs = jobs.len();
 		println!("executing of jobs: {} on single-threaded with max size = {}",
 			utils::commafy(n_jobs),
-			utils::commafy(MAX_TEST_SIZE));
-		// shuffle jobs using seed
-		let mut rng = SmallRng::seed_from_u64(RNG_SEED);
-		jobs.shuffle(&mut rng);
+			utils::commafy(self.config.max_size));
 		// our final results will be Vec<Vec<Option<BenchmarkResult>>> but as we get the data needed
 		// for these jobs, we have to store in a Vec<Vec<Vec<u64>>>
-		let mut results = vec![
-							   vec![Vec::<u64>::with_capacity(N_TESTS); TEST_SIZES.len()];
-						  self.algorithms.len()];
+		// this starts out empty unless the run was resumed from a checkpoint
+		let mut results = std::mem::take(&mut self.samples);
 		// keep track of time spent on each cell
 		// could just sum results, but may as well keep running sums in this table
-		let mut time_table = vec![vec![0u64; TEST_SIZES.len()]; self.algorithms.len()];
+		let mut time_table: Vec<Vec<Vec<u64>>> = results.iter().map(|a| {
+			a.iter().map(|d| d.iter().map(|cell| cell.iter().sum()).collect()).collect()
+		}).collect();
+		// failed cells from before a resume stay failed
+		let mut failures = std::mem::take(&mut self.failures);
+		let mut last_checkpoint = 0;
 		// job loop
 		let start = Instant::now();
 		let mut last_update = 0;
@@ -549,93 +752,656 @@ impl BenchmarkManager {
 			if start.elapsed().as_millis() as u64 - last_update >= 1_000 {
 				last_update = start.elapsed().as_millis() as u64;
 				let time_remaining = BenchmarkManager::est_time_remaining(&start, n_jobs, jobs.len());
-				eprint!("\x1b[2K\r{}/{} {} {} {}",
+				eprint!("\x1b[2K\r{}/{} {} {} {} {}",
 							utils::commafy(jobs.len()), utils::commafy(n_jobs),
 							time_remaining,
-							self.algorithms[job.0].1,
-							utils::commafy(TEST_SIZES[job.1]));
+							self.algorithms[job.0].name,
+							self.input_name(job.1),
+							utils::commafy(self.test_sizes[job.2]));
+			}
+			match self.run_job(job) {
+				Ok(result) => {
+					results[job.0][job.1][job.2].push(result);
+					time_table[job.0][job.1][job.2] += result;
+				}
+				Err(message) => {
+					self.record_failure(&mut failures, &mut time_table, Failure { job, message, timed_out: false });
+				}
+			}
+			if self.checkpoint_due(&start, &mut last_checkpoint) {
+				self.write_checkpoint(&results, &jobs, start.elapsed());
 			}
-			let result = BenchmarkManager::run_bench(
-				self.algorithms[job.0].0.unwrap(),
-				TEST_SIZES[job.1],
-				job.2
-			);
-			results[job.0][job.1].push(result);
-			time_table[job.0][job.1] += result;
 		}
 		println!();
+		self.failures = failures;
+		// a finished checkpoint is just a samples file with every timing
+		if self.config.checkpoint.is_some() {
+			self.write_checkpoint(&results, &[], start.elapsed());
+		}
 		// compute final results
-		self.compute_results(results);
+		self.samples = results;
+		self.compute_results();
 	}
-	pub fn print(&mut self, filter: fn(&String, &str) -> bool) {
-		// mins
-		for i in 0..TEST_SIZES.len() {
-			let mut min_mean = Option::<f64>::None;
-			let mut min_result = Option::<usize>::None;
-			for j in 0..self.algorithms.len() {
-				if self.algorithms[j].0.is_none() { continue; }
-				if filter(&self.algorithms[j].1, self.algorithms[j].2) {
-					let ar = &self.results_table[j][i];
-					if ar.is_some() {
-						let ar = ar.as_ref().unwrap();
-						if min_mean.is_none() || ar.mean < min_mean.unwrap() {
-							min_mean = Option::Some(ar.mean);
-							min_result = Option::Some(j);
-						}
+	// forks a subprocess worker for run_benchmarks_subprocess, exits if that isn't possible
+	#[cfg(unix)]
+	fn spawn_worker(&self, workers: &[Option<subprocess::Worker>]) -> subprocess::Worker {
+		match subprocess::Worker::spawn(workers.iter().flatten(), |job| self.run_job(job)) {
+			Ok(worker) => worker,
+			Err(e) => {
+				eprintln!("\nerror: couldn't start a worker process: {}", e);
+				std::process::exit(1);
+			}
+		}
+	}
+	// same as run_benchmarks but every worker is a forked process (--job-timeout)
+	// strategy:
+	//  * each worker gets one job at a time over a pipe and writes the result back on another
+	//  * the coordinator polls the result pipes, waking up in time for the earliest deadline
+	//  * a worker past the deadline is killed and replaced, its job counts as timed out and the rest of
+	//    its cell isn't run. a worker that dies on its own (abort, segfault) is treated the same way
+	//  * when there's no more work a worker's job pipe is closed, it exits once it sees that
+	#[cfg(unix)]
+	pub fn run_benchmarks_subprocess(&mut self) {
+		let deadline = Duration::from_nanos(self.config.job_timeout.unwrap());
+		let n_workers = std::cmp::max(self.config.n_workers, 1);
+		// setup the test cases
+		// this vec is used like a stack - jobs are consumed from the top
+		let mut jobs = self.initial_jobs();
+		let n_jobs = jobs.len();
+		println!("executing of jobs: {} on {} processes with max size = {} and a {}s timeout",
+			utils::commafy(n_jobs),
+			n_workers,
+			utils::commafy(self.config.max_size),
+			deadline.as_secs_f64());
+		// this starts out empty unless the run was resumed from a checkpoint
+		let mut results = std::mem::take(&mut self.samples);
+		// keep track of time spent on each cell
+		let mut time_table: Vec<Vec<Vec<u64>>> = results.iter().map(|a| {
+			a.iter().map(|d| d.iter().map(|cell| cell.iter().sum()).collect()).collect()
+		}).collect();
+		// failed cells from before a resume stay failed
+		let mut failures = std::mem::take(&mut self.failures);
+		let mut last_checkpoint = 0;
+		// none once a worker has been sent home
+		let mut workers: Vec<Option<subprocess::Worker>> = Vec::new();
+		for _ in 0..n_workers {
+			let worker = self.spawn_worker(&workers);
+			workers.push(Option::Some(worker));
+		}
+		let start = Instant::now();
+		let mut last_update = 0;
+		eprint!("starting...");
+		loop {
+			// dispatch new work to idle workers or tear them down
+			for w in 0..workers.len() {
+				if workers[w].as_ref().is_none_or(|worker| worker.current.is_some()) {
+					continue;
+				}
+				let job = match self.get_next_job(&time_table, &mut jobs) {
+					Option::Some(job) => job,
+					Option::None => {
+						workers[w].take().unwrap().finish();
+						continue;
+					}
+				};
+				// update status roughly every second
+				if start.elapsed().as_millis() as u64 - last_update >= 1_000 {
+					last_update = start.elapsed().as_millis() as u64;
+					let time_remaining = BenchmarkManager::est_time_remaining(&start, n_jobs, jobs.len());
+					eprint!("\x1b[2K\r{}/{} {} {} {} {}",
+								utils::commafy(jobs.len()), utils::commafy(n_jobs),
+								time_remaining,
+								self.algorithms[job.0].name,
+								self.input_name(job.1),
+								utils::commafy(self.test_sizes[job.2]));
+				}
+				if workers[w].as_mut().unwrap().send(job).is_err() {
+					// it died between jobs, which shouldn't happen, the job goes back on the stack
+					let status = workers[w].take().unwrap().reap();
+					eprintln!("\x1b[2K\rwarning: a worker process {} while idle", status);
+					jobs.push(job);
+					workers[w] = Option::Some(self.spawn_worker(&workers));
+				}
+			}
+			if workers.iter().all(Option::is_none) {
+				break;
+			}
+			// every remaining worker is busy, wait for one of them to finish or the earliest deadline
+			let now = Instant::now();
+			let timeout = workers.iter()
+								 .flatten()
+								 .filter_map(|worker| worker.current)
+								 .map(|(_, sent)| (sent + deadline).saturating_duration_since(now))
+								 .min();
+			let timeout = match timeout {
+				Option::Some(timeout) => timeout,
+				// only a replacement for a worker that died while idle is left, it hasn't been sent a job yet
+				Option::None => continue
+			};
+			let ready = match subprocess::wait(&workers, timeout) {
+				Ok(ready) => ready,
+				Err(e) => {
+					eprintln!("\nerror: couldn't wait for the worker processes: {}", e);
+					std::process::exit(1);
+				}
+			};
+			for w in ready {
+				let worker = workers[w].as_mut().unwrap();
+				let (job, _) = worker.current.unwrap();
+				match worker.receive() {
+					Ok(Ok(result)) => {
+						results[job.0][job.1][job.2].push(result);
+						// saturating, the cell may have failed in another worker in the meantime
+						time_table[job.0][job.1][job.2] = time_table[job.0][job.1][job.2].saturating_add(result);
+					}
+					Ok(Err(message)) => {
+						self.record_failure(&mut failures, &mut time_table, Failure { job, message, timed_out: false });
+					}
+					Err(_) => {
+						let message = format!("worker process {}", workers[w].take().unwrap().reap());
+						self.record_failure(&mut failures, &mut time_table, Failure { job, message, timed_out: false });
+						workers[w] = Option::Some(self.spawn_worker(&workers));
+					}
+				}
+			}
+			for w in 0..workers.len() {
+				if workers[w].as_ref().is_some_and(|worker| worker.overdue(deadline)) {
+					let worker = workers[w].take().unwrap();
+					let (job, _) = worker.current.unwrap();
+					worker.kill();
+					let message = format!("timed out after {}s", deadline.as_secs_f64());
+					self.record_failure(&mut failures, &mut time_table, Failure { job, message, timed_out: true });
+					workers[w] = Option::Some(self.spawn_worker(&workers));
+				}
+			}
+			if self.checkpoint_due(&start, &mut last_checkpoint) {
+				// jobs that are currently running haven't finished, they go back on top of the stack
+				let mut pending = jobs.clone();
+				pending.extend(workers.iter().flatten().filter_map(|worker| worker.current).map(|(job, _)| job));
+				self.write_checkpoint(&results, &pending, start.elapsed());
+			}
+		}
+		println!();
+		self.failures = failures;
+		// a finished checkpoint is just a samples file with every timing
+		if self.config.checkpoint.is_some() {
+			self.write_checkpoint(&results, &[], start.elapsed());
+		}
+		// compute final results
+		self.samples = results;
+		self.compute_results();
+	}
+	#[cfg(not(unix))]
+	pub fn run_benchmarks_subprocess(&mut self) {
+		unreachable!("--job-timeout is rejected on this platform");
+	}
+	fn checkpoint_due(&self, start: &Instant, last_checkpoint: &mut u64) -> bool {
+		let elapsed = start.elapsed().as_nanos() as u64;
+		if self.config.checkpoint.is_none() || elapsed - *last_checkpoint < self.config.checkpoint_interval {
+			return false;
+		}
+		*last_checkpoint = elapsed;
+		true
+	}
+	// saves everything needed to pick the run back up with --resume
+	// jobs is the remaining job stack with anything still running on top
+	fn write_checkpoint(&self, results: &[Vec<Vec<Vec<u64>>>], jobs: &[Job], elapsed: Duration) {
+		let path = self.config.checkpoint.as_ref().unwrap();
+		let pending: Vec<samples::PendingJob> = jobs.iter().map(|job| samples::PendingJob {
+			algorithm: String::from(self.algorithms[job.0].name),
+			distribution: String::from(self.distribution_name(job.1)),
+			element: String::from(self.element(job.1).name()),
+			size: self.test_sizes[job.2],
+			test_i: job.3
+		}).collect();
+		let metadata = self.metadata(self.previous_runtime + elapsed);
+		// written to a temporary file first so dying mid-write doesn't lose the previous checkpoint
+		let tmp = format!("{}.tmp", path);
+		let written = std::fs::File::create(&tmp).and_then(|f| {
+			let mut out = std::io::BufWriter::new(f);
+			samples::write_checkpoint(&mut out, &metadata, &self.cells(results), &pending)?;
+			std::io::Write::flush(&mut out)
+		}).and_then(|_| std::fs::rename(&tmp, path));
+		if let Err(e) = written {
+			eprintln!("\nwarning: couldn't write checkpoint {}: {}", path, e);
+		}
+	}
+	pub fn metadata(&self, runtime: Duration) -> export::RunMetadata {
+		export::RunMetadata {
+			profile: String::from(self.config.profile.name()),
+			seed: self.config.seed,
+			n_tests: self.config.n_tests,
+			min_size: self.config.min_size,
+			max_size: self.config.max_size,
+			distributions: self.config.distributions.clone(),
+			elements: self.config.elements.clone(),
+			runtime_limit: self.config.runtime_limit,
+			// anything under 2 runs single-threaded
+			n_workers: std::cmp::max(self.config.n_workers, 1),
+			alpha: self.config.alpha,
+			diff_threshold: self.config.diff_threshold,
+			outlier_coefficient: self.config.outlier_coefficient,
+			cpu: utils::cpu_model(),
+			rustc: String::from(env!("RUSTC_VERSION")),
+			runtime: runtime.as_nanos() as u64
+		}
+	}
+	// raw timings for every selected cell that has any or that failed
+	pub fn sample_cells(&self) -> Vec<samples::SampleCell> {
+		self.cells(&self.samples)
+	}
+	fn cells(&self, results: &[Vec<Vec<Vec<u64>>>]) -> Vec<samples::SampleCell> {
+		let mut cells = Vec::new();
+		for (i, a) in self.algorithms.iter().enumerate() {
+			for (d, sizes) in results[i].iter().enumerate() {
+				for (j, samples) in sizes.iter().enumerate() {
+					let failure = self.failure(i, d, j).map(Failure::cell_failure);
+					if self.selected[i] && (!samples.is_empty() || failure.is_some()) {
+						cells.push(samples::SampleCell {
+							algorithm: String::from(a.name),
+							complexity: String::from(a.complexity.label()),
+							distribution: String::from(self.distribution_name(d)),
+							element: String::from(self.element(d).name()),
+							size: self.test_sizes[j],
+							samples: samples.clone(),
+							failure
+						});
 					}
 				}
 			}
-			if min_result.is_some() {
-				let min_j = min_result.unwrap();
-				self.results_table[min_j][i].as_mut().unwrap().is_fastest = true;
-				let min = self.results_table[min_j][i].clone().unwrap();
+		}
+		cells
+	}
+	// writes every cell with results or a failure to <output_prefix>.<format>
+	// fastest flags are computed across all selected algorithms
+	pub fn export(&mut self, format: cli::OutputFormat, metadata: &export::RunMetadata)
+		-> std::io::Result<String> {
+		let path = format!("{}.{}", self.config.output_prefix, format.extension());
+		let mut file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+		if format == cli::OutputFormat::Samples {
+			return samples::write(&mut file, metadata, &self.sample_cells()).map(|_| path);
+		}
+		self.mark_fastest(|_| true);
+		// in the same order as self.failures
+		let failures: Vec<export::CellFailure> = self.failures.iter().map(Failure::cell_failure).collect();
+		let mut cells = Vec::new();
+		for (i, a) in self.algorithms.iter().enumerate() {
+			for (d, sizes) in self.results_table[i].iter().enumerate() {
+				for (j, result) in sizes.iter().enumerate() {
+					let failure = self.failures.iter().position(|f| (f.job.0, f.job.1, f.job.2) == (i, d, j));
+					let result = match (result, failure) {
+						(_, Option::Some(f)) => Err(&failures[f]),
+						(Option::Some(result), Option::None) => Ok(result),
+						(Option::None, Option::None) => continue
+					};
+					cells.push(export::CellRecord {
+						algorithm: a.name,
+						complexity: a.complexity.label(),
+						distribution: self.distribution_name(d),
+						element: self.element(d).name(),
+						size: self.test_sizes[j],
+						result
+					});
+				}
+			}
+		}
+		let status = match format {
+			cli::OutputFormat::Json => export::write_json(&mut file, metadata, &cells),
+			cli::OutputFormat::Csv => export::write_csv(&mut file, metadata, &cells),
+			cli::OutputFormat::Samples => unreachable!()
+		};
+		self.reset_display();
+		status.map(|_| path)
+	}
+	// run settings from a samples file
+	fn apply_metadata(config: &mut cli::Config, metadata: &export::RunMetadata) {
+		config.profile = cli::Profile::from_name(&metadata.profile).unwrap_or(cli::Profile::Full);
+		config.test_mode = cli::Config::from_profile(config.profile).test_mode;
+		config.seed = metadata.seed;
+		config.n_tests = metadata.n_tests;
+		config.min_size = metadata.min_size;
+		config.max_size = metadata.max_size;
+		config.runtime_limit = metadata.runtime_limit;
+		config.distributions = metadata.distributions.clone();
+		config.elements = metadata.elements.clone();
+	}
+	// sets up a manager from a saved samples file instead of running anything
+	// algorithms, distributions, and element types that are no longer registered are skipped
+	pub fn from_samples(mut config: cli::Config, file: &samples::SampleFile) -> BenchmarkManager {
+		BenchmarkManager::apply_metadata(&mut config, &file.metadata);
+		config.n_workers = file.metadata.n_workers;
+		// only distributions that are actually in the file, in the usual order, then any that came from
+		// a corpus (--inputs-from)
+		config.distributions = inputs::names().into_iter()
+											  .filter(|d| file.cells.iter().any(|c| c.distribution == *d))
+											  .map(String::from)
+											  .collect();
+		for cell in &file.cells {
+			if inputs::find(&cell.distribution).is_none() && !config.distributions.contains(&cell.distribution) {
+				config.distributions.push(cell.distribution.clone());
+			}
+		}
+		config.elements = ElementType::ALL.iter()
+										  .map(|e| e.name())
+										  .filter(|e| file.cells.iter().any(|c| c.element == *e))
+										  .map(String::from)
+										  .collect();
+		let sources = config.distributions.iter().map(|d| match inputs::find(d) {
+			Option::Some(d) => inputs::Source::Generated(d),
+			Option::None => inputs::Source::Recorded { name: d.clone(), arrays: BTreeMap::new() }
+		}).collect();
+		let test_sizes = config.test_sizes();
+		let mut manager = BenchmarkManager::with_sources(config, sources, test_sizes);
+		// and only the combinations that were actually run
+		let inputs = std::mem::take(&mut manager.inputs);
+		manager.inputs = inputs.into_iter()
+							   .filter(|(d, e)| file.cells.iter().any(|c| c.distribution == manager.sources[*d].name()
+																		  && c.element == e.name()))
+							   .collect();
+		manager.test_sizes = file.sizes();
+		manager.samples = vec![vec![vec![Vec::new(); manager.test_sizes.len()]; manager.inputs.len()];
+							   manager.algorithms.len()];
+		manager.results_table = vec![vec![vec![Option::None; manager.test_sizes.len()]; manager.inputs.len()];
+									 manager.algorithms.len()];
+		for cell in &file.cells {
+			let size_i = manager.test_sizes.iter().position(|s| *s == cell.size).unwrap();
+			let d = match manager.find_input(&cell.distribution, &cell.element) {
+				Option::Some(d) => d,
+				Option::None => {
+					eprintln!("warning: skipping unknown input {} {}", cell.distribution, cell.element);
+					continue;
+				}
+			};
+			match manager.algorithms.iter().position(|a| a.name == cell.algorithm) {
+				Option::Some(i) => {
+					manager.samples[i][d][size_i] = cell.samples.clone();
+					if let Option::Some(f) = &cell.failure {
+						manager.failures.push(Failure::from_cell_failure((i, d, size_i), f));
+					}
+				}
+				Option::None => eprintln!("warning: skipping unknown algorithm {}", cell.algorithm)
+			}
+		}
+		// only show what's actually in the file
+		for i in 0..manager.algorithms.len() {
+			manager.selected[i] = manager.selected[i]
+								  && (manager.samples[i].iter().flatten().any(|s| !s.is_empty())
+									  || manager.failures.iter().any(|f| f.job.0 == i));
+		}
+		manager.compute_results();
+		manager
+	}
+	// replays the arrays in a corpus (--inputs-from) instead of generating test vectors
+	// the distributions, sizes, and seed come from the corpus, every (distribution, size) is run on as
+	// many arrays as the one with the fewest has
+	pub fn replay(mut config: cli::Config, corpus: corpus::Corpus) -> Result<BenchmarkManager, String> {
+		if corpus.arrays.iter().any(|a| a.keys.is_empty()) {
+			return Err(String::from("corpus has an empty array"));
+		}
+		let sources = inputs::Source::from_corpus(corpus.arrays);
+		let counts: Vec<(usize, usize)> = sources.iter().flat_map(|s| s.recorded_sizes()).collect();
+		let mut test_sizes: Vec<usize> = counts.iter().map(|c| c.0).collect();
+		test_sizes.sort_unstable();
+		test_sizes.dedup();
+		match counts.iter().map(|c| c.1).min() {
+			Option::Some(n_tests) => config.n_tests = n_tests,
+			Option::None => return Err(String::from("corpus has no arrays"))
+		}
+		config.distributions = sources.iter().map(|s| String::from(s.name())).collect();
+		config.min_size = test_sizes[0];
+		config.max_size = *test_sizes.last().unwrap();
+		config.seed = corpus.seed;
+		Ok(BenchmarkManager::with_sources(config, sources, test_sizes))
+	}
+	fn find_input(&self, distribution: &str, element: &str) -> Option<usize> {
+		(0..self.inputs.len()).position(|d| {
+			self.distribution_name(d) == distribution && self.element(d).name() == element
+		})
+	}
+	// picks up an interrupted run from a checkpoint, the selection is whatever is in it
+	pub fn resume(mut config: cli::Config, file: &samples::SampleFile) -> Result<BenchmarkManager, String> {
+		BenchmarkManager::apply_metadata(&mut config, &file.metadata);
+		if let Option::Some(d) = config.distributions.iter().find(|d| inputs::find(d).is_none()) {
+			return Err(format!("checkpoint has an unknown distribution {}", d));
+		}
+		if let Option::Some(e) = config.elements.iter().find(|e| ElementType::from_name(e).is_none()) {
+			return Err(format!("checkpoint has an unknown element type {}", e));
+		}
+		let mut manager = BenchmarkManager::new(config);
+		manager.selected = vec![false; manager.algorithms.len()];
+		let find = |manager: &BenchmarkManager, algorithm: &str, distribution: &str, element: &str, size: usize| {
+			let i = manager.algorithms.iter().position(|a| a.name == algorithm);
+			let d = manager.find_input(distribution, element);
+			let size_i = manager.test_sizes.iter().position(|s| *s == size);
+			match (i, d, size_i) {
+				(Option::Some(i), Option::Some(d), Option::Some(size_i)) if manager.supports(i, d) => {
+					Ok((i, d, size_i))
+				}
+				_ => Err(format!("checkpoint has an unknown cell {} {} {} {}", algorithm, distribution,
+					element, size))
+			}
+		};
+		for cell in &file.cells {
+			let (i, d, size_i) = find(&manager, &cell.algorithm, &cell.distribution, &cell.element, cell.size)?;
+			manager.samples[i][d][size_i] = cell.samples.clone();
+			manager.selected[i] = true;
+			if let Option::Some(f) = &cell.failure {
+				manager.failures.push(Failure::from_cell_failure((i, d, size_i), f));
+			}
+		}
+		let mut jobs = Vec::with_capacity(file.jobs.len());
+		for job in &file.jobs {
+			let (i, d, size_i) = find(&manager, &job.algorithm, &job.distribution, &job.element, job.size)?;
+			manager.selected[i] = true;
+			// jobs other workers had already been given from a cell that failed
+			if !manager.failed(i, d, size_i) {
+				jobs.push((i, d, size_i, job.test_i));
+			}
+		}
+		manager.resumed_jobs = Option::Some(jobs);
+		manager.previous_runtime = Duration::from_nanos(file.metadata.runtime);
+		Ok(manager)
+	}
+	// whether a table with this filter would have any rows for an input
+	pub fn any_selected(&self, filter: AlgorithmFilter, input_i: usize) -> bool {
+		self.algorithms.iter()
+					   .enumerate()
+					   .any(|(i, a)| self.selected[i] && self.supports(i, input_i) && filter(a))
+	}
+	// flags the fastest result for each input and size and anything statistically tied with it,
+	// among the algorithms passing the filter
+	fn mark_fastest(&mut self, filter: AlgorithmFilter) {
+		// mins
+		for d in 0..self.inputs.len() {
+			for i in 0..self.test_sizes.len() {
+				let mut min_mean = Option::<f64>::None;
+				let mut min_result = Option::<usize>::None;
 				for j in 0..self.algorithms.len() {
-					if self.algorithms[j].0.is_none() { continue; }
-					if filter(&self.algorithms[j].1, self.algorithms[j].2) {
-						if self.results_table[j][i].is_some() {
-							let ar = self.results_table[j][i].as_mut().unwrap();
-							ar.update_display(&min);
+					if !self.selected[j] { continue; }
+					if filter(&self.algorithms[j]) {
+						let ar = &self.results_table[j][d][i];
+						if ar.is_some() {
+							let ar = ar.as_ref().unwrap();
+							if min_mean.is_none() || ar.mean < min_mean.unwrap() {
+								min_mean = Option::Some(ar.mean);
+								min_result = Option::Some(j);
+							}
+						}
+					}
+				}
+				if min_result.is_some() {
+					let min_j = min_result.unwrap();
+					self.results_table[min_j][d][i].as_mut().unwrap().is_fastest = true;
+					let min = self.results_table[min_j][d][i].clone().unwrap();
+					for j in 0..self.algorithms.len() {
+						if !self.selected[j] { continue; }
+						if filter(&self.algorithms[j]) {
+							if self.results_table[j][d][i].is_some() {
+								let ar = self.results_table[j][d][i].as_mut().unwrap();
+								ar.update_display(&min, self.config.alpha, self.config.diff_threshold);
+							}
 						}
 					}
 				}
 			}
 		}
-		// make pretty table
+	}
+	// mean operation counts over the first few test vectors of an input, indexed [algorithm][size]
+	// none where an algorithm isn't selected, can't be instrumented, is past its size limit, or failed
+	// cells that failed during the run aren't counted again (a timed out one would never finish), and a
+	// panic while counting only takes out its own cell
+	fn count_operations(&self, input_i: usize) -> Vec<Vec<Option<operations::Means>>> {
+		let runs = std::cmp::min(self.config.n_tests, OPERATION_RUNS);
+		self.algorithms.iter().enumerate().map(|(i, a)| {
+			self.test_sizes.iter().enumerate().map(|(size_i, size)| {
+				let sort = a.sorts.counted;
+				if !self.selected[i] || sort.is_none() || *size > self.size_limit(i, input_i)
+				   || !self.source(input_i).has_size(*size) || self.failed(i, input_i, size_i) {
+					return Option::None;
+				}
+				let counts = utils::catch_quietly(|| {
+					(0..runs).map(|test_i| {
+						let seed = BenchmarkManager::algorithm_seed(self.config.seed, test_i);
+						algos::context::with_seed(seed, || {
+							operations::count(sort.unwrap(), &self.test_vector(input_i, *size, test_i))
+						})
+					}).collect::<Vec<operations::Counts>>()
+				});
+				counts.ok().map(|counts| operations::mean(&counts))
+			}).collect()
+		}).collect()
+	}
+	// one row per algorithm with counts, cell formats a count
+	fn operations_table(&self, counts: &[Vec<Option<operations::Means>>], include: impl Fn(&Algorithm) -> bool,
+						cell: impl Fn(&operations::Means, usize) -> String) -> Table {
 		let mut table = Table::new();
 		table.add_row(Row::new(std::iter::once(String::from(""))
-								.chain(TEST_SIZES
+								.chain(self.test_sizes
 										.iter()
 										.map(|x| utils::commafy(*x)))
 								.map(|x| Cell::new(&x)).collect()));
 		for (i, a) in self.algorithms.iter().enumerate() {
-			if filter(&a.1, a.2) {
-				if self.algorithms[i].0.is_some() {
-					let mut row = vec![Cell::new(&a.1)];
-					for result in self.results_table[i].iter() {
-						if result.is_none() {
-							row.push(Cell::new("-"));
-						} else {
-							row.push(Cell::new(&format!("{}", result.as_ref().unwrap())));
-						}
-					}
-					table.add_row(Row::new(row));
-				} else {
-					table.add_row(Row::new(vec![Cell::new("--").style_spec("c"); TEST_SIZES.len() + 1]));
+			if !include(a) || counts[i].iter().all(|c| c.is_none()) {
+				continue;
+			}
+			let mut row = vec![Cell::new(a.name)];
+			for (size, count) in self.test_sizes.iter().zip(&counts[i]) {
+				match count {
+					Option::Some(count) => row.push(Cell::new(&cell(count, *size))),
+					Option::None => row.push(Cell::new("-"))
 				}
 			}
+			table.add_row(Row::new(row));
+		}
+		table
+	}
+	pub fn print_operations(&self, input_i: usize) {
+		let counts = self.count_operations(input_i);
+		let runs = std::cmp::min(self.config.n_tests, OPERATION_RUNS);
+		self.operations_table(&counts, |_| true, |count, size| {
+			format!("{} ({:.2})", utils::commafy(count.comparisons.round() as usize),
+				operations::per_n_log_n(count.comparisons, size))
+		}).printstd();
+		println!("└ Mean comparisons per sort over the first {} test vectors; (x) = comparisons / (n log2 n)", runs);
+		// copies of Copy types can't be counted, only the move-based variants report what they do
+		let moves = self.operations_table(&counts, |a| a.counts_moves, |count, _| {
+			format!("{} / {}", utils::commafy(count.moves.round() as usize),
+				utils::commafy(count.swaps.round() as usize))
+		});
+		if moves.len() > 1 {
+			println!();
+			println!("Moves / swaps of the move-based variants:");
+			moves.printstd();
+			println!("└ Mean element moves / swaps per sort over the first {} test vectors; a move writes one \
+					  element somewhere new, a swap exchanges two", runs);
+		}
+	}
+	// fits each algorithm's mean runtimes, returns the algorithms that grow faster than their label allows
+	// growing slower is only noted in the table, the labels are worst cases and easy inputs do better
+	pub fn print_complexity(&self, input_i: usize) -> Vec<String> {
+		let mut table = Table::new();
+		table.add_row(row!["", "declared", "exponent", "best fit (ns)", "error", ""]);
+		let mut contradictions = Vec::new();
+		for (i, a) in self.algorithms.iter().enumerate() {
+			if !self.selected[i] || !self.supports(i, input_i) {
+				continue;
+			}
+			let points: Vec<(usize, f64)> = self.test_sizes.iter()
+														   .zip(&self.results_table[i][input_i])
+														   .filter_map(|(size, r)| r.map(|r| (*size, r.mean)))
+														   .collect();
+			let report = match complexity::analyze(&points, a.complexity.model()) {
+				Option::Some(report) => report,
+				Option::None => continue
+			};
+			let verdict = match report.verdict {
+				complexity::Verdict::Consistent => "",
+				complexity::Verdict::Faster => "grows faster than declared",
+				complexity::Verdict::Slower => "grows slower than declared (fine on easy inputs)"
+			};
+			if report.verdict == complexity::Verdict::Faster {
+				contradictions.push(format!("{}: declared {} but grows like n^{:.2} ({} input)", a.name,
+					a.complexity.label(), report.exponent, self.input_name(input_i)));
+			}
+			table.add_row(row![a.name, a.complexity.label(), format!("{:.2}", report.exponent),
+				format!("{:.3} * {}", report.best.constant, report.best.model.name()),
+				format!("{:.3}", report.best.error), verdict]);
 		}
 		table.printstd();
-		println!("└ Values in ms; 98% confidence interval displayed; \
-					s = statistically equal to fastest; * = within 5% of fastest");
-		// reset mins / maxes
+		println!("└ Fit on sizes from {}; exponent = slope of log time against log n; error = rms of the log \
+					residuals", utils::commafy(complexity::FIT_MIN_SIZE));
+		contradictions
+	}
+	fn reset_display(&mut self) {
 		for a in &mut self.results_table {
-			for b in a {
+			for b in a.iter_mut().flatten() {
 				if b.is_some() {
 					b.as_mut().unwrap().reset_display();
 				}
 			}
 		}
 	}
+	pub fn print(&mut self, filter: AlgorithmFilter, input_i: usize) {
+		self.mark_fastest(filter);
+		// make pretty table
+		let mut table = Table::new();
+		table.add_row(Row::new(std::iter::once(String::from(""))
+								.chain(self.test_sizes
+										.iter()
+										.map(|x| utils::commafy(*x)))
+								.map(|x| Cell::new(&x)).collect()));
+		let mut primary_shown = false;
+		let mut separated = false;
+		for (i, a) in self.algorithms.iter().enumerate() {
+			if !filter(a) || !self.selected[i] || !self.supports(i, input_i) {
+				continue;
+			}
+			// separator between the main algorithms and the odd ones, only shown if there's something to
+			// separate
+			if a.secondary && primary_shown && !separated {
+				table.add_row(Row::new(vec![Cell::new("--").style_spec("c"); self.test_sizes.len() + 1]));
+				separated = true;
+			}
+			primary_shown |= !a.secondary;
+			let mut row = vec![Cell::new(a.name)];
+			for (j, result) in self.results_table[i][input_i].iter().enumerate() {
+				if let Option::Some(failure) = self.failure(i, input_i, j) {
+					row.push(Cell::new(if failure.timed_out { "timed out" } else { "failed" }));
+				} else if result.is_none() {
+					row.push(Cell::new("-"));
+				} else {
+					row.push(Cell::new(&format!("{}", result.as_ref().unwrap())));
+				}
+			}
+			table.add_row(Row::new(row));
+		}
+		table.printstd();
+		println!("└ Values in ms; 98% confidence interval displayed; \
+					s = statistically equal to fastest; * = within {}% of fastest",
+					self.config.diff_threshold * 100.0);
+		// reset mins / maxes
+		self.reset_display();
+	}
 }
 
 #[cfg(test)]
@@ -645,62 +1411,698 @@ mod test {
 		use std::collections::HashSet;
 		use super::*;
 		// This test ensures that seedgen properly generates unique seeds
+		let config = cli::Config::from_profile(cli::Profile::Full);
 		let mut set = HashSet::new();
-		for n in 0..N_TESTS {
-			assert!(set.insert(BenchmarkManager::seedgen(n)));
+		for n in 0..config.n_tests {
+			assert!(set.insert(BenchmarkManager::seedgen(config.seed, n)));
 		}
 		println!("{:?}", set);
-		assert!(set.len() == N_TESTS);
+		assert!(set.len() == config.n_tests);
+	}
+	#[test]
+	fn test_algorithm_seed() {
+		use super::*;
+		let config = cli::Config::from_profile(cli::Profile::Full);
+		// distinct from the test vector seeds
+		for n in 0..config.n_tests {
+			assert_ne!(BenchmarkManager::algorithm_seed(config.seed, n), BenchmarkManager::seedgen(config.seed, n));
+		}
+		assert_ne!(BenchmarkManager::algorithm_seed(1, 0), BenchmarkManager::algorithm_seed(2, 0));
+	}
+	#[test]
+	fn test_resume() {
+		use super::*;
+		let mut config = cli::Config::from_profile(cli::Profile::Full);
+		config.n_tests = 50;
+		config.max_size = 100;
+		config.distributions = vec![String::from("uniform"), String::from("sorted")];
+		let manager = BenchmarkManager::new(config.clone());
+		let metadata = manager.metadata(Duration::from_secs(5));
+		let file = samples::SampleFile {
+			metadata,
+			cells: vec![samples::SampleCell {
+				algorithm: String::from("algos::timsort"),
+				complexity: String::from("O(n log n)"),
+				distribution: String::from("sorted"),
+				element: String::from("i32"),
+				size: 10,
+				samples: vec![1; 50],
+				failure: Option::None
+			}, samples::SampleCell {
+				algorithm: String::from("algos::radixsort"),
+				complexity: String::from("O(n)"),
+				distribution: String::from("uniform"),
+				element: String::from("i32"),
+				size: 10,
+				samples: vec![1; 2],
+				failure: Option::Some(export::CellFailure {
+					test_i: 5,
+					message: String::from("improperly sorted"),
+					timed_out: false
+				})
+			}],
+			jobs: vec![
+				// another worker had already been given this one when the cell failed
+				samples::PendingJob {
+					algorithm: String::from("algos::radixsort"),
+					distribution: String::from("uniform"),
+					element: String::from("i32"),
+					size: 10,
+					test_i: 7
+				},
+				samples::PendingJob {
+					algorithm: String::from("algos::radixsort"),
+					distribution: String::from("uniform"),
+					element: String::from("i32"),
+					size: 100,
+					test_i: 3
+				},
+				samples::PendingJob {
+					algorithm: String::from("algos::timsort"),
+					distribution: String::from("sorted"),
+					element: String::from("i32"),
+					size: 100,
+					test_i: 9
+				}
+			]
+		};
+		let manager = BenchmarkManager::resume(cli::Config::from_profile(cli::Profile::Quick), &file).unwrap();
+		// run settings come from the checkpoint
+		assert_eq!(manager.config.n_tests, 50);
+		assert_eq!(manager.test_sizes, vec![10, 100]);
+		assert_eq!(manager.inputs.len(), 2);
+		assert_eq!(manager.previous_runtime, Duration::from_secs(5));
+		let selected: Vec<&str> = manager.algorithms.iter()
+													.enumerate()
+													.filter(|(i, _)| manager.selected[*i])
+													.map(|(_, a)| a.name)
+													.collect();
+		assert_eq!(selected, vec!["algos::timsort", "algos::radixsort"]);
+		let timsort = manager.algorithms.iter().position(|a| a.name == "algos::timsort").unwrap();
+		let radixsort = manager.algorithms.iter().position(|a| a.name == "algos::radixsort").unwrap();
+		assert_eq!(manager.samples[timsort][1][0].len(), 50);
+		assert_eq!(manager.resumed_jobs, Option::Some(vec![(radixsort, 0, 1, 3), (timsort, 1, 1, 9)]));
+		assert_eq!(manager.samples[radixsort][0][0], vec![1; 2]);
+		assert_eq!(manager.failure(radixsort, 0, 0).unwrap().job, (radixsort, 0, 0, 5));
+		let mut bad = file;
+		bad.jobs[0].size = 1000;
+		assert!(BenchmarkManager::resume(config, &bad).is_err());
+	}
+	#[test]
+	fn test_failures() {
+		use super::*;
+		let mut config = cli::Config::from_profile(cli::Profile::Quick);
+		config.max_size = 100;
+		config.n_tests = 40;
+		config.algorithms = vec![String::from("timsort"), String::from("mergesort"), String::from("heapsort_top_down")];
+		for n_workers in &[1, 3] {
+			config.n_workers = *n_workers;
+			let mut manager = BenchmarkManager::new(config.clone());
+			let find = |name| manager.algorithms.iter().position(|a| a.name == name).unwrap();
+			let (timsort, mergesort, heapsort) = (find("algos::timsort"), find("algos::mergesort"),
+												  find("algos::heapsort_top_down"));
+			// one panics past size 10, the other doesn't sort at all
+			manager.algorithms[mergesort].sorts = elements::Sorts::i32_only(|array| {
+				assert!(array.len() <= 10, "too big");
+				array.sort();
+			});
+			manager.algorithms[heapsort].sorts = elements::Sorts::i32_only(|array| array.reverse());
+			if *n_workers < 2 {
+				manager.run_benchmarks_single_threaded();
+			} else {
+				manager.run_benchmarks();
+			}
+			assert!(manager.results_table[timsort][0].iter().all(|r| r.is_some()));
+			assert!(manager.results_table[mergesort][0][0].is_some());
+			assert!(!manager.failed(mergesort, 0, 0) && manager.failed(mergesort, 0, 1));
+			assert!(manager.results_table[mergesort][0][1].is_none());
+			assert!(manager.failed(heapsort, 0, 0) && manager.failed(heapsort, 0, 1));
+			let failure = manager.failures.iter().find(|f| f.job.0 == mergesort).unwrap();
+			assert_eq!(failure.message, "too big");
+			assert!(manager.describe_failure(failure)
+						   .starts_with("algos::mergesort on uniform input at size 100, test "));
+			// the rest of a failed cell isn't run, though other threads may already have been given jobs from it
+			assert!(manager.failures.len() >= 3 && manager.failures.len() <= 3 * *n_workers);
+			// failed cells are saved along with what went wrong
+			let cells = manager.sample_cells();
+			let saved: Vec<(&str, usize, bool)> = cells.iter()
+													   .map(|c| (c.algorithm.as_str(), c.size, c.failure.is_some()))
+													   .collect();
+			assert_eq!(saved, vec![("algos::mergesort", 10, false), ("algos::mergesort", 100, true),
+								   ("algos::heapsort_top_down", 10, true), ("algos::heapsort_top_down", 100, true),
+								   ("algos::timsort", 10, false), ("algos::timsort", 100, false)]);
+			let metadata = manager.metadata(Duration::from_secs(1));
+			let file = samples::SampleFile { metadata, cells, jobs: Vec::new() };
+			let reloaded = BenchmarkManager::from_samples(config.clone(), &file);
+			let mergesort = reloaded.algorithms.iter().position(|a| a.name == "algos::mergesort").unwrap();
+			assert!(reloaded.results_table[mergesort][0][0].is_some());
+			assert!(reloaded.results_table[mergesort][0][1].is_none());
+			assert_eq!(reloaded.failure(mergesort, 0, 1).unwrap().message, "too big");
+		}
+	}
+	#[test]
+	#[cfg(unix)]
+	fn test_job_timeout() {
+		use super::*;
+		let mut config = cli::Config::from_profile(cli::Profile::Quick);
+		config.max_size = 100;
+		config.n_tests = 40;
+		config.n_workers = 2;
+		config.job_timeout = Option::Some(200_000_000);
+		config.algorithms = vec![String::from("timsort"), String::from("mergesort"), String::from("heapsort_top_down")];
+		let mut manager = BenchmarkManager::new(config);
+		let find = |name| manager.algorithms.iter().position(|a| a.name == name).unwrap();
+		let (timsort, mergesort, heapsort) = (find("algos::timsort"), find("algos::mergesort"),
+											  find("algos::heapsort_top_down"));
+		// past size 10 one never finishes and the other takes its worker down
+		manager.algorithms[mergesort].sorts = elements::Sorts::i32_only(|array| {
+			while array.len() > 10 {
+				thread::sleep(Duration::from_millis(10));
+			}
+			array.sort();
+		});
+		manager.algorithms[heapsort].sorts = elements::Sorts::i32_only(|array| {
+			if array.len() > 10 {
+				unsafe { libc::raise(libc::SIGKILL) };
+			}
+			array.sort();
+		});
+		let start = Instant::now();
+		manager.run_benchmarks_subprocess();
+		// killed workers are replaced, everything else still runs
+		assert!(start.elapsed() < Duration::from_secs(10));
+		assert!(manager.results_table[timsort][0].iter().all(|r| r.is_some()));
+		assert!(manager.results_table[mergesort][0][0].is_some() && manager.results_table[heapsort][0][0].is_some());
+		let timed_out = manager.failure(mergesort, 0, 1).unwrap();
+		assert!(timed_out.timed_out);
+		assert_eq!(timed_out.message, "timed out after 0.2s");
+		let died = manager.failure(heapsort, 0, 1).unwrap();
+		assert!(!died.timed_out);
+		assert_eq!(died.message, "worker process killed by signal 9");
+		assert!(manager.failures.iter().all(|f| f.job.2 == 1 && f.job.0 != timsort));
+	}
+	#[test]
+	fn test_operations_after_failures() {
+		use super::*;
+		let mut config = cli::Config::from_profile(cli::Profile::Quick);
+		config.max_size = 100;
+		config.n_tests = 10;
+		config.operations = true;
+		config.outputs = vec![cli::OutputFormat::Json, cli::OutputFormat::Csv];
+		let prefix = std::env::temp_dir().join(format!("sorting-benchmarks-operations-{}", std::process::id()));
+		config.output_prefix = String::from(prefix.to_str().unwrap());
+		config.algorithms = vec![String::from("timsort_move"), String::from("mergesort_move"),
+								 String::from("heapsort_move")];
+		let mut manager = BenchmarkManager::new(config.clone());
+		let find = |name| manager.algorithms.iter().position(|a| a.name == name).unwrap();
+		let (timsort, mergesort, heapsort) = (find("algos::timsort_move"), find("algos::mergesort_move"),
+											  find("algos::heapsort_move"));
+		// one fails past size 10 in the run and would again while counting, the other only fails to count
+		manager.algorithms[mergesort].sorts.i32 = |array| {
+			assert!(array.len() <= 10, "too big");
+			array.sort();
+		};
+		manager.algorithms[mergesort].sorts.counted = Option::Some(|array| {
+			assert!(array.len() <= 10, "too big");
+			algos::mergesort_move_counted(array);
+		});
+		manager.algorithms[heapsort].sorts.counted = Option::Some(|_| panic!("can't count"));
+		manager.run_benchmarks_single_threaded();
+		let metadata = manager.metadata(Duration::from_secs(1));
+		write_outputs(&mut manager, &metadata);
+		let counts = manager.count_operations(0);
+		assert!(counts[timsort].iter().all(|c| c.is_some()));
+		assert!(counts[mergesort][0].is_some() && counts[mergesort][1].is_none());
+		assert!(counts[heapsort].iter().all(|c| c.is_none()));
+		// the failed cell is in the outputs
+		for extension in &["json", "csv"] {
+			let path = format!("{}.{}", config.output_prefix, extension);
+			let written = std::fs::read_to_string(&path).unwrap();
+			std::fs::remove_file(&path).unwrap();
+			assert!(written.contains("algos::mergesort_move") && written.contains("too big"), "{}", extension);
+		}
+	}
+	#[test]
+	fn test_datasets() {
+		use super::*;
+		let path = std::env::temp_dir().join(format!("sorting-benchmarks-{}.txt", std::process::id()));
+		std::fs::write(&path, "3\n1\n2\n").unwrap();
+		let mut config = cli::Config::from_profile(cli::Profile::Quick);
+		config.distributions = vec![String::from("sorted")];
+		config.datasets = vec![String::from(path.to_str().unwrap())];
+		let manager = BenchmarkManager::with_datasets(config.clone()).unwrap();
+		let name = datasets::name(path.to_str().unwrap());
+		assert_eq!(manager.config.distributions, vec![String::from("sorted"), name.clone()]);
+		assert_eq!(manager.distribution_name(1), name);
+		// windows don't fit past 3 values
+		assert!(manager.generate_benchmark_jobs().iter().all(|job| job.1 == 0));
+		// names have to be unique
+		config.datasets.push(config.datasets[0].clone());
+		assert!(BenchmarkManager::with_datasets(config.clone()).is_err());
+		std::fs::remove_file(&path).unwrap();
+		config.datasets.pop();
+		assert!(BenchmarkManager::with_datasets(config).is_err());
+	}
+	#[test]
+	fn test_dump_and_replay() {
+		use super::*;
+		let mut config = cli::Config::from_profile(cli::Profile::Quick);
+		config.max_size = 100;
+		config.n_tests = 3;
+		config.distributions = vec![String::from("uniform"), String::from("sorted")];
+		config.algorithms = vec![String::from("timsort")];
+		let manager = BenchmarkManager::new(config.clone());
+		let path = std::env::temp_dir().join(format!("sorting-benchmarks-{}.corpus", std::process::id()));
+		let path = path.to_str().unwrap();
+		assert_eq!(manager.dump_inputs(path).unwrap(), 2 * 2 * 3);
+		let corpus = corpus::load(path).unwrap();
+		std::fs::remove_file(path).unwrap();
+		assert_eq!(corpus.seed, config.seed);
+		// the replay runs on exactly the same keys
+		let mut other = cli::Config::from_profile(cli::Profile::Quick);
+		other.seed = 1;
+		other.algorithms = config.algorithms.clone();
+		let replay = BenchmarkManager::replay(other, corpus).unwrap();
+		assert_eq!(replay.test_sizes, vec![10, 100]);
+		assert_eq!((replay.config.n_tests, replay.config.seed), (3, config.seed));
+		assert_eq!(replay.config.distributions, config.distributions);
+		for d in 0..manager.inputs.len() {
+			for size in &manager.test_sizes {
+				for test_i in 0..3 {
+					assert_eq!(replay.test_vector(d, *size, test_i), manager.test_vector(d, *size, test_i));
+				}
+			}
+		}
+		assert_eq!(replay.generate_benchmark_jobs().len(), manager.generate_benchmark_jobs().len());
+		// external data with sizes missing and uneven numbers of arrays
+		let array = |name: &str, test_i, size| corpus::Array { name: String::from(name), test_i, keys: vec![1; size] };
+		let corpus = corpus::Corpus { seed: 0, arrays: vec![
+			array("prices", 0, 10), array("prices", 1, 10), array("prices", 2, 10),
+			array("prices", 0, 20), array("prices", 1, 20),
+			array("sorted", 0, 20), array("sorted", 1, 20)
+		] };
+		let replay = BenchmarkManager::replay(config.clone(), corpus).unwrap();
+		assert_eq!(replay.test_sizes, vec![10, 20]);
+		assert_eq!(replay.config.n_tests, 2);
+		assert_eq!(replay.config.distributions, vec!["prices", "sorted"]);
+		assert!(replay.generate_benchmark_jobs().iter().all(|job| job.1 == 0 || job.2 == 1));
+		assert_eq!(replay.generate_benchmark_jobs().len(), 3 * 2);
+		assert!(BenchmarkManager::replay(config.clone(), corpus::Corpus { seed: 0, arrays: Vec::new() }).is_err());
+		assert!(BenchmarkManager::replay(config, corpus::Corpus { seed: 0, arrays: vec![array("empty", 0, 0)] })
+			.is_err());
 	}
 }
 
-fn main() {
-	let mut manager = BenchmarkManager::new();
+fn print_tables(manager: &mut BenchmarkManager) {
+	for d in 0..manager.inputs.len() {
+		// inputs other than the usual uniform i32 one get labeled
+		let label = if manager.inputs.len() > 1 || manager.input_name(d) != "uniform" {
+			format!(" ({} input)", manager.input_name(d))
+		} else {
+			String::new()
+		};
+		for (title, filter) in TABLES {
+			if manager.any_selected(*filter, d) {
+				println!("{}{}:", title, label);
+				manager.print(*filter, d);
+				println!();
+			}
+		}
+
+		let totals_filter: AlgorithmFilter = |a| a.family != Family::Radix;
+		if manager.any_selected(totals_filter, d) {
+			println!("Totals{}:", label);
+			manager.print(totals_filter, d);
+			println!();
+		}
+	}
+}
+
+// comparison counts depend on the order of the keys, not on the element type, so there's one
+// table per distribution
+fn print_operations(manager: &BenchmarkManager) {
+	let first_element = manager.element(0);
+	for d in 0..manager.inputs.len() {
+		if manager.element(d) != first_element {
+			continue;
+		}
+		if manager.config.distributions.len() > 1 || manager.distribution_name(d) != "uniform" {
+			println!("Comparisons ({} input):", manager.distribution_name(d));
+		} else {
+			println!("Comparisons:");
+		}
+		manager.print_operations(d);
+		println!();
+	}
+}
+
+// algorithms without enough sizes to fit are left out
+fn print_complexity(manager: &BenchmarkManager) {
+	let mut contradictions = Vec::new();
+	for d in 0..manager.inputs.len() {
+		if manager.inputs.len() > 1 || manager.input_name(d) != "uniform" {
+			println!("Complexity ({} input):", manager.input_name(d));
+		} else {
+			println!("Complexity:");
+		}
+		contradictions.extend(manager.print_complexity(d));
+		println!();
+	}
+	for contradiction in contradictions {
+		println!("warning: {}", contradiction);
+	}
+}
+
+// repeated after the tables since the log lines from the run will have scrolled away
+fn print_failures(manager: &BenchmarkManager) {
+	if manager.failures.is_empty() {
+		return;
+	}
+	println!("{} job(s) failed or timed out, their cells are marked in the tables:", manager.failures.len());
+	for failure in &manager.failures {
+		println!("  {}", manager.describe_failure(failure));
+	}
+	println!();
+}
+
+fn write_outputs(manager: &mut BenchmarkManager, metadata: &export::RunMetadata) {
+	for format in manager.config.outputs.clone() {
+		match manager.export(format, metadata) {
+			Ok(path) => println!("wrote {}", path),
+			Err(e) => eprintln!("error: failed to write {} output: {}", format.extension(), e)
+		}
+	}
+}
+
+// exits on failure
+fn read_samples(path: &str) -> samples::SampleFile {
+	let file = std::fs::File::open(path)
+					.map_err(|e| e.to_string())
+					.and_then(|f| samples::read(std::io::BufReader::new(f)));
+	match file {
+		Ok(file) => file,
+		Err(e) => {
+			eprintln!("error: couldn't read {}: {}", path, e);
+			std::process::exit(1);
+		}
+	}
+}
+
+fn analyze(config: cli::Config, path: &str) {
+	let file = read_samples(path);
+	println!("analyzing {} (profile: {}, seed: {}, cpu: {})", path, file.metadata.profile,
+		file.metadata.seed, file.metadata.cpu);
+	let mut metadata = file.metadata.clone();
+	metadata.alpha = config.alpha;
+	metadata.diff_threshold = config.diff_threshold;
+	metadata.outlier_coefficient = config.outlier_coefficient;
+	let mut manager = BenchmarkManager::from_samples(config, &file);
+	if let Err(e) = manager.validate_selection() {
+		eprintln!("error: {}", e);
+		std::process::exit(1);
+	}
+	print_tables(&mut manager);
+	if manager.config.complexity {
+		print_complexity(&manager);
+	}
+	write_outputs(&mut manager, &metadata);
+}
+
+// runs the selected benchmarks, exits if the selection doesn't make sense
+fn run(config: cli::Config) -> (BenchmarkManager, Duration) {
+	let mut manager = match &config.resume {
+		Option::Some(path) => {
+			let file = read_samples(path);
+			match BenchmarkManager::resume(config.clone(), &file) {
+				Ok(manager) => {
+					println!("resuming {} ({} jobs left)", path, utils::commafy(file.jobs.len()));
+					manager
+				}
+				Err(e) => {
+					eprintln!("error: couldn't resume from {}: {}", path, e);
+					std::process::exit(1);
+				}
+			}
+		}
+		Option::None => match &config.inputs_from {
+			Option::Some(path) => {
+				let replayed = corpus::load(path).and_then(|corpus| BenchmarkManager::replay(config.clone(), corpus));
+				match replayed {
+					Ok(manager) => {
+						println!("replaying {} (seed {}, {} tests per input and size)", path, manager.config.seed,
+							manager.config.n_tests);
+						manager
+					}
+					Err(e) => {
+						eprintln!("error: couldn't replay {}", e);
+						std::process::exit(1);
+					}
+				}
+			}
+			Option::None => match BenchmarkManager::with_datasets(config) {
+				Ok(manager) => manager,
+				Err(e) => {
+					eprintln!("error: {}", e);
+					std::process::exit(1);
+				}
+			}
+		}
+	};
+	println!("profile: {}", manager.config.profile.name());
+	for source in &manager.sources {
+		if let inputs::Source::Dataset { name, values, sampling } = source {
+			println!("dataset {}: {} values, {} sampling", name, utils::commafy(values.len()), sampling.name());
+			if !manager.test_sizes.iter().any(|size| source.has_size(*size)) {
+				eprintln!("warning: {} is smaller than every test size, nothing will run on it", name);
+			}
+		}
+	}
+	let n_workers = manager.config.n_workers;
+	if let Err(e) = manager.validate_selection() {
+		eprintln!("error: {}", e);
+		std::process::exit(1);
+	}
+	if let Option::Some(path) = &manager.config.dump_inputs {
+		match manager.dump_inputs(path) {
+			Ok(n) => println!("wrote {} test vectors to {}", utils::commafy(n), path),
+			Err(e) => {
+				eprintln!("error: couldn't write {}: {}", path, e);
+				std::process::exit(1);
+			}
+		}
+	}
 	let start = Instant::now();
-	if *N_WORKERS < 2 {
+	if manager.config.job_timeout.is_some() {
+		manager.run_benchmarks_subprocess();
+	} else if n_workers < 2 {
 		manager.run_benchmarks_single_threaded();
 	} else {
 		manager.run_benchmarks();
 	}
-	let runtime = start.elapsed();
+	let runtime = manager.previous_runtime + start.elapsed();
+	(manager, runtime)
+}
 
-	println!("Bubble sorts:");
-	manager.print(|n, _| (n.contains("bubble") || n.contains("cocktail"))
-						&& !n.contains("selection"));
-	println!();
+fn check(config: cli::Config, baseline_path: &str) {
+	let baseline = read_samples(baseline_path);
+	println!("checking against {} (profile: {}, seed: {}, cpu: {})", baseline_path,
+		baseline.metadata.profile, baseline.metadata.seed, baseline.metadata.cpu);
+	let cpu = utils::cpu_model();
+	if baseline.metadata.cpu != cpu {
+		eprintln!("warning: the baseline was recorded on a different cpu ({})", cpu);
+	}
+	let alpha = config.alpha;
+	let max_regression = config.max_regression;
+	let outlier_coefficient = config.outlier_coefficient;
+	let (mut manager, runtime) = run(config);
+	println!("\nRuntime: {}\n", utils::duration_to_human(runtime));
+	let metadata = manager.metadata(runtime);
+	write_outputs(&mut manager, &metadata);
+	let current = samples::SampleFile { metadata, cells: manager.sample_cells(), jobs: Vec::new() };
+	let comparisons = compare::compare_cells(&baseline.cells, &current.cells, outlier_coefficient);
+	if comparisons.is_empty() {
+		eprintln!("error: no cells in common with {}, check the selection and sizes", baseline_path);
+		std::process::exit(1);
+	}
+	compare::print(baseline_path, &baseline, "this run", &current, &comparisons, alpha);
+	// failed cells aren't in the comparison, but they still fail the check
+	if !manager.failures.is_empty() {
+		println!();
+		print_failures(&manager);
+	}
+	let regressions: Vec<&compare::CellComparison> =
+		comparisons.iter()
+				   .filter(|c| c.is_regression(max_regression, alpha))
+				   .collect();
+	if regressions.is_empty() {
+		println!("\nno regressions (> {}% slower with p < {})", max_regression * 100.0, alpha);
+		if !manager.failures.is_empty() {
+			std::process::exit(1);
+		}
+		return;
+	}
+	println!("\n{} regression(s) (> {}% slower with p < {}):", regressions.len(),
+		max_regression * 100.0, alpha);
+	for c in regressions {
+		println!("  {} on {} input at size {}: {:+.1}% (p = {:.4})", c.algorithm, c.input(),
+			utils::commafy(c.size), c.change * 100.0, c.p);
+	}
+	std::process::exit(1);
+}
 
-	println!("Insertion sorts:");
-	manager.print(|n, _| n.contains("insertion"));
-	println!();
+fn compare_files(config: cli::Config, path_a: &str, path_b: &str) {
+	if let Err(e) = BenchmarkManager::validate_groups(&config) {
+		eprintln!("error: {}", e);
+		std::process::exit(1);
+	}
+	let a = read_samples(path_a);
+	let b = read_samples(path_b);
+	let comparisons: Vec<compare::CellComparison> =
+		compare::compare_cells(&a.cells, &b.cells, config.outlier_coefficient)
+			.into_iter()
+			.filter(|c| BenchmarkManager::is_selected(&config, &c.algorithm))
+			.collect();
+	if comparisons.is_empty() {
+		eprintln!("error: {} and {} have no selected cells in common", path_a, path_b);
+		std::process::exit(1);
+	}
+	compare::print(path_a, &a, path_b, &b, &comparisons, config.alpha);
+}
 
-	println!("Insertion and selection sorts:");
-	manager.print(|n, _| n.contains("insertion")
-						|| n.contains("selection")
-						|| n.contains("cocktail"));
+fn run_sweep(config: cli::Config) {
+	if let Err(e) = BenchmarkManager::validate_groups(&config) {
+		eprintln!("error: {}", e);
+		std::process::exit(1);
+	}
+	let algorithms = sweep::selection(&config);
+	if algorithms.is_empty() {
+		eprintln!("error: no algorithms with a threshold matched the selection (available: {})",
+			sweep::SWEEPABLE.iter().map(|a| a.0).collect::<Vec<&str>>().join(", "));
+		std::process::exit(1);
+	}
+	println!("profile: {}", config.profile.name());
+	let start = Instant::now();
+	let sweeps = sweep::run(&config, &algorithms);
 	println!();
+	sweep::print(&config, &sweeps);
+	println!("Runtime: {}", utils::duration_to_human(start.elapsed()));
+}
 
-	println!("Shell sorts:");
-	manager.print(|n, _| n.contains("shellsort") || n.contains("insertionsort"));
+fn run_gaps(config: cli::Config) {
+	if let Err(e) = BenchmarkManager::validate_groups(&config) {
+		eprintln!("error: {}", e);
+		std::process::exit(1);
+	}
+	let mut sequences = gaps::selection(&config);
+	if let Option::Some(path) = &config.gap_file {
+		match gaps::load_sequences(path) {
+			Ok(file_sequences) => sequences.extend(file_sequences),
+			Err(e) => {
+				eprintln!("error: {}", e);
+				std::process::exit(1);
+			}
+		}
+	}
+	if sequences.is_empty() {
+		eprintln!("error: no shellsorts matched the selection and no --gap-file was given (available: {})",
+			gaps::builtin().iter().map(|b| b.0).collect::<Vec<&str>>().join(", "));
+		std::process::exit(1);
+	}
+	println!("profile: {}", config.profile.name());
+	let start = Instant::now();
+	let explorations = gaps::run(&config, &sequences);
 	println!();
+	gaps::print(&config, &sequences, &explorations);
+	println!("Runtime: {}", utils::duration_to_human(start.elapsed()));
+}
 
-	println!("Merge sorts:");
-	manager.print(|n, _| n.contains("mergesort"));
+fn run_gap_search(config: cli::Config) {
+	println!("profile: {}", config.profile.name());
+	let start = Instant::now();
+	let test_vectors = gap_search::test_vectors(&config);
+	let (sequence, _) = gap_search::search(&config, &test_vectors);
 	println!();
+	gap_search::print(&config, &test_vectors, &sequence);
+	println!("Runtime: {}", utils::duration_to_human(start.elapsed()));
+}
 
-	println!("Heap sorts:");
-	manager.print(|n, _| n.contains("heapsort"));
+fn run_comparators(config: cli::Config) {
+	let families = comparators::selection(&config);
+	if families.is_empty() {
+		eprintln!("error: no algorithm families matched the selection (available: {})",
+			(0..comparators::FAMILIES.len()).map(comparators::name).collect::<Vec<String>>().join(", "));
+		std::process::exit(1);
+	}
+	println!("profile: {}", config.profile.name());
+	let start = Instant::now();
+	let runs = comparators::run(&config, &families);
 	println!();
+	comparators::print(&config, &families, &runs);
+	println!("Runtime: {}", utils::duration_to_human(start.elapsed()));
+}
 
-	println!("Quick sorts:");
-	manager.print(|n, _| n.contains("quicksort"));
-	println!();
+fn main() {
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	let config = match cli::parse_args(&args) {
+		Ok(cli::Command::Run(config)) => config,
+		Ok(cli::Command::List(config)) => {
+			BenchmarkManager::new(config).list();
+			return;
+		}
+		Ok(cli::Command::Analyze(config, path)) => {
+			analyze(config, &path);
+			return;
+		}
+		Ok(cli::Command::Compare(config, path_a, path_b)) => {
+			compare_files(config, &path_a, &path_b);
+			return;
+		}
+		Ok(cli::Command::Check(config, baseline)) => {
+			check(config, &baseline);
+			return;
+		}
+		Ok(cli::Command::Sweep(config)) => {
+			run_sweep(config);
+			return;
+		}
+		Ok(cli::Command::Gaps(config)) => {
+			run_gaps(config);
+			return;
+		}
+		Ok(cli::Command::GapSearch(config)) => {
+			run_gap_search(config);
+			return;
+		}
+		Ok(cli::Command::Comparators(config)) => {
+			run_comparators(config);
+			return;
+		}
+		Ok(cli::Command::Help) => {
+			println!("{}", cli::USAGE);
+			return;
+		}
+		Err(e) => {
+			eprintln!("error: {}\n\n{}", e, cli::USAGE);
+			std::process::exit(1);
+		}
+	};
+	let (mut manager, runtime) = run(config);
 
-	println!("Radix sort:");
-	manager.print(|n, _| n.contains("radix") || n.contains("rustsort"));
-	println!();
+	// saved before anything else, so a panic in the reports below doesn't lose the run
+	let metadata = manager.metadata(runtime);
+	write_outputs(&mut manager, &metadata);
 
-	println!("Totals:");
-	manager.print(|n, _| !n.contains("radix"));
+	print_tables(&mut manager);
+
+	print_failures(&manager);
+
+	if manager.config.operations {
+		print_operations(&manager);
+	}
+
+	if manager.config.complexity {
+		print_complexity(&manager);
+	}
 
 	println!("\nRuntime: {}", utils::duration_to_human(runtime));
 
@@ -36,7 +36,8 @@ pub fn btreesort<T: Ord + Copy>(slice: &mut [T]) {
 pub fn weird<T: Ord + Copy>(slice: &mut [T]) {
 	// Sort chunks of size sqrt(N) using an O(N^2) algorithm.
 	// This has O(N^1.5) time complexity.
-	let chunk_size = (slice.len() as f64).sqrt() as usize;
+	// at least 1 so empty slices don't end up with empty chunks
+	let chunk_size = std::cmp::max((slice.len() as f64).sqrt() as usize, 1);
 	for chunk in slice.chunks_mut(chunk_size) {
 		insertionsort(chunk);
 	}
@@ -120,6 +121,11 @@ pub fn selectionsort_minmax<T: Ord + Copy>(array: &mut [T]) {
 
 const MIN_COUNT: usize = 5;
 pub fn selectionsort_manymins<C: Ord + Copy>(array: &mut [C]) {
+	// not enough elements for a full set of mins
+	if array.len() < MIN_COUNT {
+		selectionsort(array);
+		return;
+	}
 	unsafe {
 		for i in (0..(array.len() - MIN_COUNT)).step_by(MIN_COUNT) {
 			let mut mins: [(C, usize); MIN_COUNT] = std::mem::MaybeUninit::uninit().assume_init();
@@ -205,7 +211,8 @@ fn merge_to_buffer<T: Ord + Copy>(array: &mut [T], start: usize, split: usize, e
 		let mut j = split;
 		let mut k = start;
 		while i < split && j < end {
-			if array.get_unchecked(i) < array.get_unchecked(j) {
+			// ties are taken from the left to keep the sort stable
+			if array.get_unchecked(i) <= array.get_unchecked(j) {
 				*buffer.get_unchecked_mut(k) = *array.get_unchecked(i);
 				i += 1;
 			} else {
@@ -230,6 +237,10 @@ fn merge_to_buffer<T: Ord + Copy>(array: &mut [T], start: usize, split: usize, e
 }
 
 pub fn mergesort_adaptive<T: Ord + Copy>(array: &mut [T]) {
+	// the buffer is filled with copies of the first element
+	if array.len() <= 1 {
+		return;
+	}
 	unsafe {
 		let mut start = 0;
 		let mut end = 1;
@@ -237,7 +248,8 @@ pub fn mergesort_adaptive<T: Ord + Copy>(array: &mut [T]) {
 		let mut merge_queue: VecDeque<usize> = VecDeque::new();
 		while end < array.len() {
 			if end - start == 1 {
-				sorted = array.get_unchecked(start) < array.get_unchecked(end);
+				// descending runs have to be strictly descending, reversing equal elements would swap them
+				sorted = array.get_unchecked(start) <= array.get_unchecked(end);
 			} else if (array.get_unchecked(end - 1) <= array.get_unchecked(end)) != sorted { // Keeps the algorithm stable.
 				if !sorted {
 					reverse(&mut array[start..end]);
@@ -293,6 +305,10 @@ pub fn mergesort_adaptive<T: Ord + Copy>(array: &mut [T]) {
 }
 
 pub fn mergesort_double_hybrid<T: Ord + Copy>(array: &mut [T]) {
+	// the buffer is filled with copies of the first element
+	if array.len() <= 1 {
+		return;
+	}
 	unsafe {
 		let mut merge_queue: VecDeque<usize> = VecDeque::new();
 		let mut i = 0;
@@ -0,0 +1,119 @@
+// operation counting (--operations)
+// wall-clock time doesn't say why one algorithm beats another, counting comparisons on the same
+// inputs does (e.g. bottom-up heapsort does far fewer comparisons than top-down, so if the two
+// run equally fast the comparisons aren't what's costing time)
+// comparisons are counted for every generic algorithm. copies of Copy types are plain memcpys that
+// can't be hooked into, so moves and swaps are only counted for the move-based variants (*_move), which
+// report them through unchecked_tools::Moves. their *_move_counted instantiations pass CountMoves
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+
+use crate::elements::Element;
+use crate::unchecked_tools::Moves;
+use crate::utils;
+
+thread_local! {
+	static COMPARISONS: Cell<u64> = const { Cell::new(0) };
+	static MOVES: Cell<u64> = const { Cell::new(0) };
+	static SWAPS: Cell<u64> = const { Cell::new(0) };
+}
+
+fn count_comparison() {
+	COMPARISONS.with(|c| c.set(c.get() + 1));
+}
+
+// what the *_move_counted sorts report moves and swaps to
+pub struct CountMoves;
+
+impl Moves for CountMoves {
+	fn moved(&mut self, n: usize) {
+		MOVES.with(|c| c.set(c.get() + n as u64));
+	}
+	fn swapped(&mut self, n: usize) {
+		SWAPS.with(|c| c.set(c.get() + n as u64));
+	}
+}
+
+// what one sort did, moves and swaps stay 0 for sorts that don't report them
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Counts {
+	pub comparisons: u64,
+	pub moves: u64,
+	pub swaps: u64
+}
+
+// an i32 that counts every comparison made with it (== included)
+#[derive(Clone, Copy, Debug)]
+pub struct Counted(pub i32);
+
+impl PartialEq for Counted {
+	fn eq(&self, other: &Self) -> bool {
+		count_comparison();
+		self.0 == other.0
+	}
+}
+
+impl Eq for Counted {}
+
+impl PartialOrd for Counted {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Option::Some(self.cmp(other))
+	}
+}
+
+impl Ord for Counted {
+	fn cmp(&self, other: &Self) -> Ordering {
+		count_comparison();
+		self.0.cmp(&other.0)
+	}
+}
+
+impl Element for Counted {
+	fn from_key(key: i32) -> Self {
+		Counted(key)
+	}
+}
+
+// sorts the keys and returns what the sort did
+pub fn count(sort: impl FnOnce(&mut [Counted]), keys: &[i32]) -> Counts {
+	let mut array: Vec<Counted> = keys.iter().map(|key| Counted(*key)).collect();
+	for counter in &[&COMPARISONS, &MOVES, &SWAPS] {
+		counter.with(|c| c.set(0));
+	}
+	sort(&mut array);
+	let counts = Counts {
+		comparisons: COMPARISONS.with(|c| c.get()),
+		moves: MOVES.with(|c| c.get()),
+		swaps: SWAPS.with(|c| c.get())
+	};
+	// checking the result compares too, so it's done after reading the counters
+	utils::verify_sorted(&array);
+	counts
+}
+
+// mean counts over several sorts
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Means {
+	pub comparisons: f64,
+	pub moves: f64,
+	pub swaps: f64
+}
+
+pub fn mean(counts: &[Counts]) -> Means {
+	let n = counts.len() as f64;
+	Means {
+		comparisons: counts.iter().map(|c| c.comparisons).sum::<u64>() as f64 / n,
+		moves: counts.iter().map(|c| c.moves).sum::<u64>() as f64 / n,
+		swaps: counts.iter().map(|c| c.swaps).sum::<u64>() as f64 / n
+	}
+}
+
+// comparisons relative to n log2 n, the lower bound for comparison sorts up to a constant
+pub fn per_n_log_n(comparisons: f64, size: usize) -> f64 {
+	let n = size as f64;
+	if size < 2 {
+		return 0.0;
+	}
+	comparisons / (n * n.log2())
+}
@@ -0,0 +1,250 @@
+// the algorithms that get benchmarked and what's known about them
+// table grouping, --group, size limits, and the complexity checks all work from this metadata
+
+use lazy_static::lazy_static;
+
+use crate::algos;
+use crate::complexity::Model;
+use crate::elements;
+use crate::odd_algos;
+use crate::operations;
+use crate::sorts;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Family {
+	Bubble,
+	Selection,
+	Insertion,
+	Shell,
+	Merge,
+	Heap,
+	Quick,
+	// quicksort falling back to heapsort
+	Intro,
+	Tim,
+	Radix,
+	// the standard library sorts (rust and c++)
+	Library,
+	// sorts by building a search tree
+	Tree,
+	Other
+}
+
+impl Family {
+	pub const ALL: &'static [Family] = &[
+		Family::Bubble, Family::Selection, Family::Insertion, Family::Shell, Family::Merge, Family::Heap,
+		Family::Quick, Family::Intro, Family::Tim, Family::Radix, Family::Library, Family::Tree, Family::Other
+	];
+	// what --group calls the family
+	pub fn name(&self) -> &'static str {
+		match self {
+			Family::Bubble => "bubblesort",
+			Family::Selection => "selectionsort",
+			Family::Insertion => "insertionsort",
+			Family::Shell => "shellsort",
+			Family::Merge => "mergesort",
+			Family::Heap => "heapsort",
+			Family::Quick => "quicksort",
+			Family::Intro => "introsort",
+			Family::Tim => "timsort",
+			Family::Radix => "radixsort",
+			Family::Library => "library",
+			Family::Tree => "treesort",
+			Family::Other => "other"
+		}
+	}
+	pub fn from_name(name: &str) -> Option<Family> {
+		Family::ALL.iter().copied().find(|f| f.name() == name)
+	}
+}
+
+// worst case time complexity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Complexity {
+	Linear,
+	Linearithmic,
+	FourThirds,
+	ThreeHalves,
+	Quadratic
+}
+
+impl Complexity {
+	pub fn label(&self) -> &'static str {
+		match self {
+			Complexity::Linear => "O(n)",
+			Complexity::Linearithmic => "O(n log n)",
+			Complexity::FourThirds => "O(n^(4/3))",
+			Complexity::ThreeHalves => "O(n^(3/2))",
+			Complexity::Quadratic => "O(n^2)"
+		}
+	}
+	pub fn model(&self) -> Model {
+		let (exponent, log) = match self {
+			Complexity::Linear => (1.0, false),
+			Complexity::Linearithmic => (1.0, true),
+			Complexity::FourThirds => (4.0 / 3.0, false),
+			Complexity::ThreeHalves => (1.5, false),
+			Complexity::Quadratic => (2.0, false)
+		};
+		Model { exponent, log }
+	}
+	// largest size worth running
+	// don't want to run bubblesort on a million items (or 100,000 items for that matter)
+	pub fn size_limit(&self) -> usize {
+		match self {
+			Complexity::Quadratic => 10_000,
+			_ => usize::MAX
+		}
+	}
+}
+
+// extra memory used on top of the array
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Memory {
+	Constant,
+	// recursion
+	Logarithmic,
+	// buffers
+	Linear
+}
+
+impl Memory {
+	pub fn label(&self) -> &'static str {
+		match self {
+			Memory::Constant => "O(1)",
+			Memory::Logarithmic => "O(log n)",
+			Memory::Linear => "O(n)"
+		}
+	}
+}
+
+#[derive(Clone, Copy)]
+pub struct Algorithm {
+	// path, e.g. algos::heapsort_top_down
+	pub name: &'static str,
+	pub sorts: elements::Sorts,
+	pub family: Family,
+	pub complexity: Complexity,
+	pub memory: Memory,
+	// equal elements keep their order
+	pub stable: bool,
+	// faster on nearly sorted input
+	pub adaptive: bool,
+	// odd algorithms and unimportant variations, listed after the rest in the tables
+	pub secondary: bool,
+	// --operations counts its moves and swaps, not just its comparisons
+	pub counts_moves: bool
+}
+
+impl Algorithm {
+	pub fn stable(self) -> Algorithm {
+		Algorithm { stable: true, ..self }
+	}
+	pub fn adaptive(self) -> Algorithm {
+		Algorithm { adaptive: true, ..self }
+	}
+	pub fn secondary(self) -> Algorithm {
+		Algorithm { secondary: true, ..self }
+	}
+	// the move-based variants have an instantiation for --operations that reports moves and swaps
+	pub fn counted(self, sort: fn(&mut [operations::Counted])) -> Algorithm {
+		let sorts = elements::Sorts { counted: Option::Some(sort), ..self.sorts };
+		Algorithm { sorts, counts_moves: true, ..self }
+	}
+	// plain quicksort partitioning is quadratic on sorted and other degenerate inputs
+	pub fn size_limit(&self, degenerate_input: bool) -> usize {
+		if self.family == Family::Quick && degenerate_input {
+			Complexity::Quadratic.size_limit()
+		} else {
+			self.complexity.size_limit()
+		}
+	}
+}
+
+// shorthand for registry entries
+// input: function path, family, complexity, memory, and i32 for algorithms that aren't generic
+// output: an Algorithm that's unstable, not adaptive, not secondary, and only has its comparisons counted
+macro_rules! algorithm {
+	(@entry $name:expr, $sorts:expr, $family:ident, $complexity:ident, $memory:ident) => {
+		Algorithm {
+			name: $name,
+			sorts: $sorts,
+			family: Family::$family,
+			complexity: Complexity::$complexity,
+			memory: Memory::$memory,
+			stable: false,
+			adaptive: false,
+			secondary: false,
+			counts_moves: false
+		}
+	};
+	($f:ident $(:: $g:ident)*, $family:ident, $complexity:ident, $memory:ident, i32) => {
+		algorithm!(@entry concat!(stringify!($f) $(, "::", stringify!($g))*),
+				   elements::Sorts::i32_only($f $(:: $g)*), $family, $complexity, $memory)
+	};
+	($f:ident $(:: $g:ident)*, $family:ident, $complexity:ident, $memory:ident) => {
+		algorithm!(@entry concat!(stringify!($f) $(, "::", stringify!($g))*),
+				   sorts!($f $(:: $g)*), $family, $complexity, $memory)
+	};
+}
+
+lazy_static! {
+	pub static ref ALGORITHMS: Vec<Algorithm> = vec![
+		algorithm!(algos::bubblesort,               Bubble,    Quadratic,    Constant).stable().adaptive(),
+		algorithm!(algos::cocktail_shaker,          Bubble,    Quadratic,    Constant).stable().adaptive(),
+		algorithm!(algos::selectionsort,            Selection, Quadratic,    Constant),
+		algorithm!(algos::insertionsort,            Insertion, Quadratic,    Constant).stable().adaptive(),
+		algorithm!(algos::insertionsort_c,          Insertion, Quadratic,    Constant, i32).stable().adaptive(),
+		algorithm!(algos::shellsort_knuth,          Shell,     FourThirds,   Constant).adaptive(),
+		algorithm!(algos::shellsort_sedgewick82,    Shell,     FourThirds,   Constant).adaptive(),
+		algorithm!(algos::shellsort_sedgewick86,    Shell,     FourThirds,   Constant).adaptive(),
+		algorithm!(algos::shellsort_gonnet_baeza,   Shell,     FourThirds,   Constant).adaptive(),
+		algorithm!(algos::shellsort_tokuda,         Shell,     FourThirds,   Constant).adaptive(),
+		algorithm!(algos::shellsort_ciura,          Shell,     FourThirds,   Constant).adaptive(),
+		algorithm!(algos::mergesort,                Merge,     Linearithmic, Linear).stable(),
+		algorithm!(algos::mergesort_hybrid,         Merge,     Linearithmic, Linear).stable(),
+		algorithm!(algos::mergesort_in_place_naive, Merge,     Quadratic,    Logarithmic).stable().adaptive(),
+		algorithm!(algos::mergesort_in_place,       Merge,     Linearithmic, Logarithmic),
+		algorithm!(algos::heapsort_top_down,        Heap,      Linearithmic, Constant),
+		algorithm!(algos::heapsort_bottom_up,       Heap,      Linearithmic, Constant),
+		algorithm!(algos::heapsort_bottom_up_optimized, Heap,  Linearithmic, Constant),
+		algorithm!(algos::quicksort_end,            Quick,     Linearithmic, Logarithmic),
+		algorithm!(algos::quicksort_random,         Quick,     Linearithmic, Logarithmic),
+		algorithm!(algos::quicksort_hybrid,         Quick,     Linearithmic, Logarithmic),
+		algorithm!(algos::introsort,                Intro,     Linearithmic, Logarithmic),
+		algorithm!(algos::timsort,                  Tim,       Linearithmic, Linear).stable().adaptive(),
+		algorithm!(algos::radixsort,                Radix,     Linear,       Linear, i32).stable(),
+		algorithm!(algos::rustsort,                 Library,   Linearithmic, Linear).stable().adaptive(),
+		algorithm!(algos::rustsort_unsable,         Library,   Linearithmic, Logarithmic).adaptive(),
+		algorithm!(algos::cpp_std_sort,             Library,   Linearithmic, Logarithmic, i32),
+		// odd algos and unimportant variations will be tacked on at the end
+		// move-based variants that also work for types that aren't Copy
+		algorithm!(algos::insertionsort_move,       Insertion, Quadratic,    Constant).stable().adaptive().secondary()
+			.counted(algos::insertionsort_move_counted),
+		algorithm!(algos::mergesort_move,           Merge,     Linearithmic, Linear).stable().secondary()
+			.counted(algos::mergesort_move_counted),
+		algorithm!(algos::heapsort_move,            Heap,      Linearithmic, Constant).secondary()
+			.counted(algos::heapsort_move_counted),
+		algorithm!(algos::quicksort_move,           Quick,     Linearithmic, Logarithmic).secondary()
+			.counted(algos::quicksort_move_counted),
+		algorithm!(algos::introsort_move,           Intro,     Linearithmic, Logarithmic).secondary()
+			.counted(algos::introsort_move_counted),
+		algorithm!(algos::timsort_move,             Tim,       Linearithmic, Linear).stable().adaptive().secondary()
+			.counted(algos::timsort_move_counted),
+		algorithm!(algos::insertionsort_boundary_checked, Insertion, Quadratic, Constant).stable().adaptive()
+			.secondary(),
+		algorithm!(odd_algos::selectionsort_cocktail, Selection, Quadratic,  Constant).secondary(),
+		algorithm!(odd_algos::selectionsort_minmax, Selection, Quadratic,    Constant).secondary(),
+		algorithm!(odd_algos::selectionsort_manymins, Selection, Quadratic,  Constant).secondary(),
+		algorithm!(odd_algos::shellsort_alternative_ciura, Shell, FourThirds, Constant).adaptive().secondary(),
+		algorithm!(odd_algos::mergesort_adaptive,   Merge,     Linearithmic, Linear).stable().adaptive().secondary(),
+		algorithm!(odd_algos::mergesort_double_hybrid, Merge,  Linearithmic, Linear).stable().secondary(),
+		// equal elements are all overwritten with the first one, so not stable
+		algorithm!(odd_algos::btreesort,            Tree,      Linearithmic, Linear).secondary(),
+		algorithm!(odd_algos::weird,                Other,     ThreeHalves,  Linear).secondary()
+	];
+}
+
+pub fn find(name: &str) -> Option<&'static Algorithm> {
+	ALGORITHMS.iter().find(|a| a.name == name)
+}
@@ -0,0 +1,164 @@
+// raw per-run timings, written with --output samples and read back by the analyze subcommand
+// every timing is kept (outliers included) so the statistics can be recomputed later with
+// different settings without repeating a multi-hour run
+//
+// format: utf-8 text, one record per line, fields separated by tabs
+//   sorting-benchmarks samples 4
+//   meta	<key>	<value>
+//   cell	<algorithm>	<complexity>	<distribution>	<element>	<size>	<ns> <ns> <ns> ...
+//   job	<algorithm>	<distribution>	<element>	<size>	<test index>
+// a cell where a job failed has three more fields after the samples, <status> (failed or timed_out),
+// <test index>, and <message>. its samples are whatever finished before that
+// job records only appear in checkpoints (--checkpoint), they are the jobs that haven't run yet in
+// the order they'll be run (last first, the job list is used as a stack)
+// version 1 files predate input distributions, they have no distribution fields and are read as
+// uniform. version 1 and 2 files predate element types and are read as i32. version 3 and older files
+// have no failed cells
+
+use std::io::{self, BufRead, Write};
+
+use crate::export::{CellFailure, RunMetadata};
+
+const HEADER: &str = "sorting-benchmarks samples 4";
+const HEADER_V3: &str = "sorting-benchmarks samples 3";
+const HEADER_V2: &str = "sorting-benchmarks samples 2";
+const HEADER_V1: &str = "sorting-benchmarks samples 1";
+const V1_DISTRIBUTION: &str = "uniform";
+const V2_ELEMENT: &str = "i32";
+
+pub struct SampleCell {
+	pub algorithm: String,
+	pub complexity: String,
+	pub distribution: String,
+	pub element: String,
+	pub size: usize,
+	pub samples: Vec<u64>,
+	pub failure: Option<CellFailure>
+}
+
+pub struct PendingJob {
+	pub algorithm: String,
+	pub distribution: String,
+	pub element: String,
+	pub size: usize,
+	pub test_i: usize
+}
+
+pub struct SampleFile {
+	pub metadata: RunMetadata,
+	pub cells: Vec<SampleCell>,
+	// empty unless the file is a checkpoint
+	pub jobs: Vec<PendingJob>
+}
+
+impl SampleFile {
+	pub fn sizes(&self) -> Vec<usize> {
+		let mut sizes: Vec<usize> = self.cells.iter().map(|c| c.size).collect();
+		sizes.sort_unstable();
+		sizes.dedup();
+		sizes
+	}
+}
+
+// tabs and newlines would break the format, metadata like the cpu name is free-form
+fn sanitize(s: &str) -> String {
+	s.replace(['\t', '\n', '\r'], " ")
+}
+
+pub fn write(out: &mut impl Write, metadata: &RunMetadata, cells: &[SampleCell]) -> io::Result<()> {
+	writeln!(out, "{}", HEADER)?;
+	for (key, value) in metadata.to_pairs() {
+		writeln!(out, "meta\t{}\t{}", key, sanitize(&value))?;
+	}
+	for cell in cells {
+		let samples: Vec<String> = cell.samples.iter().map(|s| s.to_string()).collect();
+		write!(out, "cell\t{}\t{}\t{}\t{}\t{}\t{}",
+			sanitize(&cell.algorithm), sanitize(&cell.complexity), sanitize(&cell.distribution),
+			sanitize(&cell.element), cell.size, samples.join(" "))?;
+		if let Option::Some(failure) = &cell.failure {
+			write!(out, "\t{}\t{}\t{}", failure.status(), failure.test_i, sanitize(&failure.message))?;
+		}
+		writeln!(out)?;
+	}
+	Ok(())
+}
+
+pub fn write_checkpoint(out: &mut impl Write, metadata: &RunMetadata, cells: &[SampleCell],
+						jobs: &[PendingJob]) -> io::Result<()> {
+	write(out, metadata, cells)?;
+	for job in jobs {
+		writeln!(out, "job\t{}\t{}\t{}\t{}\t{}", sanitize(&job.algorithm), sanitize(&job.distribution),
+			sanitize(&job.element), job.size, job.test_i)?;
+	}
+	Ok(())
+}
+
+pub fn read(input: impl BufRead) -> Result<SampleFile, String> {
+	let mut lines = input.lines();
+	let version = match lines.next() {
+		Option::Some(Ok(line)) if line == HEADER => 4,
+		Option::Some(Ok(line)) if line == HEADER_V3 => 3,
+		Option::Some(Ok(line)) if line == HEADER_V2 => 2,
+		Option::Some(Ok(line)) if line == HEADER_V1 => 1,
+		_ => return Err(String::from("not a samples file"))
+	};
+	let mut pairs = Vec::new();
+	let mut cells = Vec::new();
+	let mut jobs = Vec::new();
+	for (i, line) in lines.enumerate() {
+		let line = line.map_err(|e| e.to_string())?;
+		// + 2 for the header and 1-based line numbers
+		let bad_line = || format!("malformed line {}", i + 2);
+		let mut fields: Vec<&str> = line.split('\t').collect();
+		if version < 3 && fields.len() > 2 && (fields[0] == "cell" || fields[0] == "job") {
+			// distribution goes after the complexity for cells and after the algorithm for jobs,
+			// the element right after the distribution
+			let at = if fields[0] == "cell" {3} else {2};
+			if version == 1 {
+				fields.insert(at, V1_DISTRIBUTION);
+			}
+			fields.insert(at + 1, V2_ELEMENT);
+		}
+		match fields[..] {
+			["meta", key, value] => pairs.push((String::from(key), String::from(value))),
+			["cell", algorithm, complexity, distribution, element, size, samples, ref failure @ ..]
+				if failure.is_empty() || (version >= 4 && failure.len() == 3) => {
+				let size = size.parse().map_err(|_| bad_line())?;
+				let failure = match failure {
+					[status, test_i, message] => {
+						let test_i = test_i.parse().map_err(|_| bad_line())?;
+						Option::Some(CellFailure::from_status(status, test_i, message).ok_or_else(bad_line)?)
+					}
+					_ => Option::None
+				};
+				let samples = samples.split_whitespace()
+									 .map(|s| s.parse::<u64>())
+									 .collect::<Result<Vec<u64>, _>>()
+									 .map_err(|_| bad_line())?;
+				cells.push(SampleCell {
+					algorithm: String::from(algorithm),
+					complexity: String::from(complexity),
+					distribution: String::from(distribution),
+					element: String::from(element),
+					size,
+					samples,
+					failure
+				});
+			}
+			["job", algorithm, distribution, element, size, test_i] => jobs.push(PendingJob {
+				algorithm: String::from(algorithm),
+				distribution: String::from(distribution),
+				element: String::from(element),
+				size: size.parse().map_err(|_| bad_line())?,
+				test_i: test_i.parse().map_err(|_| bad_line())?
+			}),
+			[""] => (),
+			_ => return Err(bad_line())
+		}
+	}
+	Ok(SampleFile {
+		metadata: RunMetadata::from_pairs(&pairs)?,
+		cells,
+		jobs
+	})
+}
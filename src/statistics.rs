@@ -1,3 +1,6 @@
+use rand::Rng;
+use rand::rngs::SmallRng;
+
 // computes the sample standard deviation of a Vec<u64>
 // mean passed as a parameter to take advantage of pre-computed value
 pub fn stdev(array: &[u64], mean: f64) -> f64 {
@@ -50,6 +53,35 @@ pub fn tukey(item: u64, q: &QuartileDescriptor, threshold: f64) -> bool {
 	!(item > q.q3 + threshold * q.iqr || item < q.q1 - threshold * q.iqr)
 }
 
+// bootstrap confidence interval for which group has the smallest mean (e.g. the best value of a
+// parameter)
+// every resample redraws each group with replacement and notes which group came out fastest, the
+// interval is the central `confidence` fraction of those indices, so groups should be ordered by
+// the parameter. empty groups never win
+pub fn bootstrap_argmin(groups: &[Vec<u64>], confidence: f64, resamples: usize, rng: &mut SmallRng)
+	-> (usize, usize) {
+	let mut winners = Vec::with_capacity(resamples);
+	for _ in 0..resamples {
+		let mut min = f64::INFINITY;
+		let mut argmin = 0;
+		for (i, group) in groups.iter().enumerate() {
+			if group.is_empty() {
+				continue;
+			}
+			let sum: u64 = (0..group.len()).map(|_| group[rng.gen_range(0..group.len())]).sum();
+			let mean = sum as f64 / group.len() as f64;
+			if mean < min {
+				min = mean;
+				argmin = i;
+			}
+		}
+		winners.push(argmin);
+	}
+	winners.sort_unstable();
+	let tail = ((1.0 - confidence) / 2.0 * resamples as f64) as usize;
+	(winners[tail], winners[resamples - 1 - tail])
+}
+
 // everything below is for implementing welch's t-test
 // gamma function implementation from the statrs crate
 // https://docs.rs/statrs/0.7.0/src/statrs/function/gamma.rs.html#54-72
@@ -0,0 +1,167 @@
+// subprocess workers (--job-timeout)
+// a thread stuck in an algorithm that went quadratic or never terminates can't be stopped, a process
+// can. each worker is a forked child that reads jobs from one pipe and writes results back on another,
+// and the coordinator kills and replaces any worker that's still busy past the deadline
+// the child is a fork of the coordinator so it already has everything it needs to run a job, only job
+// indices and results go over the pipes
+//
+// job:    4 u64s, the (algorithm, input, size, test) indices
+// result: u8 0 then a u64 time in ns, or u8 1 then a u32 length and a utf-8 panic message
+// everything is native endian, both ends are the same program
+
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::panic;
+use std::time::{Duration, Instant};
+
+use crate::Job;
+
+pub struct Worker {
+	pub pid: libc::pid_t,
+	jobs: File,
+	results: File,
+	// the job the worker is running and when it was sent, none while it's idle
+	pub current: Option<(Job, Instant)>
+}
+
+// (read end, write end)
+fn pipe() -> io::Result<(File, File)> {
+	let mut fds = [0; 2];
+	if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+		return Err(io::Error::last_os_error());
+	}
+	Ok(unsafe { (File::from_raw_fd(fds[0]), File::from_raw_fd(fds[1])) })
+}
+
+// the child's job loop, returns once the coordinator closes the job pipe
+fn serve(mut jobs: File, mut results: File, run: impl Fn(Job) -> Result<u64, String>) {
+	let mut job = [0; 32];
+	while jobs.read_exact(&mut job).is_ok() {
+		let index = |i: usize| u64::from_ne_bytes(job[i * 8..i * 8 + 8].try_into().unwrap()) as usize;
+		let mut message = Vec::new();
+		match run((index(0), index(1), index(2), index(3))) {
+			Ok(time) => {
+				message.push(0);
+				message.extend_from_slice(&time.to_ne_bytes());
+			}
+			Err(panic_message) => {
+				message.push(1);
+				message.extend_from_slice(&(panic_message.len() as u32).to_ne_bytes());
+				message.extend_from_slice(panic_message.as_bytes());
+			}
+		}
+		// a single write so a result is never half sent
+		if results.write_all(&message).is_err() {
+			return;
+		}
+	}
+}
+
+// waits for a child and describes how it ended
+fn reap(pid: libc::pid_t) -> String {
+	let mut status = 0;
+	unsafe { libc::waitpid(pid, &mut status, 0) };
+	if libc::WIFSIGNALED(status) {
+		format!("killed by signal {}", libc::WTERMSIG(status))
+	} else {
+		format!("exited with status {}", libc::WEXITSTATUS(status))
+	}
+}
+
+impl Worker {
+	// forks a worker that runs jobs with run, which shouldn't panic
+	// others are the coordinator's other workers, the child closes its copies of their pipes or they
+	// wouldn't see the coordinator hang up on them
+	pub fn spawn<'a>(others: impl Iterator<Item = &'a Worker>, run: impl Fn(Job) -> Result<u64, String>)
+		-> io::Result<Worker> {
+		let (jobs_rx, jobs_tx) = pipe()?;
+		let (results_rx, results_tx) = pipe()?;
+		// anything still buffered would be printed by both processes
+		io::stdout().flush()?;
+		match unsafe { libc::fork() } {
+			-1 => Err(io::Error::last_os_error()),
+			0 => {
+				for other in others {
+					unsafe {
+						libc::close(other.jobs.as_raw_fd());
+						libc::close(other.results.as_raw_fd());
+					}
+				}
+				drop(jobs_tx);
+				drop(results_rx);
+				// the child must never make it back into the coordinator's code
+				let status = match panic::catch_unwind(panic::AssertUnwindSafe(|| serve(jobs_rx, results_tx, run))) {
+					Ok(()) => 0,
+					Err(_) => 1
+				};
+				unsafe { libc::_exit(status) }
+			}
+			pid => Ok(Worker { pid, jobs: jobs_tx, results: results_rx, current: Option::None })
+		}
+	}
+	pub fn send(&mut self, job: Job) -> io::Result<()> {
+		let mut message = Vec::with_capacity(32);
+		for index in &[job.0, job.1, job.2, job.3] {
+			message.extend_from_slice(&(*index as u64).to_ne_bytes());
+		}
+		self.jobs.write_all(&message)?;
+		self.current = Option::Some((job, Instant::now()));
+		Ok(())
+	}
+	// the result of the current job, blocks until it's there. the worker is idle afterwards
+	// an error means the worker died, reap() says how
+	pub fn receive(&mut self) -> io::Result<Result<u64, String>> {
+		self.current = Option::None;
+		let mut tag = [0; 1];
+		self.results.read_exact(&mut tag)?;
+		if tag[0] == 0 {
+			let mut time = [0; 8];
+			self.results.read_exact(&mut time)?;
+			return Ok(Ok(u64::from_ne_bytes(time)));
+		}
+		let mut len = [0; 4];
+		self.results.read_exact(&mut len)?;
+		let mut message = vec![0; u32::from_ne_bytes(len) as usize];
+		self.results.read_exact(&mut message)?;
+		Ok(Err(String::from_utf8_lossy(&message).into_owned()))
+	}
+	// whether the current job has been running for longer than deadline
+	pub fn overdue(&self, deadline: Duration) -> bool {
+		self.current.is_some_and(|(_, sent)| sent.elapsed() >= deadline)
+	}
+	pub fn kill(self) {
+		unsafe { libc::kill(self.pid, libc::SIGKILL) };
+		reap(self.pid);
+	}
+	// for a worker that died on its own
+	pub fn reap(self) -> String {
+		reap(self.pid)
+	}
+	// hangs up and waits for the worker to exit
+	pub fn finish(self) {
+		let Worker { pid, jobs, .. } = self;
+		drop(jobs);
+		reap(pid);
+	}
+}
+
+// waits until a busy worker has a result (or died) or the timeout passes, returns the indices of the
+// ones that are ready
+pub fn wait(workers: &[Option<Worker>], timeout: Duration) -> io::Result<Vec<usize>> {
+	let busy: Vec<usize> = (0..workers.len()).filter(|i| workers[*i].as_ref().is_some_and(|w| w.current.is_some()))
+											 .collect();
+	let mut fds: Vec<libc::pollfd> = busy.iter().map(|i| libc::pollfd {
+		fd: workers[*i].as_ref().unwrap().results.as_raw_fd(),
+		events: libc::POLLIN,
+		revents: 0
+	}).collect();
+	// rounded up, otherwise the last moments before a deadline would be spent polling in a loop
+	let ms = std::cmp::min(timeout.as_nanos().div_ceil(1_000_000), i32::MAX as u128) as libc::c_int;
+	if unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, ms) } < 0 {
+		let e = io::Error::last_os_error();
+		return if e.kind() == io::ErrorKind::Interrupted { Ok(Vec::new()) } else { Err(e) };
+	}
+	Ok(busy.into_iter().zip(&fds).filter(|(_, fd)| fd.revents != 0).map(|(i, _)| i).collect())
+}
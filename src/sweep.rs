@@ -0,0 +1,265 @@
+// parameter sweeps (sweep subcommand)
+// the hybrid algorithms switch to insertionsort below algos::INSERTIONSORT_THRESHOLD, this
+// benchmarks them over a range of thresholds at every size and estimates the best one
+// the best threshold is the one with the lowest mean runtime, its confidence interval comes from
+// bootstrapping which threshold wins
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use prettytable::*;
+use rand::rngs::SmallRng;
+use rand::{SeedableRng, seq::SliceRandom};
+
+use crate::algos;
+use crate::cli;
+use crate::inputs;
+use crate::statistics;
+use crate::utils;
+use crate::registry;
+use crate::{BenchmarkManager, BenchmarkResult};
+
+type ThresholdSort = fn(&mut [i32], usize);
+
+// algorithms with a threshold parameter
+pub static SWEEPABLE: &[(&str, ThresholdSort)] = &[
+	("algos::quicksort_hybrid", algos::quicksort_hybrid_with_threshold::<i32>),
+	("algos::mergesort_hybrid", algos::mergesort_hybrid_with_threshold::<i32>),
+	("algos::introsort",        algos::introsort_with_threshold::<i32>)
+];
+
+const BOOTSTRAP_RESAMPLES: usize = 1000;
+// matches the 98% confidence intervals in the timing tables
+const CONFIDENCE: f64 = 0.98;
+
+// timings for one (algorithm, distribution, size) across every threshold
+pub struct Sweep {
+	// index into SWEEPABLE
+	pub algorithm: usize,
+	// index into inputs::DISTRIBUTIONS
+	pub distribution: usize,
+	pub size: usize,
+	// raw timings in ns, one list per threshold in config.thresholds
+	pub samples: Vec<Vec<u64>>
+}
+
+// the best threshold for one sweep
+#[derive(Debug, PartialEq)]
+pub struct Optimum {
+	pub threshold: usize,
+	// confidence interval, thresholds the bootstrap picked as fastest
+	pub low: usize,
+	pub high: usize
+}
+
+impl Sweep {
+	pub fn results(&self, outlier_coefficient: f64) -> Vec<Option<BenchmarkResult>> {
+		self.samples.iter().map(|s| BenchmarkResult::from_samples(s, outlier_coefficient)).collect()
+	}
+	// none unless at least two thresholds have enough runs to compare
+	pub fn optimum(&self, thresholds: &[usize], outlier_coefficient: f64, seed: u64) -> Option<Optimum> {
+		let results = self.results(outlier_coefficient);
+		if results.iter().filter(|r| r.is_some()).count() < 2 {
+			return Option::None;
+		}
+		let best = (0..results.len()).filter(|i| results[*i].is_some())
+									 .min_by(|a, b| results[*a].unwrap().mean
+														   .partial_cmp(&results[*b].unwrap().mean)
+														   .unwrap())
+									 .unwrap();
+		// same outlier filtering as the means, thresholds without enough runs can't win
+		let groups: Vec<Vec<u64>> = self.samples.iter().zip(&results).map(|(samples, result)| {
+			if result.is_none() {
+				return Vec::new();
+			}
+			let q = statistics::quartiles(samples);
+			samples.iter().copied().filter(|s| statistics::tukey(*s, &q, outlier_coefficient)).collect()
+		}).collect();
+		let mut rng = SmallRng::seed_from_u64(seed);
+		let (low, high) = statistics::bootstrap_argmin(&groups, CONFIDENCE, BOOTSTRAP_RESAMPLES, &mut rng);
+		Option::Some(Optimum {
+			threshold: thresholds[best],
+			// the bootstrap interval can exclude the observed best when it's close
+			low: thresholds[std::cmp::min(low, best)],
+			high: thresholds[std::cmp::max(high, best)]
+		})
+	}
+}
+
+// selected algorithms that can be swept
+pub fn selection(config: &cli::Config) -> Vec<usize> {
+	(0..SWEEPABLE.len()).filter(|i| BenchmarkManager::is_selected(config, SWEEPABLE[*i].0)).collect()
+}
+
+fn size_limit(algorithm: usize, distribution: usize) -> usize {
+	// same limits as the main benchmark
+	registry::find(SWEEPABLE[algorithm].0).unwrap().size_limit(inputs::DISTRIBUTIONS[distribution].2)
+}
+
+// same test vectors as the main benchmark
+pub fn test_vector(config: &cli::Config, distribution: usize, size: usize, test_i: usize) -> Vec<i32> {
+	let mut test_vector: Vec<i32> = vec![0; size];
+	let mut rng = SmallRng::seed_from_u64(BenchmarkManager::seedgen(config.seed, test_i));
+	(inputs::DISTRIBUTIONS[distribution].1)(&mut test_vector, &mut rng);
+	test_vector
+}
+
+pub fn run_bench(config: &cli::Config, sort: impl FnOnce(&mut [i32]), distribution: usize, size: usize,
+				 test_i: usize) -> u64 {
+	let mut test_vector = test_vector(config, distribution, size, test_i);
+	if !config.test_mode { thread::sleep(Duration::from_millis(10)) };
+	let start = Instant::now();
+	sort(&mut test_vector);
+	let r = start.elapsed().as_nanos() as u64;
+	utils::verify_sorted(&test_vector);
+	r
+}
+
+// runs config.n_tests of every (cell, variant) job in a seeded random order, single-threaded
+// bench(cell, variant, test_i) times one run, status(cell, variant) describes a job for the status line
+// returns the timings indexed [cell][variant]
+pub fn run_jobs(config: &cli::Config, n_cells: usize, n_variants: usize, status: impl Fn(usize, usize) -> String,
+				mut bench: impl FnMut(usize, usize, usize) -> u64) -> Vec<Vec<Vec<u64>>> {
+	// (cell, variant, test) indices, used as a stack
+	let mut jobs: Vec<(usize, usize, usize)> = Vec::new();
+	for cell in 0..n_cells {
+		for variant in 0..n_variants {
+			for test_i in 0..config.n_tests {
+				jobs.push((cell, variant, test_i));
+			}
+		}
+	}
+	let mut rng = SmallRng::seed_from_u64(config.seed);
+	jobs.shuffle(&mut rng);
+	let n_jobs = jobs.len();
+	println!("executing of jobs: {} single-threaded with max size = {}", utils::commafy(n_jobs),
+		utils::commafy(config.max_size));
+	let mut samples = vec![vec![Vec::new(); n_variants]; n_cells];
+	// time spent on each (cell, variant), they stop being scheduled past the runtime limit
+	let mut time_table = vec![vec![0; n_variants]; n_cells];
+	let start = Instant::now();
+	let mut last_update = 0;
+	eprint!("starting...");
+	while let Option::Some((cell, variant, test_i)) = jobs.pop() {
+		if time_table[cell][variant] >= config.runtime_limit {
+			continue;
+		}
+		if start.elapsed().as_millis() as u64 - last_update >= 1_000 {
+			last_update = start.elapsed().as_millis() as u64;
+			eprint!("\x1b[2K\r{}/{} {}", utils::commafy(jobs.len()), utils::commafy(n_jobs), status(cell, variant));
+		}
+		let result = bench(cell, variant, test_i);
+		samples[cell][variant].push(result);
+		time_table[cell][variant] += result;
+	}
+	eprintln!();
+	samples
+}
+
+// benchmarks the algorithms at every threshold, size, and distribution
+pub fn run(config: &cli::Config, algorithms: &[usize]) -> Vec<Sweep> {
+	let distributions: Vec<usize> = config.distributions
+										  .iter()
+										  .map(|d| inputs::find(d).expect("unknown distribution"))
+										  .collect();
+	let mut sweeps = Vec::new();
+	for &algorithm in algorithms {
+		for &distribution in &distributions {
+			for size in config.test_sizes() {
+				if size <= size_limit(algorithm, distribution) {
+					sweeps.push(Sweep { algorithm, distribution, size, samples: Vec::new() });
+				}
+			}
+		}
+	}
+	let samples = run_jobs(config, sweeps.len(), config.thresholds.len(), |sweep_i, threshold_i| {
+		let sweep = &sweeps[sweep_i];
+		format!("{} {} {} threshold {}", SWEEPABLE[sweep.algorithm].0,
+			inputs::DISTRIBUTIONS[sweep.distribution].0, utils::commafy(sweep.size), config.thresholds[threshold_i])
+	}, |sweep_i, threshold_i, test_i| {
+		let sweep = &sweeps[sweep_i];
+		let threshold = config.thresholds[threshold_i];
+		run_bench(config, |array| (SWEEPABLE[sweep.algorithm].1)(array, threshold), sweep.distribution,
+				  sweep.size, test_i)
+	});
+	for (sweep, samples) in sweeps.iter_mut().zip(samples) {
+		sweep.samples = samples;
+	}
+	sweeps
+}
+
+// a table with a row per variant and a column per size, results are indexed [size][variant]
+// marks the fastest variant at each size the same way the timing tables mark the fastest algorithm
+pub fn table(config: &cli::Config, corner: &str, labels: &[String], sizes: &[usize],
+			 results: &mut [Vec<Option<BenchmarkResult>>]) -> Table {
+	for column in results.iter_mut() {
+		let min = column.iter().flatten().copied().min_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap());
+		if let Option::Some(min) = min {
+			for result in column.iter_mut().flatten() {
+				result.update_display(&min, config.alpha, config.diff_threshold);
+			}
+		}
+	}
+	let mut table = Table::new();
+	table.add_row(Row::new(std::iter::once(String::from(corner))
+							.chain(sizes.iter().map(|size| utils::commafy(*size)))
+							.map(|x| Cell::new(&x)).collect()));
+	for (variant, label) in labels.iter().enumerate() {
+		let mut row = vec![Cell::new(label)];
+		for column in results.iter() {
+			match &column[variant] {
+				Option::Some(result) => row.push(Cell::new(&format!("{}", result))),
+				Option::None => row.push(Cell::new("-"))
+			}
+		}
+		table.add_row(Row::new(row));
+	}
+	table
+}
+
+// one table per (algorithm, distribution) with a row per threshold and the best threshold at the
+// bottom
+pub fn print(config: &cli::Config, sweeps: &[Sweep]) {
+	let mut tables: Vec<(usize, usize)> = Vec::new();
+	for sweep in sweeps {
+		if !tables.contains(&(sweep.algorithm, sweep.distribution)) {
+			tables.push((sweep.algorithm, sweep.distribution));
+		}
+	}
+	for (algorithm, distribution) in tables {
+		let columns: Vec<&Sweep> = sweeps.iter()
+										 .filter(|s| s.algorithm == algorithm && s.distribution == distribution)
+										 .collect();
+		let mut results: Vec<Vec<Option<BenchmarkResult>>> =
+			columns.iter().map(|s| s.results(config.outlier_coefficient)).collect();
+		if config.distributions.len() > 1 || inputs::DISTRIBUTIONS[distribution].0 != "uniform" {
+			println!("{} threshold sweep ({} input):", SWEEPABLE[algorithm].0, inputs::DISTRIBUTIONS[distribution].0);
+		} else {
+			println!("{} threshold sweep:", SWEEPABLE[algorithm].0);
+		}
+		let labels: Vec<String> = config.thresholds.iter().map(|t| t.to_string()).collect();
+		let sizes: Vec<usize> = columns.iter().map(|s| s.size).collect();
+		let mut table = table(config, "threshold", &labels, &sizes, &mut results);
+		let optima: Vec<Option<Optimum>> = columns.iter().map(|s| {
+			s.optimum(&config.thresholds, config.outlier_coefficient, config.seed)
+		}).collect();
+		let mut row = vec![Cell::new("best")];
+		for optimum in &optima {
+			match optimum {
+				Option::Some(o) => row.push(Cell::new(&format!("{} ({} - {})", o.threshold, o.low, o.high))),
+				Option::None => row.push(Cell::new("-"))
+			}
+		}
+		table.add_row(Row::new(row));
+		table.printstd();
+		println!("└ Values in ms; 98% confidence interval displayed; s = statistically equal to fastest; \
+					* = within {}% of fastest; best = fastest threshold (98% confidence interval)",
+					config.diff_threshold * 100.0);
+		// the largest size is what the threshold matters for the most
+		if let Option::Some((sweep, Option::Some(o))) = columns.iter().zip(&optima).rfind(|(_, o)| o.is_some()) {
+			println!("best threshold for {}: {} (98% confidence interval {} - {}) at size {}",
+				SWEEPABLE[algorithm].0, o.threshold, o.low, o.high, utils::commafy(sweep.size));
+		}
+		println!();
+	}
+}
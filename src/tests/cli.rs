@@ -0,0 +1,323 @@
+use crate::cli;
+use crate::datasets::Sampling;
+use crate::gap_search::Score;
+use crate::gaps::HMax;
+
+fn args(list: &[&str]) -> Vec<String> {
+	list.iter().map(|s| String::from(*s)).collect()
+}
+
+fn parse_config(list: &[&str]) -> cli::Config {
+	match cli::parse_args(&args(list)) {
+		Ok(cli::Command::Run(config)) => config,
+		_ => panic!("expected a run command")
+	}
+}
+
+#[test]
+fn test_default_profile() {
+	assert_eq!(parse_config(&[]), cli::Config::from_profile(cli::Profile::Full));
+}
+
+#[test]
+fn test_profiles() {
+	let quick = parse_config(&["--profile", "quick"]);
+	assert!(quick.test_mode);
+	assert!(quick.algorithms.contains(&String::from("algos::insertionsort_c")));
+	assert_eq!(quick.max_size, 10_000);
+	assert_eq!(quick.n_tests, 50);
+	let embedded = parse_config(&["--profile=embedded"]);
+	assert!(!embedded.test_mode);
+	assert_eq!(embedded.max_size, 100_000);
+	assert!(cli::parse_args(&args(&["--profile", "huge"])).is_err());
+}
+
+#[test]
+fn test_overrides() {
+	// explicit flags win over the profile no matter where --profile appears
+	let config = parse_config(&["--max-size", "1_000", "--tests=30", "--profile", "full",
+								"--seed", "7", "--runtime-limit", "0.5", "--workers", "1"]);
+	assert_eq!(config.max_size, 1_000);
+	assert_eq!(config.n_tests, 30);
+	assert_eq!(config.seed, 7);
+	assert_eq!(config.runtime_limit, 500_000_000);
+	assert_eq!(config.n_workers, 1);
+	assert_eq!(config.test_sizes(), vec![10, 100, 1_000]);
+	// the last power of ten below usize::MAX ends the sizes instead of overflowing
+	let config = parse_config(&["--min-size", "1", "--max-size", &usize::MAX.to_string()]);
+	assert_eq!(config.test_sizes().len(), usize::MAX.to_string().len());
+}
+
+#[test]
+fn test_bad_args() {
+	assert!(cli::parse_args(&args(&["--tests"])).is_err());
+	assert!(cli::parse_args(&args(&["--tests", "many"])).is_err());
+	assert!(cli::parse_args(&args(&["--bogus", "1"])).is_err());
+	assert!(cli::parse_args(&args(&["quick"])).is_err());
+	assert!(cli::parse_args(&args(&["run", "extra"])).is_err());
+	assert!(cli::parse_args(&args(&["--min-size", "1000", "--max-size", "10"])).is_err());
+	assert!(matches!(cli::parse_args(&args(&["--seed", "1", "-h"])), Ok(cli::Command::Help)));
+}
+
+#[test]
+fn test_selection() {
+	let config = parse_config(&["--algo", "shellsort_*,heapsort_top_down", "--algo", "timsort",
+								"--group", "mergesort", "--exclude", "shellsort_knuth"]);
+	assert_eq!(config.algorithms, vec!["shellsort_*", "heapsort_top_down", "timsort"]);
+	assert_eq!(config.groups, vec!["mergesort"]);
+	assert_eq!(config.exclude, vec!["shellsort_knuth"]);
+	// the quick profile's default selection is replaced, not extended
+	assert!(!parse_config(&["--profile", "quick"]).algorithms.is_empty());
+	let config = parse_config(&["--profile", "quick", "--group", "heapsort"]);
+	assert!(config.algorithms.is_empty());
+	assert_eq!(config.groups, vec!["heapsort"]);
+	assert!(matches!(cli::parse_args(&args(&["--list"])), Ok(cli::Command::List(_))));
+}
+
+#[test]
+fn test_analyze() {
+	match cli::parse_args(&args(&["analyze", "run.samples", "--alpha", "0.01",
+								  "--outlier-coefficient=1.5", "--output", "csv"])) {
+		Ok(cli::Command::Analyze(config, path)) => {
+			assert_eq!(path, "run.samples");
+			assert_eq!(config.alpha, 0.01);
+			assert_eq!(config.outlier_coefficient, 1.5);
+			assert_eq!(config.outputs, vec![cli::OutputFormat::Csv]);
+		}
+		_ => panic!("expected an analyze command")
+	}
+	assert!(cli::parse_args(&args(&["analyze"])).is_err());
+	assert!(cli::parse_args(&args(&["analyze", "a.samples", "b.samples"])).is_err());
+	assert!(cli::parse_args(&args(&["analyze", "a.samples", "--tests", "10"])).is_err());
+	assert!(cli::parse_args(&args(&["--alpha", "2"])).is_err());
+}
+
+#[test]
+fn test_compare() {
+	match cli::parse_args(&args(&["compare", "a.samples", "b.samples", "--group", "heapsort"])) {
+		Ok(cli::Command::Compare(config, a, b)) => {
+			assert_eq!(a, "a.samples");
+			assert_eq!(b, "b.samples");
+			assert_eq!(config.groups, vec!["heapsort"]);
+		}
+		_ => panic!("expected a compare command")
+	}
+	assert!(cli::parse_args(&args(&["compare", "a.samples"])).is_err());
+	assert!(cli::parse_args(&args(&["compare", "a.samples", "b.samples", "c.samples"])).is_err());
+	assert!(cli::parse_args(&args(&["compare", "a.samples", "b.samples", "--seed", "1"])).is_err());
+	assert!(cli::parse_args(&args(&["compare", "a.samples", "b.samples", "--output", "csv"])).is_err());
+}
+
+#[test]
+fn test_check() {
+	match cli::parse_args(&args(&["check", "--baseline", "main.samples", "--profile", "quick",
+								  "--algo", "heapsort_*", "--max-regression", "0.1"])) {
+		Ok(cli::Command::Check(config, baseline)) => {
+			assert_eq!(baseline, "main.samples");
+			assert_eq!(config.profile, cli::Profile::Quick);
+			assert_eq!(config.algorithms, vec!["heapsort_*"]);
+			assert_eq!(config.max_regression, 0.1);
+		}
+		_ => panic!("expected a check command")
+	}
+	assert!(cli::parse_args(&args(&["check"])).is_err());
+	assert!(cli::parse_args(&args(&["check", "main.samples"])).is_err());
+	assert!(cli::parse_args(&args(&["check", "--baseline", "main.samples", "--list"])).is_err());
+	assert!(cli::parse_args(&args(&["--baseline", "main.samples"])).is_err());
+	assert!(cli::parse_args(&args(&["--max-regression", "0.1"])).is_err());
+	assert!(cli::parse_args(&args(&["check", "--baseline", "a", "--max-regression", "-1"])).is_err());
+}
+
+#[test]
+fn test_checkpoint() {
+	let config = parse_config(&["--checkpoint", "run.samples", "--checkpoint-interval", "300"]);
+	assert_eq!(config.checkpoint, Option::Some(String::from("run.samples")));
+	assert_eq!(config.checkpoint_interval, 300_000_000_000);
+	assert_eq!(config.resume, Option::None);
+	// resuming keeps saving to the same checkpoint unless told otherwise
+	let config = parse_config(&["--resume", "run.samples", "--workers", "2"]);
+	assert_eq!(config.resume, Option::Some(String::from("run.samples")));
+	assert_eq!(config.checkpoint, config.resume);
+	assert_eq!(config.n_workers, 2);
+	let config = parse_config(&["--resume", "run.samples", "--checkpoint", "new.samples"]);
+	assert_eq!(config.checkpoint, Option::Some(String::from("new.samples")));
+	assert!(cli::parse_args(&args(&["--resume", "run.samples", "--seed", "1"])).is_err());
+	assert!(cli::parse_args(&args(&["--resume", "run.samples", "--algo", "heapsort_*"])).is_err());
+	assert!(cli::parse_args(&args(&["analyze", "a.samples", "--resume", "run.samples"])).is_err());
+	assert!(cli::parse_args(&args(&["check", "--baseline", "a", "--resume", "run.samples"])).is_err());
+}
+
+#[test]
+fn test_distributions() {
+	assert_eq!(parse_config(&[]).distributions, vec!["uniform"]);
+	let config = parse_config(&["--distribution", "sorted,zipf", "--distribution=sorted"]);
+	assert_eq!(config.distributions, vec!["sorted", "zipf"]);
+	let config = parse_config(&["--distribution", "all"]);
+	assert_eq!(config.distributions.len(), crate::inputs::DISTRIBUTIONS.len());
+	assert_eq!(config.distributions[0], "uniform");
+	assert!(cli::parse_args(&args(&["--distribution", "gaussian"])).is_err());
+	assert!(cli::parse_args(&args(&["--distribution", ","])).is_err());
+	assert!(cli::parse_args(&args(&["analyze", "a.samples", "--distribution", "sorted"])).is_err());
+}
+
+#[test]
+fn test_elements() {
+	assert_eq!(parse_config(&[]).elements, vec!["i32"]);
+	let config = parse_config(&["--element", "u8,record64", "--element=u8"]);
+	assert_eq!(config.elements, vec!["u8", "record64"]);
+	let config = parse_config(&["--element", "all"]);
+	assert_eq!(config.elements.len(), crate::elements::ElementType::ALL.len());
+	assert!(cli::parse_args(&args(&["--element", "f32"])).is_err());
+	assert!(cli::parse_args(&args(&["--element", ","])).is_err());
+	assert!(cli::parse_args(&args(&["--resume", "run.samples", "--element", "u8"])).is_err());
+	assert!(cli::parse_args(&args(&["analyze", "a.samples", "--element", "u8"])).is_err());
+}
+
+#[test]
+fn test_operations() {
+	assert!(!parse_config(&[]).operations);
+	// doesn't take a value
+	let config = parse_config(&["--operations", "--tests", "40"]);
+	assert!(config.operations);
+	assert_eq!(config.n_tests, 40);
+	assert!(cli::parse_args(&args(&["analyze", "a.samples", "--operations"])).is_err());
+	assert!(cli::parse_args(&args(&["check", "--baseline", "a", "--operations"])).is_err());
+}
+
+#[test]
+fn test_complexity() {
+	assert!(!parse_config(&[]).complexity);
+	// doesn't take a value
+	let config = parse_config(&["--complexity", "--tests", "40"]);
+	assert!(config.complexity);
+	assert_eq!(config.n_tests, 40);
+	assert!(matches!(cli::parse_args(&args(&["analyze", "a.samples", "--complexity"])),
+					 Ok(cli::Command::Analyze(config, _)) if config.complexity));
+	assert!(cli::parse_args(&args(&["compare", "a", "b", "--complexity"])).is_err());
+	assert!(cli::parse_args(&args(&["check", "--baseline", "a", "--complexity"])).is_err());
+	assert!(cli::parse_args(&args(&["sweep", "--complexity"])).is_err());
+}
+
+#[test]
+fn test_sweep() {
+	let sweep = |list: &[&str]| match cli::parse_args(&args(list)) {
+		Ok(cli::Command::Sweep(config)) => config,
+		_ => panic!("expected sweep")
+	};
+	assert_eq!(sweep(&["sweep"]).thresholds, (4..=64).step_by(4).collect::<Vec<usize>>());
+	assert_eq!(sweep(&["sweep", "--thresholds", "8..12"]).thresholds, vec![8, 9, 10, 11, 12]);
+	assert_eq!(sweep(&["sweep", "--thresholds", "8..32:8"]).thresholds, vec![8, 16, 24, 32]);
+	// sorted and deduplicated
+	assert_eq!(sweep(&["sweep", "--thresholds", "32,8,16,8"]).thresholds, vec![8, 16, 32]);
+	for bad in &["8", "8..", "8..32:0", "a,b", "32..8"] {
+		assert!(cli::parse_args(&args(&["sweep", "--thresholds", bad])).is_err(), "{}", bad);
+	}
+	assert!(cli::parse_args(&args(&["--thresholds", "8,16"])).is_err());
+	assert!(cli::parse_args(&args(&["sweep", "--element", "u8"])).is_err());
+	assert!(cli::parse_args(&args(&["sweep", "--output", "json"])).is_err());
+	assert!(cli::parse_args(&args(&["sweep", "extra"])).is_err());
+}
+
+#[test]
+fn test_gaps() {
+	let gaps = |list: &[&str]| match cli::parse_args(&args(list)) {
+		Ok(cli::Command::Gaps(config)) => config,
+		_ => panic!("expected gaps")
+	};
+	let config = gaps(&["gaps"]);
+	assert_eq!(config.h_max, vec![HMax::Fraction(4), HMax::Fraction(3), HMax::Fraction(2), HMax::Fraction(1)]);
+	assert_eq!(config.gap_file, Option::None);
+	// deduplicated in order
+	let config = gaps(&["gaps", "--h-max", "n,1000,n/2,n", "--gap-file", "gaps.txt"]);
+	assert_eq!(config.h_max, vec![HMax::Fraction(1), HMax::Absolute(1000), HMax::Fraction(2)]);
+	assert_eq!(config.gap_file, Option::Some(String::from("gaps.txt")));
+	for bad in &["", "n/0", "x"] {
+		assert!(cli::parse_args(&args(&["gaps", "--h-max", bad])).is_err(), "{}", bad);
+	}
+	assert!(cli::parse_args(&args(&["--h-max", "n"])).is_err());
+	assert!(cli::parse_args(&args(&["sweep", "--gap-file", "gaps.txt"])).is_err());
+	assert!(cli::parse_args(&args(&["gaps", "--thresholds", "8,16"])).is_err());
+	assert!(cli::parse_args(&args(&["gaps", "--workers", "2"])).is_err());
+	assert!(cli::parse_args(&args(&["gaps", "extra"])).is_err());
+}
+
+#[test]
+fn test_gap_search() {
+	let gap_search = |list: &[&str]| match cli::parse_args(&args(list)) {
+		Ok(cli::Command::GapSearch(config)) => config,
+		_ => panic!("expected gap-search")
+	};
+	let config = gap_search(&["gap-search"]);
+	assert_eq!((config.score, config.iterations), (Score::Comparisons, 1000));
+	let config = gap_search(&["gap-search", "--score", "time", "--iterations", "50"]);
+	assert_eq!((config.score, config.iterations), (Score::Time, 50));
+	assert!(cli::parse_args(&args(&["gap-search", "--score", "speed"])).is_err());
+	assert!(cli::parse_args(&args(&["--iterations", "50"])).is_err());
+	assert!(cli::parse_args(&args(&["gaps", "--score", "time"])).is_err());
+	assert!(cli::parse_args(&args(&["gap-search", "--h-max", "n"])).is_err());
+	assert!(cli::parse_args(&args(&["gap-search", "--tests", "5"])).is_err());
+	assert!(cli::parse_args(&args(&["gap-search", "--output", "json"])).is_err());
+}
+
+#[test]
+fn test_comparators() {
+	match cli::parse_args(&args(&["comparators", "--algo", "quicksort_by", "--max-size", "1000"])) {
+		Ok(cli::Command::Comparators(config)) => assert_eq!(config.max_size, 1000),
+		_ => panic!("expected comparators")
+	}
+	assert!(cli::parse_args(&args(&["comparators", "extra"])).is_err());
+	assert!(cli::parse_args(&args(&["comparators", "--group", "heapsort"])).is_err());
+	assert!(cli::parse_args(&args(&["comparators", "--workers", "2"])).is_err());
+	assert!(cli::parse_args(&args(&["comparators", "--thresholds", "8,16"])).is_err());
+}
+
+#[test]
+fn test_inputs() {
+	let config = parse_config(&["--dump-inputs", "inputs.bin", "--max-size", "1000"]);
+	assert_eq!(config.dump_inputs, Option::Some(String::from("inputs.bin")));
+	assert_eq!(config.inputs_from, Option::None);
+	match cli::parse_args(&args(&["check", "--baseline", "base.samples", "--inputs-from", "inputs.bin"])) {
+		Ok(cli::Command::Check(config, _)) => assert_eq!(config.inputs_from, Option::Some(String::from("inputs.bin"))),
+		_ => panic!("expected check")
+	}
+	// the corpus decides these
+	for flag in &["--max-size", "--distribution", "--tests", "--seed", "--checkpoint"] {
+		let value = if *flag == "--distribution" { "sorted" } else { "100" };
+		assert!(cli::parse_args(&args(&["--inputs-from", "inputs.bin", flag, value])).is_err(), "{}", flag);
+	}
+	assert!(cli::parse_args(&args(&["--resume", "run.checkpoint", "--dump-inputs", "inputs.bin"])).is_err());
+	assert!(cli::parse_args(&args(&["analyze", "run.samples", "--inputs-from", "inputs.bin"])).is_err());
+	assert!(cli::parse_args(&args(&["sweep", "--dump-inputs", "inputs.bin"])).is_err());
+}
+
+#[test]
+fn test_datasets() {
+	// datasets replace the distributions unless they're given too
+	let config = parse_config(&["--dataset", "prices.txt", "--dataset", "orders.csv:amount", "--dataset",
+								"prices.txt"]);
+	assert_eq!(config.datasets, vec!["prices.txt", "orders.csv:amount"]);
+	assert!(config.distributions.is_empty());
+	assert_eq!(config.sampling, Sampling::Window);
+	let config = parse_config(&["--dataset", "prices.txt", "--distribution", "sorted", "--sampling", "resample"]);
+	assert_eq!(config.distributions, vec!["sorted"]);
+	assert_eq!(config.sampling, Sampling::Resample);
+	assert!(cli::parse_args(&args(&["--sampling", "resample"])).is_err());
+	assert!(cli::parse_args(&args(&["--dataset", "prices.txt", "--sampling", "all"])).is_err());
+	assert!(cli::parse_args(&args(&["--dataset", "prices.txt", "--checkpoint", "run.samples"])).is_err());
+	assert!(cli::parse_args(&args(&["--dataset", "prices.txt", "--inputs-from", "inputs.bin"])).is_err());
+	assert!(cli::parse_args(&args(&["gaps", "--dataset", "prices.txt"])).is_err());
+}
+
+#[test]
+fn test_job_timeout() {
+	assert_eq!(parse_config(&[]).job_timeout, Option::None);
+	let config = parse_config(&["--job-timeout", "2.5", "--workers", "1"]);
+	assert_eq!(config.job_timeout, Option::Some(2_500_000_000));
+	// it's how the run is executed, not part of the run settings
+	let config = parse_config(&["--resume", "run.samples", "--job-timeout", "5"]);
+	assert_eq!(config.job_timeout, Option::Some(5_000_000_000));
+	assert!(cli::parse_args(&args(&["--job-timeout", "0"])).is_err());
+	assert!(cli::parse_args(&args(&["--job-timeout", "soon"])).is_err());
+	assert!(cli::parse_args(&args(&["analyze", "run.samples", "--job-timeout", "5"])).is_err());
+	assert!(cli::parse_args(&args(&["sweep", "--job-timeout", "5"])).is_err());
+}
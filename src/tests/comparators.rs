@@ -0,0 +1,89 @@
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use crate::cli;
+use crate::comparators::{self, Record, FAMILIES};
+
+fn family(name: &str) -> usize {
+	FAMILIES.iter().position(|f| f.0 == name).unwrap()
+}
+
+#[test]
+fn test_sort_by() {
+	let mut rng = SmallRng::seed_from_u64(0);
+	for size in &[0, 1, 2, 3, 10, 33, 100, 1000] {
+		let input: Vec<i32> = (0..*size).map(|_| rng.gen_range(-50..50)).collect();
+		for (f, (name, _)) in FAMILIES.iter().enumerate() {
+			// reversed comparator
+			let mut expected = input.clone();
+			expected.sort_by(|a, b| b.cmp(a));
+			let mut array = input.clone();
+			comparators::sort_by(f, &mut array, |a, b| b.cmp(a));
+			assert_eq!(array, expected, "{} size {}", name, size);
+			let mut expected = input.clone();
+			expected.sort_by_key(|x| x.abs());
+			let mut array = input.clone();
+			comparators::sort_by_key(f, &mut array, |x| x.abs());
+			assert_eq!(array.iter().map(|x| x.abs()).collect::<Vec<i32>>(),
+					   expected.iter().map(|x| x.abs()).collect::<Vec<i32>>(), "{} size {}", name, size);
+		}
+	}
+}
+
+#[test]
+fn test_stable_by_key() {
+	// same families as the stable registered algorithms
+	let mut rng = SmallRng::seed_from_u64(1);
+	let input: Vec<(i32, usize)> = (0..500).map(|i| (rng.gen_range(0..10), i)).collect();
+	let mut expected = input.clone();
+	expected.sort_by_key(|x| x.0);
+	for name in &["bubblesort", "insertionsort", "mergesort", "timsort"] {
+		let mut array = input.clone();
+		comparators::sort_by_key(family(name), &mut array, |x| x.0);
+		assert_eq!(array, expected, "{}", name);
+	}
+}
+
+#[test]
+fn test_record() {
+	let keys = [i32::MIN, -1, 0, 1, 9, 10, i32::MAX];
+	for pair in keys.windows(2) {
+		assert!(Record::new(pair[0]).name < Record::new(pair[1]).name, "{:?}", pair);
+	}
+	let mut records: Vec<Record> = [3, -7, 12, 0].iter().map(|k| Record::new(*k)).collect();
+	comparators::sort_by(family("quicksort"), &mut records, |a, b| a.name.cmp(&b.name));
+	assert_eq!(records.iter().map(|r| r.key).collect::<Vec<i32>>(), vec![-7, 0, 3, 12]);
+}
+
+#[test]
+fn test_selection() {
+	let mut config = cli::Config::from_profile(cli::Profile::Full);
+	assert_eq!(comparators::selection(&config).len(), FAMILIES.len());
+	config.algorithms = vec![String::from("*sort_by")];
+	config.exclude = vec![String::from("algos::bubblesort_by")];
+	assert_eq!(comparators::selection(&config).len(), FAMILIES.len() - 1);
+	config.algorithms = vec![String::from("heapsort_by")];
+	config.exclude = vec![];
+	assert_eq!(comparators::selection(&config), vec![family("heapsort")]);
+	// the registered algorithms aren't entry points
+	config.algorithms = vec![String::from("heapsort_top_down")];
+	assert!(comparators::selection(&config).is_empty());
+}
+
+#[test]
+fn test_run() {
+	let mut config = cli::Config::from_profile(cli::Profile::Quick);
+	config.min_size = 10;
+	config.max_size = 100;
+	config.n_tests = 2;
+	config.distributions = vec![String::from("uniform"), String::from("sorted")];
+	let families = [family("bubblesort"), family("timsort")];
+	let runs = comparators::run(&config, &families);
+	// 2 families, 3 workloads, 2 distributions, 2 sizes
+	assert_eq!(runs.len(), 24);
+	assert!(runs.iter().all(|r| r.samples.len() == 2));
+	// bubblesort isn't run past its size limit
+	config.min_size = 100_000;
+	config.max_size = 100_000;
+	assert!(comparators::run(&config, &families[..1]).is_empty());
+}
@@ -0,0 +1,87 @@
+use crate::compare;
+use crate::export::CellFailure;
+use crate::samples::SampleCell;
+
+fn cell(algorithm: &str, size: usize, base: u64) -> SampleCell {
+	SampleCell {
+		algorithm: String::from(algorithm),
+		complexity: String::from("O(n^2)"),
+		distribution: String::from("uniform"),
+		element: String::from("i32"),
+		size,
+		// a little noise so the stdev isn't 0
+		samples: (0..40).map(|i| base + i % 5).collect(),
+		failure: Option::None
+	}
+}
+
+#[test]
+fn test_compare_cells() {
+	let a = vec![
+		cell("algos::bubblesort", 10, 1000),
+		cell("algos::bubblesort", 100, 1000),
+		cell("algos::insertionsort", 10, 1000),
+		cell("algos::selectionsort", 10, 1000),
+		SampleCell {
+			failure: Option::Some(CellFailure { test_i: 3, message: String::from("oops"), timed_out: true }),
+			..cell("algos::heapsort", 10, 1000)
+		}
+	];
+	let b = vec![
+		cell("algos::heapsort", 10, 1000),
+		cell("algos::insertionsort", 10, 1000),
+		cell("algos::bubblesort", 10, 500),
+		cell("algos::bubblesort", 1000, 500),
+		SampleCell { samples: vec![1000; 5], ..cell("algos::selectionsort", 10, 0) }
+	];
+	let comparisons = compare::compare_cells(&a, &b, 3.0);
+	// bubblesort at 100 and 1000 only appear on one side, selectionsort doesn't have enough runs, and
+	// heapsort failed in a
+	assert_eq!(comparisons.len(), 2);
+	assert_eq!(comparisons[0].algorithm, "algos::bubblesort");
+	assert_eq!(comparisons[0].size, 10);
+	assert!((comparisons[0].change + 0.5).abs() < 0.01);
+	assert!(comparisons[0].is_significant(0.001));
+	assert_eq!(comparisons[1].algorithm, "algos::insertionsort");
+	assert_eq!(comparisons[1].change, 0.0);
+	assert!(!comparisons[1].is_significant(0.001));
+}
+
+#[test]
+fn test_regressions() {
+	let a = vec![
+		cell("algos::bubblesort", 10, 1000),
+		cell("algos::insertionsort", 10, 1000),
+		cell("algos::selectionsort", 10, 1000)
+	];
+	let b = vec![
+		cell("algos::bubblesort", 10, 2000),
+		cell("algos::insertionsort", 10, 1030),
+		cell("algos::selectionsort", 10, 500)
+	];
+	let comparisons = compare::compare_cells(&a, &b, 3.0);
+	assert_eq!(comparisons.len(), 3);
+	// twice as slow
+	assert!(comparisons[0].is_regression(0.05, 0.001));
+	assert!(!comparisons[0].is_regression(1.5, 0.001));
+	// significant but within the tolerance
+	assert!(comparisons[1].is_significant(0.001));
+	assert!(!comparisons[1].is_regression(0.05, 0.001));
+	assert!(comparisons[1].is_regression(0.01, 0.001));
+	// faster
+	assert!(!comparisons[2].is_regression(0.05, 0.001));
+}
+
+#[test]
+fn test_elements() {
+	let u8_cell = |base| SampleCell { element: String::from("u8"), ..cell("algos::bubblesort", 10, base) };
+	let a = vec![cell("algos::bubblesort", 10, 1000), u8_cell(1000)];
+	let b = vec![u8_cell(2000), cell("algos::bubblesort", 10, 1000)];
+	let comparisons = compare::compare_cells(&a, &b, 3.0);
+	assert_eq!(comparisons.len(), 2);
+	// only cells with the same element type are paired up
+	assert_eq!(comparisons[0].change, 0.0);
+	assert_eq!(comparisons[0].input(), "uniform");
+	assert!((comparisons[1].change - 1.0).abs() < 0.01);
+	assert_eq!(comparisons[1].input(), "uniform u8");
+}
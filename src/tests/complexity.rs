@@ -0,0 +1,47 @@
+use crate::complexity::{self, Model, Verdict};
+
+const QUADRATIC: Model = Model { exponent: 2.0, log: false };
+
+fn close(a: f64, b: f64) -> bool {
+	(a - b).abs() < 1e-9
+}
+
+#[test]
+fn test_model() {
+	assert_eq!(complexity::CANDIDATES.iter().map(|m| m.name()).collect::<Vec<String>>(),
+			   vec!["n", "n log n", "n^1.25", "n^1.5", "n^2"]);
+	assert!(close(Model { exponent: 1.0, log: true }.f(1024), 10240.0));
+	assert!(close(Model { exponent: 2.0, log: false }.local_exponent(&[10, 100, 1000]), 2.0));
+	// n log n grows a bit faster than n
+	let local = Model { exponent: 1.0, log: true }.local_exponent(&[1000, 10_000, 100_000, 1_000_000]);
+	assert!(local > 1.05 && local < 1.15, "{}", local);
+}
+
+#[test]
+fn test_fit() {
+	let sizes = [1000, 10_000, 100_000, 1_000_000];
+	for model in &complexity::CANDIDATES {
+		let points: Vec<(usize, f64)> = sizes.iter().map(|n| (*n, 3.0 * model.f(*n))).collect();
+		let fit = complexity::fit(*model, &points);
+		assert!((fit.constant - 3.0).abs() < 1e-6 && fit.error < 1e-9, "{:?}", fit);
+		let report = complexity::analyze(&points, complexity::CANDIDATES[0]).unwrap();
+		assert_eq!(report.best.model, *model);
+		assert!(close(report.exponent, model.local_exponent(&sizes)));
+	}
+}
+
+#[test]
+fn test_analyze() {
+	let quadratic: Vec<(usize, f64)> = [10, 100, 1000, 10_000, 100_000].iter()
+																	 .map(|n| (*n, (*n as f64).powi(2)))
+																	 .collect();
+	assert_eq!(complexity::analyze(&quadratic, QUADRATIC).unwrap().verdict, Verdict::Consistent);
+	assert_eq!(complexity::analyze(&quadratic, Model { exponent: 1.5, log: false }).unwrap().verdict, Verdict::Faster);
+	assert_eq!(complexity::analyze(&quadratic, Model { exponent: 2.5, log: false }).unwrap().verdict, Verdict::Slower);
+	// small sizes are left out of the fit
+	let mut noisy = quadratic.clone();
+	noisy[0].1 = 1e9;
+	assert!(close(complexity::analyze(&noisy, QUADRATIC).unwrap().exponent, 2.0));
+	// not enough sizes at or above FIT_MIN_SIZE
+	assert!(complexity::analyze(&quadratic[..3], QUADRATIC).is_none());
+}
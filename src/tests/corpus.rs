@@ -0,0 +1,53 @@
+use crate::corpus::{self, Array, Corpus};
+
+fn array(name: &str, test_i: usize, keys: &[i32]) -> Array {
+	Array { name: String::from(name), test_i, keys: keys.to_vec() }
+}
+
+fn write(corpus: &Corpus) -> Vec<u8> {
+	let mut bytes = Vec::new();
+	corpus::write_header(&mut bytes, corpus.seed, corpus.arrays.len()).unwrap();
+	for a in &corpus.arrays {
+		corpus::write_array(&mut bytes, &a.name, a.test_i, &a.keys).unwrap();
+	}
+	bytes
+}
+
+#[test]
+fn test_round_trip() {
+	let corpus = Corpus {
+		seed: 2222,
+		arrays: vec![
+			array("uniform", 0, &[3, i32::MIN, i32::MAX, -1]),
+			array("uniform", 1, &[]),
+			array("prices (usd)", 0, &[5, 5, 5])
+		]
+	};
+	let bytes = write(&corpus);
+	assert_eq!(&bytes[..8], b"SORTKEYS");
+	assert_eq!(corpus::read(&bytes[..]).unwrap(), corpus);
+}
+
+#[test]
+fn test_bad_files() {
+	let bytes = write(&Corpus { seed: 1, arrays: vec![array("sorted", 0, &[1, 2, 3])] });
+	assert!(corpus::read(&b"sorting-benchmarks samples 3\n"[..]).is_err());
+	assert!(corpus::read(&bytes[..bytes.len() - 1]).is_err());
+	let mut trailing = bytes.clone();
+	trailing.push(0);
+	assert!(corpus::read(&trailing[..]).is_err());
+	let mut version = bytes.clone();
+	version[8] = 2;
+	assert!(corpus::read(&version[..]).unwrap_err().contains("version"));
+	// says there are more arrays than there are
+	let mut count = bytes.clone();
+	count[20] = 2;
+	assert!(corpus::read(&count[..]).is_err());
+	// a corrupt name length is rejected before anything is allocated for it
+	let mut name_len = bytes;
+	name_len[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+	assert!(corpus::read(&name_len[..]).unwrap_err().contains("too long"));
+	assert!(corpus::write_array(&mut Vec::new(), &"x".repeat(4097), 0, &[1]).is_err());
+	assert!(corpus::read(&write(&Corpus { seed: 1, arrays: vec![array(&"x".repeat(4096), 0, &[1])] })[..]).is_ok());
+	assert!(corpus::load("/nonexistent/corpus").is_err());
+}
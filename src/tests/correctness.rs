@@ -6,6 +6,9 @@ use rand::rngs::SmallRng;
 use rand::{RngCore, SeedableRng};
 
 use crate::algos;
+use crate::elements::Record;
+use crate::operations;
+use crate::registry;
 use crate::utils;
 
 const TEST_ARRAY_SIZE: usize = 1000;
@@ -223,6 +226,21 @@ fn test_quicksort_random() {
 	test_sorting_algorithm(algos::quicksort_random);
 }
 
+#[test]
+fn test_quicksort_random_seeded() {
+	let mut rng = SmallRng::seed_from_u64(0);
+	let keys: Vec<i32> = (0..1000).map(|_| rng.next_u32() as i32).collect();
+	// pivots only depend on the context's seed
+	let comparisons = |seed| algos::context::with_seed(seed, || operations::count(algos::quicksort_random, &keys));
+	assert_eq!(comparisons(1), comparisons(1));
+	assert_ne!(comparisons(1), comparisons(2));
+	// and go back to the default afterwards
+	assert_eq!(operations::count(algos::quicksort_random, &keys), comparisons(0));
+	let mut array = keys.clone();
+	algos::quicksort_random_with_rng(&mut array, &mut SmallRng::seed_from_u64(7));
+	utils::verify_sorted(&array);
+}
+
 #[test]
 fn test_quicksort_hybrid() {
 	test_sorting_algorithm(algos::quicksort_hybrid);
@@ -285,3 +303,131 @@ fn test_weird() {
 fn test_alternative() {
 	test_sorting_algorithm(odd_algos::shellsort_alternative_ciura);
 }
+
+// ---------------------  stability  ---------------------
+
+// (key, original index) records, the index is kept in the payload which the sorts never look at
+fn tag(keys: &[i32]) -> Vec<Record<16>> {
+	keys.iter().enumerate().map(|(i, key)| {
+		let mut payload = [0; 16];
+		payload[..8].copy_from_slice(&(i as u64).to_le_bytes());
+		Record { key: *key, payload }
+	}).collect()
+}
+
+fn original_index(record: &Record<16>) -> u64 {
+	let mut bytes = [0; 8];
+	bytes.copy_from_slice(&record.payload[..8]);
+	u64::from_le_bytes(bytes)
+}
+
+// lots of equal keys, both in random order and in the ascending / descending runs the adaptive sorts
+// look for
+fn stability_inputs(size: usize) -> Vec<Vec<i32>> {
+	let mut rng = SmallRng::seed_from_u64(FIXED_SEED);
+	vec![
+		(0..size).map(|_| (rng.next_u32() % 8) as i32).collect(),
+		(0..size).map(|_| (rng.next_u32() % (size as u32 / 4 + 1)) as i32).collect(),
+		(0..size).map(|i| (i / 4) as i32).collect(),
+		(0..size).rev().map(|i| (i / 4) as i32).collect(),
+		(0..size).map(|i| (i % 50 / 5) as i32).collect()
+	]
+}
+
+fn test_stability(sort: fn(&mut [Record<16>]), name: &str) {
+	for size in &[2, 10, 100, TEST_ARRAY_SIZE] {
+		for keys in stability_inputs(*size) {
+			let mut array = tag(&keys);
+			sort(&mut array);
+			for (i, pair) in array.windows(2).enumerate() {
+				assert!(pair[0].key <= pair[1].key, "{} didn't sort (size {})", name, size);
+				if pair[0].key == pair[1].key {
+					assert!(original_index(&pair[0]) < original_index(&pair[1]),
+							"{} isn't stable: equal keys {} swapped at {} (size {})", name, pair[0].key, i, size);
+				}
+			}
+		}
+	}
+}
+
+#[test]
+#[should_panic]
+fn test_unstable_sort() {
+	test_stability(algos::heapsort_top_down, "algos::heapsort_top_down");
+}
+
+#[test]
+fn test_stable_algorithms() {
+	// algorithms that only sort i32s (radixsort, insertionsort_c) can't carry an index along and are
+	// taken at their word
+	for algorithm in registry::ALGORITHMS.iter().filter(|a| a.stable) {
+		if let Option::Some(sort) = algorithm.sorts.record16 {
+			test_stability(sort, algorithm.name);
+		}
+	}
+}
+
+// ---------------------  tiny inputs  ---------------------
+
+// next permutation in lexicographic order, false once the array is back to ascending
+fn next_permutation(array: &mut [i32]) -> bool {
+	if array.len() < 2 {
+		return false;
+	}
+	let mut i = array.len() - 1;
+	while i > 0 && array[i - 1] >= array[i] {
+		i -= 1;
+	}
+	if i == 0 {
+		array.reverse();
+		return false;
+	}
+	let mut j = array.len() - 1;
+	while array[j] <= array[i - 1] {
+		j -= 1;
+	}
+	array.swap(i - 1, j);
+	array[i..].reverse();
+	true
+}
+
+#[test]
+fn test_next_permutation() {
+	let mut array = [1, 2, 3];
+	let mut count = 1;
+	while next_permutation(&mut array) {
+		count += 1;
+	}
+	assert_eq!(count, 6);
+	assert_eq!(array, [1, 2, 3]);
+	assert!(!next_permutation(&mut []));
+}
+
+#[test]
+fn test_small_sizes() {
+	for algorithm in registry::ALGORITHMS.iter() {
+		for size in 0..=64 {
+			test_sorting_algorithm_size(algorithm.sorts.i32, size);
+		}
+	}
+}
+
+#[test]
+fn test_permutations() {
+	// every ordering of up to 8 elements, distinct and in pairs of duplicates
+	for algorithm in registry::ALGORITHMS.iter() {
+		for size in 0..=8 {
+			for sorted in &[(0..size).collect::<Vec<i32>>(), (0..size).map(|i| i / 2).collect()] {
+				let mut permutation = sorted.clone();
+				loop {
+					let mut array = permutation.clone();
+					(algorithm.sorts.i32)(&mut array);
+					assert_eq!(&array, sorted, "{} on {:?}", algorithm.name, permutation);
+					if !next_permutation(&mut permutation) {
+						break;
+					}
+				}
+			}
+		}
+	}
+}
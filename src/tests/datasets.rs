@@ -0,0 +1,75 @@
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::datasets::{self, Sampling};
+use crate::inputs::Source;
+
+#[test]
+fn test_sampling() {
+	for name in &["window", "resample"] {
+		assert_eq!(Sampling::from_name(name).unwrap().name(), *name);
+	}
+	assert!(Sampling::from_name("bootstrap").is_err());
+}
+
+#[test]
+fn test_parse_lines() {
+	assert_eq!(datasets::parse_lines("3\n\n-1\n 2147483647 \n"), Ok(vec![3, -1, i32::MAX]));
+	assert!(datasets::parse_lines("1\n2.5\n").unwrap_err().starts_with("line 2"));
+	assert!(datasets::parse_lines("2147483648\n").unwrap_err().contains("i32"));
+}
+
+#[test]
+fn test_parse_binary() {
+	let bytes: Vec<u8> = [1, -2, i32::MIN].iter().flat_map(|v: &i32| v.to_le_bytes().to_vec()).collect();
+	assert_eq!(datasets::parse_binary(&bytes), Ok(vec![1, -2, i32::MIN]));
+	assert!(datasets::parse_binary(&bytes[1..]).is_err());
+}
+
+#[test]
+fn test_parse_csv() {
+	let csv = "id,amount,note\n1,500,\"a\"\n2,\"-3\",b\n\n3,7,c\n";
+	assert_eq!(datasets::parse_csv(csv, "amount"), Ok(vec![500, -3, 7]));
+	// the header is skipped when the column is picked by number
+	assert_eq!(datasets::parse_csv(csv, "1"), Ok(vec![1, 2, 3]));
+	assert_eq!(datasets::parse_csv("4,5\n6,7\n", "2"), Ok(vec![5, 7]));
+	assert!(datasets::parse_csv(csv, "price").unwrap_err().contains("id, amount, note"));
+	assert!(datasets::parse_csv(csv, "3").is_err());
+	assert!(datasets::parse_csv(csv, "0").is_err());
+	assert!(datasets::parse_csv("1,2\n3\n", "2").unwrap_err().starts_with("line 2"));
+}
+
+#[test]
+fn test_parse() {
+	assert_eq!(datasets::name("data/orders.csv:amount"), "orders.amount");
+	assert_eq!(datasets::name("/tmp/prices.txt"), "prices");
+	assert_eq!(datasets::name("c:keys"), "c:keys");
+	assert_eq!(datasets::parse("orders.csv:amount", b"amount\n5\n"), Ok(vec![5]));
+	assert_eq!(datasets::parse("orders.csv", b"5,6\n"), Ok(vec![5]));
+	assert_eq!(datasets::parse("keys.bin", &7i32.to_le_bytes()), Ok(vec![7]));
+	assert_eq!(datasets::parse("keys", b"7\n8\n"), Ok(vec![7, 8]));
+	assert!(datasets::parse("keys.txt", b"\n").is_err());
+	assert!(datasets::load("/nonexistent/keys.txt", Sampling::Window).is_err());
+}
+
+#[test]
+fn test_dataset_source() {
+	let values: Vec<i32> = (0..100).collect();
+	let window = Source::Dataset { name: String::from("data"), values: values.clone(), sampling: Sampling::Window };
+	let resample = Source::Dataset { name: String::from("data"), values, sampling: Sampling::Resample };
+	assert!(window.has_size(100) && !window.has_size(101));
+	assert!(resample.has_size(1000));
+	assert!(window.degenerate());
+	let mut rng = SmallRng::seed_from_u64(0);
+	for _ in 0..20 {
+		let keys = window.keys(10, 0, &mut rng);
+		assert_eq!(keys.len(), 10);
+		assert!(keys.windows(2).all(|w| w[1] == w[0] + 1), "{:?}", keys);
+	}
+	assert_eq!(window.keys(100, 0, &mut rng), (0..100).collect::<Vec<i32>>());
+	let keys = resample.keys(1000, 0, &mut rng);
+	assert!(keys.len() == 1000 && keys.iter().all(|k| (0..100).contains(k)));
+	// reproducible from the run's rng
+	let keys = |seed| resample.keys(50, 0, &mut SmallRng::seed_from_u64(seed));
+	assert_eq!(keys(3), keys(3));
+}
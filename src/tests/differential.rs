@@ -0,0 +1,170 @@
+// differential testing: random inputs through every registered algorithm, checked against slice::sort
+// failing inputs are shrunk and appended to the corpus file, which is replayed on every run
+// set SORT_FUZZ_SEED to explore inputs other than the fixed ones, e.g.
+// SORT_FUZZ_SEED=$RANDOM cargo test differential
+
+use std::io::Write;
+use std::panic;
+
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use crate::inputs;
+use crate::registry;
+
+const CASES: usize = 200;
+const FIXED_SEED: u64 = 0xD1FF;
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/differential_corpus.txt");
+
+// a description of what went wrong, none if the sort matched slice::sort
+fn check(sort: fn(&mut [i32]), input: &[i32]) -> Option<String> {
+	let mut expected = input.to_vec();
+	expected.sort();
+	let mut array = input.to_vec();
+	match panic::catch_unwind(panic::AssertUnwindSafe(|| sort(&mut array))) {
+		Ok(()) if array == expected => Option::None,
+		Ok(()) => Option::Some(format!("output {:?}", array)),
+		Err(_) => Option::Some(String::from("panicked"))
+	}
+}
+
+// mostly short arrays since that's where the boundary cases are, with a long one now and then
+fn random_length(rng: &mut SmallRng) -> usize {
+	match rng.gen_range(0..10) {
+		0..=5 => rng.gen_range(0..=16),
+		6..=8 => rng.gen_range(17..=200),
+		_ => rng.gen_range(201..=2000)
+	}
+}
+
+// one of the benchmark distributions squeezed into a random range of values, narrow ranges give lots
+// of duplicates and ranges at the ends of i32 catch overflow
+fn random_input(rng: &mut SmallRng) -> Vec<i32> {
+	let mut array = vec![0; random_length(rng)];
+	let distribution = rng.gen_range(0..inputs::DISTRIBUTIONS.len());
+	(inputs::DISTRIBUTIONS[distribution].1)(&mut array, rng);
+	let range: i64 = match rng.gen_range(0..4) {
+		0 => 2,
+		1 => rng.gen_range(3..=array.len() as i64 + 3),
+		2 => rng.gen_range(3..=1_000_000),
+		_ => 1 << 32
+	};
+	let low = match rng.gen_range(0..3) {
+		0 => i32::MIN as i64,
+		1 => i32::MAX as i64 - range + 1,
+		_ => rng.gen_range(-1000..=1000)
+	};
+	// order preserving
+	for item in array.iter_mut() {
+		*item = (low as i128 + (*item as i128 - i32::MIN as i128) * range as i128 / (1 << 32)) as i32;
+	}
+	array
+}
+
+// a smaller input that still fails: drops chunks, then pulls values towards 0
+fn shrink(input: &[i32], fails: impl Fn(&[i32]) -> bool) -> Vec<i32> {
+	let mut input = input.to_vec();
+	let mut changed = true;
+	while changed {
+		changed = false;
+		let mut chunk = input.len() / 2;
+		while chunk > 0 {
+			let mut i = 0;
+			while i + chunk <= input.len() {
+				let mut candidate = input.clone();
+				candidate.drain(i..(i + chunk));
+				if fails(&candidate) {
+					input = candidate;
+					changed = true;
+				} else {
+					i += chunk;
+				}
+			}
+			chunk /= 2;
+		}
+		for i in 0..input.len() {
+			for value in &[0, input[i] / 2, input[i] - input[i].signum()] {
+				if *value != input[i] {
+					let mut candidate = input.clone();
+					candidate[i] = *value;
+					if fails(&candidate) {
+						input = candidate;
+						changed = true;
+						break;
+					}
+				}
+			}
+		}
+	}
+	input
+}
+
+// "[3, 1, 2]" per line, # comments
+fn parse_corpus(text: &str) -> Vec<Vec<i32>> {
+	text.lines()
+		.map(|line| line.trim())
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.map(|line| {
+			let inner = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')).expect("bad corpus line");
+			inner.split(',').map(|v| v.trim()).filter(|v| !v.is_empty()).map(|v| v.parse().unwrap()).collect()
+		})
+		.collect()
+}
+
+fn save(name: &str, input: &[i32]) {
+	let mut file = std::fs::OpenOptions::new().append(true).create(true).open(CORPUS).unwrap();
+	writeln!(file, "# {}\n{:?}", name, input).unwrap();
+}
+
+#[test]
+fn test_shrink() {
+	// drops the last element of anything longer than 5
+	let broken: fn(&mut [i32]) = |array| {
+		array.sort();
+		if array.len() > 5 {
+			let last = array.len() - 1;
+			array[last] = 0;
+		}
+	};
+	let mut rng = SmallRng::seed_from_u64(FIXED_SEED);
+	let input: Vec<i32> = (0..100).map(|_| rng.next_u32() as i32).collect();
+	assert!(check(broken, &input).is_some());
+	let shrunk = shrink(&input, |input| check(broken, input).is_some());
+	assert_eq!(shrunk.len(), 6);
+	assert!(shrunk.iter().all(|v| v.abs() <= 1), "{:?}", shrunk);
+}
+
+#[test]
+fn test_parse_corpus() {
+	assert_eq!(parse_corpus("# comment\n[]\n\n[3, -1, 2]\n  [7]  \n"), vec![vec![], vec![3, -1, 2], vec![7]]);
+}
+
+#[test]
+fn test_corpus() {
+	let text = std::fs::read_to_string(CORPUS).unwrap();
+	for input in parse_corpus(&text) {
+		for algorithm in registry::ALGORITHMS.iter() {
+			if let Option::Some(problem) = check(algorithm.sorts.i32, &input) {
+				panic!("{} on corpus input {:?}: {}", algorithm.name, input, problem);
+			}
+		}
+	}
+}
+
+#[test]
+fn test_differential() {
+	let seed = std::env::var("SORT_FUZZ_SEED").map(|s| s.parse().expect("bad SORT_FUZZ_SEED"))
+											  .unwrap_or(FIXED_SEED);
+	let mut rng = SmallRng::seed_from_u64(seed);
+	for case in 0..CASES {
+		let input = random_input(&mut rng);
+		for algorithm in registry::ALGORITHMS.iter() {
+			if check(algorithm.sorts.i32, &input).is_some() {
+				let shrunk = shrink(&input, |input| check(algorithm.sorts.i32, input).is_some());
+				save(algorithm.name, &shrunk);
+				panic!("{} failed on case {} of seed {}, shrunk to {:?}: {} (saved to {})", algorithm.name, case,
+					   seed, shrunk, check(algorithm.sorts.i32, &shrunk).unwrap(), CORPUS);
+			}
+		}
+	}
+}
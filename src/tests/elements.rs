@@ -0,0 +1,103 @@
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::elements::{Element, ElementType, Record};
+use crate::inputs;
+use crate::registry;
+
+fn keys(distribution: &str, size: usize) -> Vec<i32> {
+	let mut keys = vec![0; size];
+	let mut rng = SmallRng::seed_from_u64(5);
+	(inputs::DISTRIBUTIONS[inputs::find(distribution).unwrap()].1)(&mut keys, &mut rng);
+	keys
+}
+
+fn assert_order_preserved<T: Element>() {
+	let mut sorted = keys("uniform", 1000);
+	sorted.extend_from_slice(&[i32::MIN, -1, 0, 1, i32::MAX]);
+	sorted.sort_unstable();
+	let converted: Vec<T> = sorted.iter().map(|k| T::from_key(*k)).collect();
+	assert!(converted.windows(2).all(|w| w[0] <= w[1]), "{:?}", converted);
+}
+
+// sorts with the given instantiation and checks the result against the standard library
+fn assert_sorts<T: Element>(sort: Option<fn(&mut [T])>, keys: &[i32], name: &str) {
+	if let Option::Some(sort) = sort {
+		let mut array: Vec<T> = keys.iter().map(|k| T::from_key(*k)).collect();
+		let mut expected = array.clone();
+		expected.sort();
+		sort(&mut array);
+		assert_eq!(array, expected, "{} {}", name, std::any::type_name::<T>());
+	}
+}
+
+#[test]
+fn test_order_preserved() {
+	assert_order_preserved::<i32>();
+	assert_order_preserved::<u8>();
+	assert_order_preserved::<u16>();
+	assert_order_preserved::<u64>();
+	assert_order_preserved::<u128>();
+	assert_order_preserved::<Record<16>>();
+	assert_order_preserved::<Record<256>>();
+	// narrow types keep the high bits
+	assert_eq!(u8::from_key(i32::MIN), 0);
+	assert_eq!(u8::from_key(-1), 127);
+	assert_eq!(u8::from_key(0), 128);
+	assert_eq!(u16::from_key(i32::MAX), u16::MAX);
+	assert_eq!(u64::from_key(0), 0x8000_0000_8000_0000);
+}
+
+#[test]
+fn test_names() {
+	for element in ElementType::ALL {
+		assert_eq!(ElementType::from_name(element.name()), Option::Some(*element));
+	}
+	assert_eq!(ElementType::from_name("f32"), Option::None);
+}
+
+#[test]
+fn test_records() {
+	let a = Record::<16>::from_key(0x01020304);
+	assert_eq!(a.payload[..8], [4, 3, 2, 1, 4, 3, 2, 1]);
+	// only the key is compared
+	let mut b = a;
+	b.payload[0] = 0;
+	assert!(a == b);
+	assert!(Record::<64>::from_key(-5) < Record::<64>::from_key(3));
+}
+
+#[test]
+fn test_registered_algorithms() {
+	// every registered algorithm on every element type it supports, including the payloads getting
+	// moved along with the keys
+	for distribution in &["uniform", "few-unique", "organ-pipe"] {
+		let keys = keys(distribution, 300);
+		for algorithm in registry::ALGORITHMS.iter() {
+			let (sorts, name) = (&algorithm.sorts, algorithm.name);
+			assert_sorts(Option::Some(sorts.i32), &keys, name);
+			assert_sorts(sorts.u8, &keys, name);
+			assert_sorts(sorts.u16, &keys, name);
+			assert_sorts(sorts.u64, &keys, name);
+			assert_sorts(sorts.u128, &keys, name);
+			assert_sorts(sorts.record16, &keys, name);
+			assert_sorts(sorts.record64, &keys, name);
+			if let Option::Some(sort) = sorts.record256 {
+				let mut array: Vec<Record<256>> = keys.iter().map(|k| Record::from_key(*k)).collect();
+				sort(&mut array);
+				assert!(array.iter().all(|r| r.payload == Record::<256>::from_key(r.key).payload), "{}", name);
+			}
+		}
+	}
+}
+
+#[test]
+fn test_i32_only() {
+	for algorithm in registry::ALGORITHMS.iter() {
+		let i32_only = ["algos::insertionsort_c", "algos::radixsort", "algos::cpp_std_sort"].contains(&algorithm.name);
+		for element in ElementType::ALL {
+			assert_eq!(algorithm.sorts.supports(*element), !i32_only || *element == ElementType::I32, "{}",
+					   algorithm.name);
+		}
+	}
+}
@@ -0,0 +1,140 @@
+use crate::BenchmarkResult;
+use crate::export;
+use crate::export::CellFailure;
+
+fn metadata() -> export::RunMetadata {
+	export::RunMetadata {
+		profile: String::from("quick"),
+		seed: 2222,
+		n_tests: 50,
+		min_size: 10,
+		max_size: 100,
+		distributions: vec![String::from("uniform"), String::from("sorted")],
+		elements: vec![String::from("i32"), String::from("record64")],
+		runtime_limit: 10_000_000_000,
+		n_workers: 2,
+		alpha: 0.001,
+		diff_threshold: 0.05,
+		outlier_coefficient: 3.0,
+		cpu: String::from("Some \"quoted\" cpu"),
+		rustc: String::from("rustc 1.0.0"),
+		runtime: 123
+	}
+}
+
+fn result() -> BenchmarkResult {
+	BenchmarkResult {
+		mean: 1500.0,
+		stdev: 10.0,
+		count: 31,
+		outliers: 2,
+		is_fastest: true,
+		is_stat_fastest: false
+	}
+}
+
+#[test]
+fn test_write_json() {
+	let r = result();
+	let cells = vec![
+		export::CellRecord {
+			algorithm: "algos::timsort", complexity: "O(n log n)", distribution: "uniform", element: "i32", size: 10,
+			result: Ok(&r)
+		},
+		export::CellRecord {
+			algorithm: "algos::radixsort", complexity: "O(n)", distribution: "sorted", element: "record64", size: 100,
+			result: Ok(&r)
+		}
+	];
+	let mut out = Vec::new();
+	export::write_json(&mut out, &metadata(), &cells).unwrap();
+	let out = String::from_utf8(out).unwrap();
+	assert!(out.contains("\"cpu\": \"Some \\\"quoted\\\" cpu\","));
+	assert!(out.contains("\"seed\": 2222,"));
+	assert!(out.contains("\"distributions\": [\"uniform\", \"sorted\"],"));
+	assert!(out.contains("{\"algorithm\": \"algos::timsort\", \"complexity\": \"O(n log n)\", \
+							\"distribution\": \"uniform\", \"element\": \"i32\", \"size\": 10, \
+							\"status\": \"ok\", \"mean_ns\": 1500, \"stdev_ns\": 10, \"count\": 31, "));
+	assert!(out.contains("\"elements\": [\"i32\", \"record64\"],"));
+	assert!(out.contains("\"outliers\": 2, \"is_fastest\": true, \"is_stat_fastest\": false},\n"));
+	// no trailing comma after the last cell
+	assert!(out.contains("\"is_stat_fastest\": false}\n\t]\n}\n"));
+}
+
+#[test]
+fn test_write_failed() {
+	let failure = CellFailure { test_i: 7, message: String::from("improperly sorted: [2, 1]"), timed_out: false };
+	let timeout = CellFailure { test_i: 0, message: String::from("timed out after 0.2s"), timed_out: true };
+	let cells = vec![
+		export::CellRecord {
+			algorithm: "algos::timsort", complexity: "O(n log n)", distribution: "uniform", element: "i32", size: 10,
+			result: Err(&failure)
+		},
+		export::CellRecord {
+			algorithm: "algos::radixsort", complexity: "O(n)", distribution: "uniform", element: "i32", size: 10,
+			result: Err(&timeout)
+		}
+	];
+	let mut out = Vec::new();
+	export::write_json(&mut out, &metadata(), &cells).unwrap();
+	let out = String::from_utf8(out).unwrap();
+	assert!(out.contains("\"size\": 10, \"status\": \"failed\", \"failed_test\": 7, \
+							\"failure\": \"improperly sorted: [2, 1]\"},\n"));
+	assert!(out.contains("\"status\": \"timed_out\", \"failed_test\": 0, \"failure\": \"timed out after 0.2s\"}\n"));
+	assert!(!out.contains("mean_ns"));
+	let mut out = Vec::new();
+	export::write_csv(&mut out, &metadata(), &cells).unwrap();
+	let out = String::from_utf8(out).unwrap();
+	let lines: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+	assert_eq!(lines[1], "algos::timsort,O(n log n),uniform,i32,10,,,,,,,,failed,7,\"improperly sorted: [2, 1]\"");
+	assert_eq!(lines[2], "algos::radixsort,O(n),uniform,i32,10,,,,,,,,timed_out,0,timed out after 0.2s");
+}
+
+#[test]
+fn test_write_non_finite() {
+	let r = BenchmarkResult { stdev: f64::NAN, ..result() };
+	let mut m = metadata();
+	m.outlier_coefficient = f64::INFINITY;
+	let cells = vec![
+		export::CellRecord {
+			algorithm: "algos::timsort", complexity: "O(n log n)", distribution: "uniform", element: "i32", size: 10,
+			result: Ok(&r)
+		}
+	];
+	let mut out = Vec::new();
+	export::write_json(&mut out, &m, &cells).unwrap();
+	let out = String::from_utf8(out).unwrap();
+	assert!(out.contains("\"mean_ns\": 1500, \"stdev_ns\": null, \"count\": 31, \"ci98_ns\": null,"));
+	assert!(out.contains("\"outlier_coefficient\": null,"));
+	assert!(!out.contains("NaN") && !out.contains("inf"));
+	// csv leaves the same statistics empty
+	let mut out = Vec::new();
+	export::write_csv(&mut out, &m, &cells).unwrap();
+	let out = String::from_utf8(out).unwrap();
+	let lines: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+	assert!(lines[1].starts_with("algos::timsort,O(n log n),uniform,i32,10,1500,,31,,"), "{}", lines[1]);
+}
+
+#[test]
+fn test_write_csv() {
+	let r = result();
+	let cells = vec![
+		export::CellRecord {
+			algorithm: "algos::timsort", complexity: "O(n log n)", distribution: "uniform", element: "i32", size: 10,
+			result: Ok(&r)
+		}
+	];
+	let mut out = Vec::new();
+	export::write_csv(&mut out, &metadata(), &cells).unwrap();
+	let out = String::from_utf8(out).unwrap();
+	let lines: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+	assert_eq!(lines.len(), 2);
+	assert_eq!(lines[0], "algorithm,complexity,distribution,element,size,mean_ns,stdev_ns,count,\
+							ci98_ns,outliers,is_fastest,is_stat_fastest,status,failed_test,failure");
+	let fields: Vec<&str> = lines[1].split(',').collect();
+	assert_eq!(fields[..8], ["algos::timsort", "O(n log n)", "uniform", "i32", "10", "1500", "10", "31"]);
+	assert_eq!(fields[9..], ["2", "true", "false", "ok", "", ""]);
+	assert!(out.contains("# n_workers: 2\n"));
+	// comma-separated inside a single metadata line
+	assert!(out.contains("# distributions: uniform,sorted\n"));
+}
@@ -0,0 +1,63 @@
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::cli;
+use crate::gap_search::{self, Score};
+
+#[test]
+fn test_score_names() {
+	for score in &[Score::Comparisons, Score::Time] {
+		assert_eq!(Score::from_name(score.name()), Ok(*score));
+	}
+	assert!(Score::from_name("speed").is_err());
+}
+
+#[test]
+fn test_initial() {
+	assert_eq!(gap_search::initial(1), vec![1]);
+	assert_eq!(gap_search::initial(20), vec![1, 4, 10]);
+	let sequence = gap_search::initial(1_000_000);
+	assert_eq!(&sequence[..12], &[1, 4, 10, 23, 57, 132, 301, 701, 1633, 3802, 8855, 20622]);
+	// 20622 * 2.25, ...
+	assert_eq!(sequence[12], 46399);
+	assert!(*sequence.last().unwrap() < 1_000_000 && *sequence.last().unwrap() * 9 / 4 >= 1_000_000);
+}
+
+#[test]
+fn test_mutate() {
+	let mut rng = SmallRng::seed_from_u64(13);
+	for max_size in &[1, 2, 10, 1000] {
+		let mut sequence = gap_search::initial(*max_size);
+		for _ in 0..1000 {
+			sequence = gap_search::mutate(&sequence, *max_size, &mut rng);
+			assert_eq!(sequence[0], 1);
+			assert!(sequence.windows(2).all(|w| w[0] < w[1]), "{:?}", sequence);
+			assert!(sequence.len() == 1 || *sequence.last().unwrap() < *max_size, "{:?}", sequence);
+		}
+	}
+}
+
+#[test]
+fn test_rust_const() {
+	assert_eq!(gap_search::rust_const("GAPS", &[1, 4, 10, 23, 57, 132]),
+			   "pub const GAPS: [usize; 6] = [\n\t132, 57, 23, 10,\n\t4, 1\n];");
+	assert_eq!(gap_search::rust_const("GAPS", &[1]), "pub const GAPS: [usize; 1] = [\n\t1\n];");
+}
+
+#[test]
+fn test_search() {
+	let mut config = cli::Config::from_profile(cli::Profile::Quick);
+	config.max_size = 1000;
+	config.iterations = 50;
+	config.distributions = vec![String::from("uniform"), String::from("reversed")];
+	let test_vectors = gap_search::test_vectors(&config);
+	// 2 distributions at 3 sizes
+	assert_eq!(test_vectors.len(), 6);
+	let initial: Vec<usize> = gap_search::initial(config.max_size).into_iter().rev().collect();
+	let initial_score = gap_search::score(Score::Comparisons, &test_vectors, |_| initial.clone());
+	let (sequence, score) = gap_search::search(&config, &test_vectors);
+	assert!(score <= initial_score);
+	assert_eq!(sequence[0], 1);
+	// comparison counts are exact so the search is reproducible
+	assert_eq!(gap_search::search(&config, &test_vectors), (sequence, score));
+}
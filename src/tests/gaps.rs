@@ -0,0 +1,105 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::algos;
+use crate::cli;
+use crate::gaps::{self, Generator, HMax, Sequence};
+use crate::utils;
+
+#[test]
+fn test_h_max() {
+	assert_eq!(HMax::parse("n"), Ok(HMax::Fraction(1)));
+	assert_eq!(HMax::parse("n/3"), Ok(HMax::Fraction(3)));
+	assert_eq!(HMax::parse("1000"), Ok(HMax::Absolute(1000)));
+	for bad in &["", "n/0", "n/", "m/2", "n/x", "-5"] {
+		assert!(HMax::parse(bad).is_err(), "{}", bad);
+	}
+	assert_eq!(HMax::Fraction(1).cap(1000), 1000);
+	assert_eq!(HMax::Fraction(3).cap(1000), 333);
+	assert_eq!(HMax::Absolute(50).cap(1000), 50);
+	for name in &["n", "n/3", "1000"] {
+		assert_eq!(&HMax::parse(name).unwrap().name(), name);
+	}
+}
+
+#[test]
+fn test_sequence_gaps() {
+	let sequence = |generator| Sequence { name: String::from("test"), generator };
+	let knuth = sequence(Generator::Function(algos::knuth_gap));
+	assert_eq!(knuth.gaps(100), vec![40, 13, 4, 1]);
+	assert_eq!(knuth.gaps(40), vec![40, 13, 4, 1]);
+	assert_eq!(knuth.gaps(0), vec![1]);
+	let tokuda = sequence(Generator::Function(algos::tokuda_gap));
+	assert_eq!(tokuda.gaps(100), vec![46, 20, 9, 4, 1]);
+	let gonnet_baeza = sequence(Generator::Recursive(algos::gonnet_baeza_next));
+	assert_eq!(gonnet_baeza.gaps(100), vec![100, 45, 20, 9, 4, 1]);
+	assert_eq!(gonnet_baeza.gaps(1), vec![1]);
+	let fixed = sequence(Generator::Fixed(vec![1, 23, 10, 4]));
+	assert_eq!(fixed.gaps(15), vec![10, 4, 1]);
+	// e.g. --h-max 18446744073709551615, generating stops before the gaps overflow
+	let cap = HMax::parse(&usize::MAX.to_string()).unwrap().cap(1000);
+	for (name, sequence) in gaps::builtin() {
+		let gaps = sequence.gaps(cap);
+		assert!(gaps.windows(2).all(|w| w[0] > w[1]) && gaps.last() == Option::Some(&1), "{}", name);
+	}
+	assert_eq!(knuth.gaps(cap)[0], algos::knuth_gap(36));
+}
+
+#[test]
+fn test_builtin_sort() {
+	let mut rng = SmallRng::seed_from_u64(12);
+	for (name, sequence) in gaps::builtin() {
+		for size in &[0, 1, 2, 3, 100, 1000] {
+			for h_max in &[HMax::Fraction(1), HMax::Fraction(4), HMax::Absolute(0), HMax::Absolute(5000)] {
+				let mut array: Vec<i32> = (0..*size).map(|_| (rng.next_u32() % 100) as i32).collect();
+				let gaps = sequence.gaps(h_max.cap(*size));
+				assert!(gaps.windows(2).all(|w| w[0] > w[1]) && gaps.last() == Option::Some(&1), "{}", name);
+				algos::shell_sequence(&mut array, &gaps);
+				utils::verify_sorted(&array);
+			}
+		}
+	}
+}
+
+#[test]
+fn test_parse_sequences() {
+	let sequences = gaps::parse_sequences("# comment\n\nciura_ext: 1, 4, 10, 23\n 31 15,7 3  1\n").unwrap();
+	assert_eq!(sequences.len(), 2);
+	assert_eq!(sequences[0].name, "ciura_ext");
+	assert_eq!(sequences[0].gaps(usize::MAX), vec![23, 10, 4, 1]);
+	assert_eq!(sequences[1].name, "line 4");
+	assert_eq!(sequences[1].gaps(usize::MAX), vec![31, 15, 7, 3, 1]);
+	assert!(gaps::parse_sequences("2, 4, 8").is_err());
+	assert!(gaps::parse_sequences("1, 0, 8").is_err());
+	assert!(gaps::parse_sequences("a: 1, x").is_err());
+	assert!(gaps::parse_sequences("# nothing\n").is_err());
+}
+
+#[test]
+fn test_selection() {
+	let config = cli::Config::from_profile(cli::Profile::Full);
+	assert_eq!(gaps::selection(&config).len(), 6);
+	let mut config = cli::Config::from_profile(cli::Profile::Full);
+	config.algorithms = vec![String::from("shellsort_tokuda")];
+	let names: Vec<String> = gaps::selection(&config).into_iter().map(|s| s.name).collect();
+	assert_eq!(names, vec![String::from("tokuda")]);
+	config.algorithms = vec![String::from("heapsort_*")];
+	assert!(gaps::selection(&config).is_empty());
+}
+
+#[test]
+fn test_run() {
+	let mut config = cli::Config::from_profile(cli::Profile::Quick);
+	config.max_size = 100;
+	config.n_tests = 3;
+	config.h_max = vec![HMax::Fraction(2), HMax::Absolute(10)];
+	config.distributions = vec![String::from("uniform"), String::from("reversed")];
+	let mut sequences = gaps::selection(&config);
+	sequences.extend(gaps::parse_sequences("1 3 7").unwrap());
+	assert_eq!(gaps::labels(&config, &sequences).len(), sequences.len() * 2);
+	let explorations = gaps::run(&config, &sequences);
+	// 2 distributions at 2 sizes
+	assert_eq!(explorations.len(), 4);
+	assert!(explorations.iter().all(|e| e.samples.len() == sequences.len() * 2
+										&& e.samples.iter().all(|s| s.len() == 3)));
+}
@@ -0,0 +1,95 @@
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use crate::corpus::Array;
+use crate::inputs::{self, Source};
+
+fn generate(name: &str, size: usize, seed: u64) -> Vec<i32> {
+	let mut array = vec![0; size];
+	let mut rng = SmallRng::seed_from_u64(seed);
+	(inputs::DISTRIBUTIONS[inputs::find(name).unwrap()].1)(&mut array, &mut rng);
+	array
+}
+
+fn distinct(array: &[i32]) -> usize {
+	let mut copy = array.to_vec();
+	copy.sort_unstable();
+	copy.dedup();
+	copy.len()
+}
+
+#[test]
+fn test_deterministic() {
+	for (name, _, _) in inputs::DISTRIBUTIONS {
+		for size in &[0, 1, 2, 10, 1000] {
+			assert_eq!(generate(name, *size, 7), generate(name, *size, 7), "{} {}", name, size);
+		}
+	}
+}
+
+#[test]
+fn test_shapes() {
+	let n = 10_000;
+	let sorted = generate("sorted", n, 1);
+	assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+	let reversed = generate("reversed", n, 1);
+	assert!(reversed.windows(2).all(|w| w[0] >= w[1]));
+	// at most 2 elements out of place per swap
+	let nearly = generate("nearly-sorted", n, 1);
+	let out_of_place = nearly.iter().zip(sorted.iter()).filter(|(a, b)| a != b).count();
+	assert!(out_of_place > 0 && out_of_place <= 2 * n / 100);
+	assert!(distinct(&generate("few-unique", n, 1)) <= 8);
+	assert_eq!(distinct(&generate("all-equal", n, 1)), 1);
+	let pipe = generate("organ-pipe", n, 1);
+	assert!(pipe[..n / 2].windows(2).all(|w| w[0] <= w[1]));
+	assert!(pipe[n / 2..].windows(2).all(|w| w[0] >= w[1]));
+	let saw = generate("sawtooth", n, 1);
+	assert_eq!(saw.windows(2).filter(|w| w[0] > w[1]).count(), 99);
+	let runs = generate("random-runs", n, 1);
+	let descents = runs.windows(2).filter(|w| w[0] > w[1]).count();
+	assert!(descents > 10 && descents < n / 10);
+	// the most common value should be rank 1 (the smallest) and take up a good chunk of the array
+	let zipf = generate("zipf", n, 1);
+	let ones = zipf.iter().filter(|x| **x == i32::MIN).count();
+	assert!(ones > n / 20 && ones < n / 5);
+	assert!(distinct(&zipf) < n / 2);
+	// structured inputs use the whole range so they survive being narrowed to small element types
+	assert_eq!(*pipe.iter().min().unwrap(), i32::MIN);
+	assert_eq!(*pipe.iter().max().unwrap(), i32::MAX);
+	assert_eq!(*saw.iter().max().unwrap(), i32::MAX);
+}
+
+#[test]
+fn test_uniform_unchanged() {
+	// uniform has to match what run_bench generated before distributions existed so old results
+	// stay comparable
+	use rand::RngCore;
+	let mut rng = SmallRng::seed_from_u64(3);
+	let expected: Vec<i32> = (0..100).map(|_| rng.next_u32() as i32).collect();
+	assert_eq!(generate("uniform", 100, 3), expected);
+}
+
+#[test]
+fn test_recorded_source() {
+	let array = |name: &str, test_i, keys: Vec<i32>| Array { name: String::from(name), test_i, keys };
+	let sources = Source::from_corpus(vec![
+		array("prices", 1, vec![4, 5]),
+		array("sorted", 0, vec![1, 2, 3]),
+		array("prices", 0, vec![6, 7]),
+		array("prices", 0, vec![1, 2, 3])
+	]);
+	let names: Vec<&str> = sources.iter().map(|s| s.name()).collect();
+	assert_eq!(names, vec!["prices", "sorted"]);
+	// grouped by length in test index order
+	assert_eq!(sources[0].recorded_sizes(), vec![(2, 2), (3, 1)]);
+	let mut rng = SmallRng::seed_from_u64(0);
+	assert_eq!(sources[0].keys(2, 0, &mut rng), vec![6, 7]);
+	assert_eq!(sources[0].keys(2, 1, &mut rng), vec![4, 5]);
+	assert!(sources[0].has_size(3) && !sources[0].has_size(4));
+	// degenerate like the distribution with the same name
+	assert!(!sources[0].degenerate() && sources[1].degenerate());
+	let generated = Source::Generated(inputs::find("zipf").unwrap());
+	assert_eq!(generated.name(), "zipf");
+	assert!(generated.has_size(12345) && generated.recorded_sizes().is_empty());
+	assert_eq!(generated.keys(100, 0, &mut SmallRng::seed_from_u64(7)), generate("zipf", 100, 7));
+}
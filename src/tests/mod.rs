@@ -1,4 +1,23 @@
 #![cfg(test)]
+mod cli;
+mod compare;
+mod comparators;
+mod complexity;
+mod corpus;
 mod correctness;
+mod datasets;
+mod differential;
+mod elements;
+mod export;
+mod gap_search;
+mod gaps;
+mod inputs;
+mod non_copy;
+mod operations;
+mod registry;
+mod samples;
 mod statistics;
+#[cfg(unix)]
+mod subprocess;
+mod sweep;
 mod utils;
@@ -0,0 +1,137 @@
+use std::cell::Cell;
+use std::panic;
+use std::rc::Rc;
+
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::algos;
+
+type NamedSort<T> = (&'static str, fn(&mut [T]));
+
+fn move_sorts<T: Ord>() -> Vec<NamedSort<T>> {
+	vec![
+		("insertionsort_move", algos::insertionsort_move),
+		("mergesort_move", algos::mergesort_move),
+		("heapsort_move", algos::heapsort_move),
+		("quicksort_move", algos::quicksort_move),
+		("introsort_move", algos::introsort_move),
+		("timsort_move", algos::timsort_move)
+	]
+}
+
+fn random_keys(size: usize, range: u32) -> Vec<u32> {
+	let mut rng = SmallRng::seed_from_u64(0x5EED);
+	(0..size).map(|_| rng.next_u32() % range).collect()
+}
+
+#[test]
+fn test_strings() {
+	for size in &[0, 1, 2, 10, 100, 1000] {
+		let strings: Vec<String> = random_keys(*size, 100).iter().map(|k| format!("key {}", k)).collect();
+		let mut expected = strings.clone();
+		expected.sort();
+		for (name, sort) in move_sorts::<String>() {
+			let mut array = strings.clone();
+			sort(&mut array);
+			assert_eq!(array, expected, "{}", name);
+		}
+	}
+}
+
+#[test]
+fn test_vecs_and_boxes() {
+	let keys = random_keys(500, 50);
+	let vecs: Vec<Vec<u8>> = keys.iter().map(|k| k.to_le_bytes().to_vec()).collect();
+	let boxes: Vec<Box<u32>> = keys.iter().map(|k| Box::new(*k)).collect();
+	for ((name, sort_vecs), (_, sort_boxes)) in move_sorts::<Vec<u8>>().into_iter().zip(move_sorts::<Box<u32>>()) {
+		let mut array = vecs.clone();
+		sort_vecs(&mut array);
+		assert!(array.windows(2).all(|w| w[0] <= w[1]), "{}", name);
+		let mut array = boxes.clone();
+		sort_boxes(&mut array);
+		assert!(array.windows(2).all(|w| w[0] <= w[1]), "{}", name);
+	}
+}
+
+// counts its drops and panics on the comparison number panic_at
+struct Tracked {
+	key: u32,
+	// position in the unsorted array, to check nothing was duplicated or lost
+	id: usize,
+	drops: Rc<Cell<usize>>,
+	comparisons: Rc<Cell<usize>>,
+	panic_at: usize
+}
+
+impl Drop for Tracked {
+	fn drop(&mut self) {
+		self.drops.set(self.drops.get() + 1);
+	}
+}
+
+impl PartialEq for Tracked {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == std::cmp::Ordering::Equal
+	}
+}
+
+impl Eq for Tracked {}
+
+impl PartialOrd for Tracked {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Option::Some(self.cmp(other))
+	}
+}
+
+impl Ord for Tracked {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.comparisons.set(self.comparisons.get() + 1);
+		if self.comparisons.get() == self.panic_at {
+			panic!("comparison {}", self.panic_at);
+		}
+		self.key.cmp(&other.key)
+	}
+}
+
+// sorts with a comparison that panics partway through, returns whether it panicked and how many
+// comparisons were made. whether or not it panicked, every element has to still be in the array exactly once and be dropped
+// exactly once along with it
+fn sort_tracked(sort: fn(&mut [Tracked]), keys: &[u32], panic_at: usize, name: &str) -> (bool, usize) {
+	let drops = Rc::new(Cell::new(0));
+	let comparisons = Rc::new(Cell::new(0));
+	let mut array: Vec<Tracked> = keys.iter().enumerate().map(|(id, key)| Tracked {
+		key: *key,
+		id,
+		drops: drops.clone(),
+		comparisons: comparisons.clone(),
+		panic_at
+	}).collect();
+	let panicked = panic::catch_unwind(panic::AssertUnwindSafe(|| sort(&mut array))).is_err();
+	assert_eq!(drops.get(), 0, "{} dropped an element while sorting (panic at {})", name, panic_at);
+	let mut ids: Vec<usize> = array.iter().map(|t| t.id).collect();
+	ids.sort_unstable();
+	assert!(ids.iter().copied().eq(0..keys.len()), "{} lost or duplicated an element (panic at {})", name,
+			panic_at);
+	drop(array);
+	assert_eq!(drops.get(), keys.len(), "{} (panic at {})", name, panic_at);
+	(panicked, comparisons.get())
+}
+
+#[test]
+fn test_panic_safety() {
+	for size in &[2, 3, 10, 50, 200] {
+		for range in &[3, 1000] {
+			let keys = random_keys(*size, *range);
+			for (name, sort) in move_sorts::<Tracked>() {
+				let (panicked, comparisons) = sort_tracked(sort, &keys, usize::MAX, name);
+				assert!(!panicked);
+				// panic at 50 or so points spread over the sort
+				let step = std::cmp::max(comparisons / 50, 1);
+				for panic_at in (1..=comparisons).step_by(step) {
+					assert!(sort_tracked(sort, &keys, panic_at, name).0, "{} didn't panic at {}", name, panic_at);
+				}
+			}
+		}
+	}
+}
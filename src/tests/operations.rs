@@ -0,0 +1,92 @@
+use crate::algos;
+use crate::cli;
+use crate::operations::{self, Counted, Counts};
+use crate::BenchmarkManager;
+
+#[test]
+fn test_count() {
+	let sorted: Vec<i32> = (0..100).collect();
+	let reversed: Vec<i32> = (0..100).rev().collect();
+	let comparisons = |sort: fn(&mut [Counted]), keys: &[i32]| operations::count(sort, keys).comparisons;
+	// insertionsort is easy to count by hand
+	assert_eq!(comparisons(algos::insertionsort::<Counted>, &sorted), 99);
+	// the check before the inner loop repeats its first comparison
+	assert_eq!(comparisons(algos::insertionsort::<Counted>, &reversed), 99 * 100 / 2 + 99);
+	assert_eq!(comparisons(algos::selectionsort::<Counted>, &sorted), 99 * 100 / 2);
+	// the counter starts over every time
+	assert_eq!(comparisons(algos::insertionsort::<Counted>, &sorted), 99);
+	assert_eq!(comparisons(algos::insertionsort::<Counted>, &[]), 0);
+	// copies can't be counted
+	assert_eq!(operations::count(algos::insertionsort::<Counted>, &reversed).moves, 0);
+}
+
+#[test]
+fn test_count_moves() {
+	let sorted: Vec<i32> = (0..100).collect();
+	let reversed: Vec<i32> = (0..100).rev().collect();
+	// nothing to move on sorted input. on reversed input element i is read out, shifted past all i
+	// before it, and written back
+	assert_eq!(operations::count(algos::insertionsort_move_counted, &sorted),
+			   Counts { comparisons: 99, moves: 0, swaps: 0 });
+	assert_eq!(operations::count(algos::insertionsort_move_counted, &reversed),
+			   Counts { comparisons: 99 * 100 / 2, moves: 99 * 100 / 2 + 2 * 99, swaps: 0 });
+	// every element is less than the last one, so each partition swaps everything and the pivot ends
+	// up last
+	assert_eq!(operations::count(algos::quicksort_move_counted, &sorted[..10]).swaps, (2..=10).sum::<u64>());
+	// a single descending run that's reversed in place, its first comparison is made twice
+	assert_eq!(operations::count(algos::timsort_move_counted, &reversed),
+			   Counts { comparisons: 100, moves: 0, swaps: 50 });
+	// the left element goes out to the buffer and back
+	assert_eq!(operations::count(algos::mergesort_move_counted, &[1, 2]),
+			   Counts { comparisons: 1, moves: 2, swaps: 0 });
+	// the counted instantiations make the same comparisons as the sorts themselves
+	let keys: Vec<i32> = (0..1000).map(|i| (i * 7919) % 1000).collect();
+	let pairs: &[(fn(&mut [Counted]), fn(&mut [Counted]))] = &[
+		(algos::insertionsort_move::<Counted>, algos::insertionsort_move_counted),
+		(algos::mergesort_move::<Counted>, algos::mergesort_move_counted),
+		(algos::heapsort_move::<Counted>, algos::heapsort_move_counted),
+		(algos::quicksort_move::<Counted>, algos::quicksort_move_counted),
+		(algos::introsort_move::<Counted>, algos::introsort_move_counted),
+		(algos::timsort_move::<Counted>, algos::timsort_move_counted)
+	];
+	for (sort, counted) in pairs {
+		let counts = operations::count(*counted, &keys);
+		assert_eq!(operations::count(*sort, &keys), Counts { comparisons: counts.comparisons, moves: 0, swaps: 0 });
+		assert!(counts.moves + counts.swaps > 0);
+	}
+}
+
+#[test]
+fn test_mean() {
+	let counts = [Counts { comparisons: 1, moves: 2, swaps: 3 }, Counts { comparisons: 2, moves: 4, swaps: 6 }];
+	assert_eq!(operations::mean(&counts), operations::Means { comparisons: 1.5, moves: 3.0, swaps: 4.5 });
+}
+
+#[test]
+fn test_per_n_log_n() {
+	assert_eq!(operations::per_n_log_n(1024.0 * 10.0, 1024), 1.0);
+	assert_eq!(operations::per_n_log_n(0.0, 1), 0.0);
+}
+
+#[test]
+fn test_count_operations() {
+	let mut config = cli::Config::from_profile(cli::Profile::Quick);
+	config.max_size = 100;
+	config.algorithms = vec![String::from("insertionsort*"), String::from("radixsort")];
+	config.distributions = vec![String::from("sorted")];
+	let manager = BenchmarkManager::new(config);
+	let counts = manager.count_operations(0);
+	let insertionsort = manager.algorithms.iter().position(|a| a.name == "algos::insertionsort").unwrap();
+	let comparisons = |i: usize| counts[i].iter().map(|c| c.map(|c| c.comparisons)).collect::<Vec<Option<f64>>>();
+	assert_eq!(comparisons(insertionsort), vec![Option::Some(9.0), Option::Some(99.0)]);
+	// the move-based variant counts the same comparisons, and moves, nothing needs moving on sorted input
+	let insertionsort_move = manager.algorithms.iter().position(|a| a.name == "algos::insertionsort_move").unwrap();
+	assert!(manager.algorithms[insertionsort_move].counts_moves && !manager.algorithms[insertionsort].counts_moves);
+	assert_eq!(comparisons(insertionsort_move), comparisons(insertionsort));
+	assert!(counts[insertionsort_move].iter().all(|c| c.unwrap().moves == 0.0));
+	// radixsort doesn't compare elements and insertionsort_c can't be instrumented
+	for name in &["algos::radixsort", "algos::insertionsort_c", "algos::bubblesort"] {
+		let i = manager.algorithms.iter().position(|a| a.name == *name).unwrap();
+		assert!(counts[i].iter().all(|c| c.is_none()), "{}", name);
+	}
+}
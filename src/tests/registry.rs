@@ -0,0 +1,76 @@
+use crate::cli;
+use crate::complexity::Model;
+use crate::registry::{self, Complexity, Family};
+use crate::BenchmarkManager;
+
+#[test]
+fn test_names() {
+	for (i, a) in registry::ALGORITHMS.iter().enumerate() {
+		assert!(a.name.starts_with("algos::") || a.name.starts_with("odd_algos::"), "{}", a.name);
+		assert!(registry::ALGORITHMS[..i].iter().all(|b| b.name != a.name), "{}", a.name);
+		assert_eq!(registry::find(a.name).unwrap().name, a.name);
+	}
+	assert!(registry::find("heapsort_top_down").is_none());
+	assert!(registry::find("algos::nonexistent").is_none());
+	// odd algorithms are listed after the rest
+	let first_secondary = registry::ALGORITHMS.iter().position(|a| a.secondary).unwrap();
+	assert!(registry::ALGORITHMS[first_secondary..].iter().all(|a| a.secondary));
+}
+
+#[test]
+fn test_metadata() {
+	let mergesort = registry::find("algos::mergesort").unwrap();
+	assert_eq!(mergesort.family, Family::Merge);
+	assert_eq!(mergesort.complexity.label(), "O(n log n)");
+	assert_eq!(mergesort.memory.label(), "O(n)");
+	assert!(mergesort.stable && !mergesort.adaptive && !mergesort.secondary);
+	let heapsort = registry::find("algos::heapsort_top_down").unwrap();
+	assert!(!heapsort.stable && !heapsort.adaptive);
+	assert!(registry::find("odd_algos::weird").unwrap().secondary);
+	assert_eq!(Complexity::FourThirds.label(), "O(n^(4/3))");
+	assert_eq!(Complexity::Linearithmic.model(), Model { exponent: 1.0, log: true });
+	assert_eq!(Complexity::ThreeHalves.model(), Model { exponent: 1.5, log: false });
+}
+
+#[test]
+fn test_size_limit() {
+	let bubblesort = registry::find("algos::bubblesort").unwrap();
+	assert_eq!(bubblesort.size_limit(false), 10_000);
+	assert_eq!(bubblesort.size_limit(true), 10_000);
+	// plain quicksorts are only limited on degenerate inputs, introsort never is
+	let quicksort = registry::find("algos::quicksort_end").unwrap();
+	assert_eq!(quicksort.size_limit(false), usize::MAX);
+	assert_eq!(quicksort.size_limit(true), 10_000);
+	assert_eq!(registry::find("algos::introsort").unwrap().size_limit(true), usize::MAX);
+}
+
+#[test]
+fn test_groups() {
+	let selection = |group: &str| {
+		let mut config = cli::Config::from_profile(cli::Profile::Full);
+		config.groups = vec![String::from(group)];
+		registry::ALGORITHMS.iter().map(|a| a.name).filter(|name| BenchmarkManager::is_selected(&config, name))
+								   .collect::<Vec<&str>>()
+	};
+	// one group per family, the tables that put related families together don't leak into the selection
+	let shellsorts = selection("shellsort");
+	assert!(shellsorts.contains(&"algos::shellsort_ciura") && !shellsorts.contains(&"algos::insertionsort"));
+	assert!(!selection("selectionsort").contains(&"algos::insertionsort"));
+	assert_eq!(selection("heapsort"), vec!["algos::heapsort_top_down", "algos::heapsort_bottom_up",
+										   "algos::heapsort_bottom_up_optimized", "algos::heapsort_move"]);
+	let quicksorts = selection("quicksort");
+	assert!(quicksorts.iter().all(|name| name.starts_with("algos::quicksort")));
+	assert_eq!(selection("introsort"), vec!["algos::introsort", "algos::introsort_move"]);
+	assert_eq!(selection("timsort"), vec!["algos::timsort", "algos::timsort_move"]);
+	assert_eq!(selection("radixsort"), vec!["algos::radixsort"]);
+	assert!(selection("library").contains(&"algos::rustsort"));
+	for family in Family::ALL {
+		assert_eq!(Family::from_name(family.name()), Option::Some(*family));
+		assert!(!selection(family.name()).is_empty(), "{}", family.name());
+	}
+	assert_eq!(Family::from_name("quicksorts"), Option::None);
+	// groups only know about registered algorithms
+	let mut config = cli::Config::from_profile(cli::Profile::Full);
+	config.groups = vec![String::from("mergesort")];
+	assert!(!BenchmarkManager::is_selected(&config, "algos::mergesort_unregistered"));
+}
@@ -0,0 +1,199 @@
+use crate::export::{CellFailure, RunMetadata};
+use crate::samples;
+
+fn metadata() -> RunMetadata {
+	RunMetadata {
+		profile: String::from("full"),
+		seed: 2222,
+		n_tests: 3,
+		min_size: 10,
+		max_size: 100,
+		distributions: vec![String::from("uniform"), String::from("zipf")],
+		elements: vec![String::from("i32"), String::from("u8")],
+		runtime_limit: 10_000_000_000,
+		n_workers: 4,
+		alpha: 0.001,
+		diff_threshold: 0.05,
+		outlier_coefficient: 3.0,
+		cpu: String::from("ARMv6-compatible\tprocessor"),
+		rustc: String::from("rustc 1.0.0"),
+		runtime: 5
+	}
+}
+
+#[test]
+fn test_round_trip() {
+	let cells = vec![
+		samples::SampleCell {
+			algorithm: String::from("algos::timsort"),
+			complexity: String::from("O(n log n)"),
+			distribution: String::from("zipf"),
+			element: String::from("u8"),
+			size: 100,
+			samples: vec![300, 200, 100],
+			failure: Option::None
+		},
+		samples::SampleCell {
+			algorithm: String::from("algos::bubblesort"),
+			complexity: String::from("O(n^2)"),
+			distribution: String::from("uniform"),
+			element: String::from("i32"),
+			size: 10,
+			samples: vec![1, 2],
+			failure: Option::Some(CellFailure {
+				test_i: 2,
+				message: String::from("improperly sorted:\t[2, 1]"),
+				timed_out: false
+			})
+		}
+	];
+	let mut out = Vec::new();
+	samples::write(&mut out, &metadata(), &cells).unwrap();
+	let file = samples::read(&out[..]).unwrap();
+	assert_eq!(file.metadata.seed, 2222);
+	assert_eq!(file.metadata.n_workers, 4);
+	assert_eq!(file.metadata.distributions, vec!["uniform", "zipf"]);
+	assert_eq!(file.metadata.elements, vec!["i32", "u8"]);
+	assert_eq!(file.metadata.outlier_coefficient, 3.0);
+	// tabs would break the format and are replaced
+	assert_eq!(file.metadata.cpu, "ARMv6-compatible processor");
+	assert_eq!(file.cells.len(), 2);
+	assert_eq!(file.cells[0].algorithm, "algos::timsort");
+	assert_eq!(file.cells[0].complexity, "O(n log n)");
+	assert_eq!(file.cells[0].distribution, "zipf");
+	assert_eq!(file.cells[0].element, "u8");
+	assert_eq!(file.cells[0].size, 100);
+	assert_eq!(file.cells[0].samples, vec![300, 200, 100]);
+	assert_eq!(file.cells[0].failure, Option::None);
+	assert_eq!(file.cells[1].samples, vec![1, 2]);
+	assert_eq!(file.cells[1].failure, Option::Some(CellFailure {
+		test_i: 2,
+		message: String::from("improperly sorted: [2, 1]"),
+		timed_out: false
+	}));
+	assert_eq!(file.sizes(), vec![10, 100]);
+	assert!(file.jobs.is_empty());
+}
+
+#[test]
+fn test_checkpoint() {
+	let cells = vec![
+		samples::SampleCell {
+			algorithm: String::from("algos::timsort"),
+			complexity: String::from("O(n log n)"),
+			distribution: String::from("sorted"),
+			element: String::from("i32"),
+			size: 10,
+			samples: vec![3, 2],
+			failure: Option::None
+		}
+	];
+	let jobs = vec![
+		samples::PendingJob {
+			algorithm: String::from("algos::timsort"),
+			distribution: String::from("sorted"),
+			element: String::from("i32"),
+			size: 100,
+			test_i: 7
+		},
+		samples::PendingJob {
+			algorithm: String::from("algos::timsort"),
+			distribution: String::from("uniform"),
+			element: String::from("i32"),
+			size: 10,
+			test_i: 0
+		}
+	];
+	let mut out = Vec::new();
+	samples::write_checkpoint(&mut out, &metadata(), &cells, &jobs).unwrap();
+	let file = samples::read(&out[..]).unwrap();
+	assert_eq!(file.cells.len(), 1);
+	// order matters, it's the job stack
+	assert_eq!(file.jobs.len(), 2);
+	assert_eq!(file.jobs[0].size, 100);
+	assert_eq!(file.jobs[0].test_i, 7);
+	assert_eq!(file.jobs[0].distribution, "sorted");
+	assert_eq!(file.jobs[1].algorithm, "algos::timsort");
+	assert_eq!(file.jobs[1].test_i, 0);
+	out.extend_from_slice(b"job\talgos::timsort\tuniform\ti32\t10\n");
+	assert!(samples::read(&out[..]).is_err());
+}
+
+#[test]
+fn test_bad_files() {
+	assert!(samples::read(&b"algorithm,complexity\n"[..]).is_err());
+	// missing metadata
+	assert!(samples::read(&b"sorting-benchmarks samples 1\n"[..]).is_err());
+	let mut out = Vec::new();
+	samples::write(&mut out, &metadata(), &[]).unwrap();
+	let mut bad_samples = out.clone();
+	bad_samples.extend_from_slice(b"cell\talgos::timsort\tO(n log n)\tuniform\ti32\t10\t1 2 x\n");
+	assert!(samples::read(&bad_samples[..]).is_err());
+	let mut bad_status = out.clone();
+	bad_status.extend_from_slice(b"cell\talgos::timsort\tO(n log n)\tuniform\ti32\t10\t1 2\tbroken\t3\toops\n");
+	assert!(samples::read(&bad_status[..]).is_err());
+	let mut no_message = out;
+	no_message.extend_from_slice(b"cell\talgos::timsort\tO(n log n)\tuniform\ti32\t10\t1 2\tfailed\t3\n");
+	assert!(samples::read(&no_message[..]).is_err());
+}
+
+#[test]
+fn test_version_3() {
+	// files written before failed cells were saved
+	let mut out = Vec::new();
+	samples::write(&mut out, &metadata(), &[]).unwrap();
+	let old = String::from_utf8(out).unwrap().replace("sorting-benchmarks samples 4", "sorting-benchmarks samples 3");
+	let cell = "cell\talgos::timsort\tO(n log n)\tzipf\tu8\t10\t1 2 3\n";
+	let file = samples::read((old.clone() + cell).as_bytes()).unwrap();
+	assert_eq!(file.cells[0].samples, vec![1, 2, 3]);
+	assert_eq!(file.cells[0].failure, Option::None);
+	let failed = "cell\talgos::timsort\tO(n log n)\tzipf\tu8\t10\t1 2 3\tfailed\t3\toops\n";
+	assert!(samples::read((old + failed).as_bytes()).is_err());
+}
+
+#[test]
+fn test_version_1() {
+	// files written before input distributions were added
+	let mut out = Vec::new();
+	samples::write(&mut out, &metadata(), &[]).unwrap();
+	let old: String = String::from_utf8(out).unwrap()
+									   .replace("sorting-benchmarks samples 4", "sorting-benchmarks samples 1")
+									   .lines()
+									   .filter(|l| !l.starts_with("meta\tdistributions")
+												   && !l.starts_with("meta\telements"))
+									   .map(|l| format!("{}\n", l))
+									   .collect();
+	let old = old + "cell\talgos::timsort\tO(n log n)\t10\t1 2 3\njob\talgos::timsort\t100\t4\n";
+	let file = samples::read(old.as_bytes()).unwrap();
+	assert_eq!(file.metadata.distributions, vec!["uniform"]);
+	assert_eq!(file.metadata.elements, vec!["i32"]);
+	assert_eq!(file.cells[0].distribution, "uniform");
+	assert_eq!(file.cells[0].element, "i32");
+	assert_eq!(file.cells[0].size, 10);
+	assert_eq!(file.cells[0].samples, vec![1, 2, 3]);
+	assert_eq!(file.jobs[0].distribution, "uniform");
+	assert_eq!(file.jobs[0].size, 100);
+	assert_eq!(file.jobs[0].test_i, 4);
+}
+
+#[test]
+fn test_version_2() {
+	// files written before element types were added
+	let mut out = Vec::new();
+	samples::write(&mut out, &metadata(), &[]).unwrap();
+	let old: String = String::from_utf8(out).unwrap()
+									   .replace("sorting-benchmarks samples 4", "sorting-benchmarks samples 2")
+									   .lines()
+									   .filter(|l| !l.starts_with("meta\telements"))
+									   .map(|l| format!("{}\n", l))
+									   .collect();
+	let old = old + "cell\talgos::timsort\tO(n log n)\tzipf\t10\t1 2 3\njob\talgos::timsort\tsorted\t100\t4\n";
+	let file = samples::read(old.as_bytes()).unwrap();
+	assert_eq!(file.metadata.elements, vec!["i32"]);
+	assert_eq!(file.cells[0].distribution, "zipf");
+	assert_eq!(file.cells[0].element, "i32");
+	assert_eq!(file.cells[0].samples, vec![1, 2, 3]);
+	assert_eq!(file.jobs[0].distribution, "sorted");
+	assert_eq!(file.jobs[0].element, "i32");
+	assert_eq!(file.jobs[0].size, 100);
+}
@@ -86,3 +86,15 @@ fn test_t_lookup() {
 	assert_eq!(statistics::t_lookup(72), 2.374);
 	assert_eq!(statistics::t_lookup(124124), 2.326);
 }
+
+#[test]
+fn test_bootstrap_argmin() {
+	use rand::SeedableRng;
+	let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+	let noisy = |base: u64| (0..50).map(|i| base + i % 10).collect::<Vec<u64>>();
+	let groups = vec![noisy(300), noisy(100), noisy(200)];
+	assert_eq!(statistics::bootstrap_argmin(&groups, 0.98, 500, &mut rng), (1, 1));
+	// a tie, either one can come out fastest
+	let groups = vec![noisy(300), noisy(100), noisy(100), Vec::new()];
+	assert_eq!(statistics::bootstrap_argmin(&groups, 0.98, 500, &mut rng), (1, 2));
+}
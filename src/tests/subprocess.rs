@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+use crate::subprocess::{self, Worker};
+
+// test_i picks what the job does: 0 returns the algorithm index, 1 panics (caught, the way run_job
+// does), 2 never finishes, 3 kills the process
+fn run(job: (usize, usize, usize, usize)) -> Result<u64, String> {
+	match job.3 {
+		0 => Ok(job.0 as u64),
+		1 => Err(String::from("improperly sorted")),
+		2 => loop {
+			std::thread::sleep(Duration::from_millis(10));
+		},
+		_ => {
+			unsafe { libc::raise(libc::SIGKILL) };
+			unreachable!()
+		}
+	}
+}
+
+#[test]
+fn test_results() {
+	let mut worker = Worker::spawn(std::iter::empty(), run).unwrap();
+	worker.send((7, 0, 0, 0)).unwrap();
+	assert_eq!(worker.current.unwrap().0, (7, 0, 0, 0));
+	assert_eq!(worker.receive().unwrap(), Ok(7));
+	assert!(worker.current.is_none());
+	worker.send((1 << 40, 0, 0, 0)).unwrap();
+	assert_eq!(worker.receive().unwrap(), Ok(1 << 40));
+	// a panicking job doesn't take the worker with it
+	worker.send((0, 0, 0, 1)).unwrap();
+	assert_eq!(worker.receive().unwrap(), Err(String::from("improperly sorted")));
+	worker.send((3, 0, 0, 0)).unwrap();
+	assert_eq!(worker.receive().unwrap(), Ok(3));
+	worker.finish();
+}
+
+#[test]
+fn test_wait() {
+	let mut workers = vec![Option::Some(Worker::spawn(std::iter::empty(), run).unwrap())];
+	workers.push(Option::Some(Worker::spawn(workers.iter().flatten(), run).unwrap()));
+	workers.push(Option::None);
+	// idle workers are never ready
+	assert!(subprocess::wait(&workers, Duration::from_millis(10)).unwrap().is_empty());
+	workers[0].as_mut().unwrap().send((0, 0, 0, 2)).unwrap();
+	workers[1].as_mut().unwrap().send((5, 0, 0, 0)).unwrap();
+	assert_eq!(subprocess::wait(&workers, Duration::from_secs(10)).unwrap(), vec![1]);
+	assert_eq!(workers[1].as_mut().unwrap().receive().unwrap(), Ok(5));
+	// the stuck one times out
+	let start = Instant::now();
+	assert!(subprocess::wait(&workers, Duration::from_millis(50)).unwrap().is_empty());
+	assert!(start.elapsed() >= Duration::from_millis(50));
+	assert!(workers[0].as_ref().unwrap().overdue(Duration::from_millis(50)));
+	assert!(!workers[0].as_ref().unwrap().overdue(Duration::from_secs(60)));
+	workers[0].take().unwrap().kill();
+	workers[1].take().unwrap().finish();
+}
+
+#[test]
+fn test_death() {
+	let mut workers = vec![Option::Some(Worker::spawn(std::iter::empty(), run).unwrap())];
+	workers[0].as_mut().unwrap().send((0, 0, 0, 3)).unwrap();
+	assert_eq!(subprocess::wait(&workers, Duration::from_secs(10)).unwrap(), vec![0]);
+	assert!(workers[0].as_mut().unwrap().receive().is_err());
+	assert_eq!(workers[0].take().unwrap().reap(), format!("killed by signal {}", libc::SIGKILL));
+}
+
+#[test]
+fn test_idle_death() {
+	let mut worker = Worker::spawn(std::iter::empty(), run).unwrap();
+	worker.send((2, 0, 0, 0)).unwrap();
+	assert_eq!(worker.receive().unwrap(), Ok(2));
+	unsafe { libc::kill(worker.pid, libc::SIGKILL) };
+	// the job pipe has no reader left once the kill has gone through
+	let start = Instant::now();
+	while worker.send((4, 0, 0, 0)).is_ok() {
+		// it was still alive, the job never gets an answer
+		assert!(worker.receive().is_err());
+		assert!(start.elapsed() < Duration::from_secs(10));
+	}
+	assert_eq!(worker.reap(), format!("killed by signal {}", libc::SIGKILL));
+}
@@ -0,0 +1,74 @@
+use rand::rngs::SmallRng;
+use rand::{RngCore, SeedableRng};
+
+use crate::cli;
+use crate::sweep;
+use crate::utils;
+
+#[test]
+fn test_thresholds_sort() {
+	let mut rng = SmallRng::seed_from_u64(11);
+	for (name, sort) in sweep::SWEEPABLE {
+		for size in &[0, 1, 2, 31, 32, 33, 1000] {
+			for threshold in &[0, 1, 2, 16, 5000] {
+				let mut array: Vec<i32> = (0..*size).map(|_| (rng.next_u32() % 100) as i32).collect();
+				sort(&mut array, *threshold);
+				assert!(array.windows(2).all(|w| w[0] <= w[1]), "{} {} {}", name, size, threshold);
+				utils::verify_sorted(&array);
+			}
+		}
+	}
+}
+
+fn sweep_with(samples: Vec<Vec<u64>>) -> sweep::Sweep {
+	sweep::Sweep { algorithm: 0, distribution: 0, size: 1000, samples }
+}
+
+// 40 runs around base with a little noise
+fn runs(base: u64) -> Vec<u64> {
+	(0..40).map(|i| base + i % 7).collect()
+}
+
+#[test]
+fn test_optimum() {
+	let thresholds = [8, 16, 24, 32];
+	// a clear winner
+	let s = sweep_with(vec![runs(2000), runs(1000), runs(1500), runs(3000)]);
+	let o = s.optimum(&thresholds, 3.0, 1).unwrap();
+	assert_eq!(o, sweep::Optimum { threshold: 16, low: 16, high: 16 });
+	// 24 and 32 can't be told apart
+	let s = sweep_with(vec![runs(2000), runs(1500), runs(1000), runs(1000)]);
+	let o = s.optimum(&thresholds, 3.0, 1).unwrap();
+	assert!(o.threshold == 24 || o.threshold == 32);
+	assert_eq!((o.low, o.high), (24, 32));
+	// not enough runs to compare anything
+	let s = sweep_with(vec![runs(1000), vec![5; 3], vec![], vec![]]);
+	assert_eq!(s.optimum(&thresholds, 3.0, 1), Option::None);
+	// thresholds without enough runs are ignored
+	let s = sweep_with(vec![runs(2000), vec![5; 3], runs(1000), vec![]]);
+	assert_eq!(s.optimum(&thresholds, 3.0, 1).unwrap().threshold, 24);
+}
+
+#[test]
+fn test_selection() {
+	let config = cli::Config::from_profile(cli::Profile::Full);
+	assert_eq!(sweep::selection(&config), vec![0, 1, 2]);
+	let mut config = cli::Config::from_profile(cli::Profile::Full);
+	config.algorithms = vec![String::from("introsort")];
+	assert_eq!(sweep::selection(&config), vec![2]);
+	config.algorithms = vec![String::from("bubblesort")];
+	assert!(sweep::selection(&config).is_empty());
+}
+
+#[test]
+fn test_run() {
+	let mut config = cli::Config::from_profile(cli::Profile::Quick);
+	config.max_size = 100;
+	config.n_tests = 3;
+	config.thresholds = vec![4, 8];
+	config.distributions = vec![String::from("uniform"), String::from("sorted")];
+	let sweeps = sweep::run(&config, &[0, 2]);
+	// quicksort_hybrid and introsort on 2 distributions at 2 sizes
+	assert_eq!(sweeps.len(), 8);
+	assert!(sweeps.iter().all(|s| s.samples.iter().all(|t| t.len() == 3)));
+}
@@ -47,8 +47,49 @@ fn test_fmin() {
 	assert_eq!(utils::fmin(2.0, 1.0), 1.0);
 }
 
+#[test]
+fn test_panic_message() {
+	let payload = std::panic::catch_unwind(|| utils::verify_sorted(&[2, 1])).unwrap_err();
+	assert_eq!(utils::panic_message(&*payload), "improperly sorted: [2, 1]");
+	let payload = std::panic::catch_unwind(|| panic!("at the disco")).unwrap_err();
+	assert_eq!(utils::panic_message(&*payload), "at the disco");
+	let mut unsorted = vec![1; 999];
+	unsorted.push(0);
+	let payload = std::panic::catch_unwind(|| utils::verify_sorted(&unsorted)).unwrap_err();
+	let message = utils::panic_message(&*payload);
+	assert!(message.starts_with("improperly sorted: [1, 1") && message.ends_with("..."));
+	assert_eq!(message.len(), 203);
+	assert_eq!(utils::panic_message(&5), "unknown panic");
+}
+
+#[test]
+fn test_catch_quietly() {
+	assert_eq!(utils::catch_quietly(|| 5), Ok(5));
+	assert_eq!(utils::catch_quietly(|| utils::verify_sorted(&[2, 1])), Err(String::from("improperly sorted: [2, 1]")));
+	// nested, the outer one is still quiet afterwards
+	let inner = utils::catch_quietly(|| {
+		let inner = utils::catch_quietly(|| panic!("inner"));
+		panic!("outer after {:?}", inner);
+	});
+	assert_eq!(inner, Err(String::from("outer after Err(\"inner\")")));
+}
+
 #[test]
 fn test_fmax() {
 	assert_eq!(utils::fmax(1.0, 2.0), 2.0);
 	assert_eq!(utils::fmax(2.0, 1.0), 2.0);
 }
+
+#[test]
+fn test_glob_match() {
+	assert!(utils::glob_match("shellsort_*", "shellsort_knuth"));
+	assert!(utils::glob_match("*sort", "heapsort"));
+	assert!(utils::glob_match("*", ""));
+	assert!(utils::glob_match("heap*_*", "heapsort_bottom_up"));
+	assert!(utils::glob_match("quicksort_?nd", "quicksort_end"));
+	assert!(utils::glob_match("bubblesort", "bubblesort"));
+	assert!(!utils::glob_match("bubblesort", "bubblesort2"));
+	assert!(!utils::glob_match("shellsort_*", "odd_algos::shellsort_alternative_ciura"));
+	assert!(!utils::glob_match("?", ""));
+	assert!(!utils::glob_match("*merge*", "timsort"));
+}
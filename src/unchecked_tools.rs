@@ -37,3 +37,55 @@ impl<T> SliceUnchecked<T> for [T] {
 		(from_raw_parts_mut(ptr, i), from_raw_parts_mut(ptr.add(i + 1), len - i - 1))
 	}
 }
+
+// holes for moving values that aren't Copy around without two live copies of one ever being dropped
+// if a comparison panics partway through a sort, dropping the hole puts what it holds back in the
+// slice, so every element is still there exactly once
+
+// a value read out of a slice, src points at it (usually in a ManuallyDrop) and dest is the slot it
+// belongs in for now. dropping the hole writes it there
+pub struct Hole<T> {
+	pub src: *const T,
+	pub dest: *mut T
+}
+
+impl<T> Drop for Hole<T> {
+	fn drop(&mut self) {
+		unsafe {
+			std::ptr::copy_nonoverlapping(self.src, self.dest, 1);
+		}
+	}
+}
+
+// values moved out to [start, end) of a buffer and not merged back yet, dropping the hole moves them
+// back starting at dest
+pub struct MergeHole<T> {
+	pub start: *mut T,
+	pub end: *mut T,
+	pub dest: *mut T
+}
+
+impl<T> Drop for MergeHole<T> {
+	fn drop(&mut self) {
+		unsafe {
+			std::ptr::copy_nonoverlapping(self.start, self.dest, self.end.offset_from(self.start) as usize);
+		}
+	}
+}
+
+// how the move-based sorts report what they do to the elements, for --operations (see operations.rs)
+// a move is one element written somewhere new, through a hole or into a buffer, and a swap exchanges two
+// the sorts themselves are run with Uncounted, which compiles away
+pub trait Moves {
+	fn moved(&mut self, n: usize);
+	fn swapped(&mut self, n: usize);
+}
+
+pub struct Uncounted;
+
+impl Moves for Uncounted {
+	#[inline(always)]
+	fn moved(&mut self, _: usize) {}
+	#[inline(always)]
+	fn swapped(&mut self, _: usize) {}
+}
@@ -1,14 +1,8 @@
+use std::cell::Cell;
+use std::panic;
+use std::sync::Once;
 use std::time::Duration;
 
-// this macro is shorthand for adding algorithms to to benchmark
-// input: function pointer, string
-// output: tuple (fn pointer, fn name, string)
-#[macro_export] macro_rules! sfn {
-	($f:expr, $c:literal) => {
-		(Option::Some($f), (&Regex::new("::<.+>$").unwrap().replace(stringify!($f), "")).to_string(), $c)
-	};
-}
-
 // this macro assists with extending the lifetime of an object
 // it is short for extend_lifetime(LifetimeContainer(obj)).0
 pub struct LifetimeContainer<'a, T>(pub &'a T);
@@ -46,6 +40,22 @@ pub fn set_thread_priority_max() {
 	winproc::Thread::current().set_priority(winproc::PriorityLevel::TimeCritical).unwrap();
 }
 
+// best-effort description of the cpu, used in result metadata
+#[cfg(not(tarpaulin_include))]
+pub fn cpu_model() -> String {
+	if let Ok(cpuinfo) = std::fs::read_to_string("/proc/cpuinfo") {
+		for line in cpuinfo.lines() {
+			// older raspberry pi kernels only have a "Model" line
+			if line.starts_with("model name") || line.starts_with("Model") {
+				if let Option::Some(colon) = line.find(':') {
+					return String::from(line[colon + 1..].trim());
+				}
+			}
+		}
+	}
+	std::env::var("PROCESSOR_IDENTIFIER").unwrap_or(String::from("unknown"))
+}
+
 // returns number with comma separators (i.e. 1000000 -> "1,000,000")
 // note: this method fails for num == 0 - it prints "x". I'm leaving it for now.
 pub fn commafy(mut num: usize) -> String {
@@ -99,6 +109,44 @@ pub fn verify_sorted<T: Ord + std::fmt::Debug>(array: &[T]) {
 	}
 }
 
+// the message a panic was raised with, cut short since failed sortedness checks print whole arrays
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+	let message = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+		(Option::Some(message), _) => message,
+		(_, Option::Some(message)) => message.as_str(),
+		_ => "unknown panic"
+	};
+	match message.char_indices().nth(200) {
+		Option::Some((i, _)) => format!("{}...", &message[..i]),
+		Option::None => String::from(message)
+	}
+}
+
+thread_local! {
+	// set while catch_quietly is running something on this thread
+	static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+// catch_unwind with the panic message cut short (panic_message) and without the panic hook printing
+// the whole thing first, the caller reports it instead
+// the hook is replaced once with one that stays quiet inside catch_quietly and defers to the previous
+// hook everywhere else
+pub fn catch_quietly<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+	static INSTALL_HOOK: Once = Once::new();
+	INSTALL_HOOK.call_once(|| {
+		let previous = panic::take_hook();
+		panic::set_hook(Box::new(move |info| {
+			if !QUIET.with(Cell::get) {
+				previous(info);
+			}
+		}));
+	});
+	let was_quiet = QUIET.with(|quiet| quiet.replace(true));
+	let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+	QUIET.with(|quiet| quiet.set(was_quiet));
+	result.map_err(|payload| panic_message(&*payload))
+}
+
 pub fn fmin<T: PartialOrd>(f0: T, f1: T) -> T {
 	// assuming neither is NaN
 	if f0 < f1 {
@@ -122,3 +170,30 @@ pub fn compare_and_swap<T: Ord + Copy>(a: &mut T, b: &mut T) {
 		std::mem::swap(a, b);
 	}
 }
+
+// shell-style glob matching supporting * (any run of characters) and ? (any single character)
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+	let pattern: Vec<char> = pattern.chars().collect();
+	let text: Vec<char> = text.chars().collect();
+	let mut p = 0;
+	let mut t = 0;
+	// position of the last * seen and the text position it was tried at, for backtracking
+	let mut star: Option<(usize, usize)> = Option::None;
+	while t < text.len() {
+		if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+			p += 1;
+			t += 1;
+		} else if p < pattern.len() && pattern[p] == '*' {
+			star = Option::Some((p, t));
+			p += 1;
+		} else if let Option::Some((star_p, star_t)) = star {
+			// let the last * absorb one more character
+			p = star_p + 1;
+			t = star_t + 1;
+			star = Option::Some((star_p, star_t + 1));
+		} else {
+			return false;
+		}
+	}
+	pattern[p..].iter().all(|c| *c == '*')
+}